@@ -20,6 +20,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, discriminator, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 const crypto = require('crypto');
 
 // Connect to local Solana node
@@ -34,9 +35,9 @@ const wallet = Keypair.fromSecretKey(secretKey);
 // Program ID
 const PROGRAM_ID = new PublicKey('CYUSvq2vmNZ4rcyhfKyfaTKvfeH3doxyqx69ifV3w3TP');
 
-// Discriminators from test-fixed.js
-const DISCRIMINATOR_INITIALIZE = Buffer.from([30, 120, 39, 212, 120, 168, 29, 81]); // "global:initializeLaunch"
-const DISCRIMINATOR_BUY = Buffer.from([103, 17, 200, 25, 118, 95, 125, 61]); // "global:buy"
+// Discriminators, as Anchor derives them from the instruction names
+const DISCRIMINATOR_INITIALIZE = discriminator('initialize_launch'); // "global:initialize_launch"
+const DISCRIMINATOR_BUY = discriminator('buy'); // "global:buy"
 
 // Generate discriminator
 function generateDiscriminator(namespace, name) {
@@ -81,76 +82,61 @@ async function main() {
   );
   console.log("Vault SOL PDA:", vaultSolPda.toString());
   
-  // Create vault ATA
-  console.log("\nCreating vault ATA...");
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  // Vault ATA and the creator's token account
+  console.log("\nCreating creator token account...");
+  const { vaultAta } = launchPdas(PROGRAM_ID, mint);
+  // initialize_launch creates the vault ATA; the supply goes to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     wallet,
     mint,
-    authorityPda,
-    true
+    wallet.publicKey
   );
-  console.log("Vault ATA:", vaultAta.address.toString());
+  console.log("Vault ATA:", vaultAta.toString());
   
-  // Mint initial supply to vault
-  console.log("\nMinting initial supply to vault...");
+  // Mint initial supply to the creator
+  console.log("\nMinting initial supply to the creator...");
   const initialSupply = 1_000_000_000_000; // 1000 tokens with 9 decimals
   await mintTo(
     connection,
     wallet,
     mint,
-    vaultAta.address,
+    creatorAta.address,
     wallet.publicKey,
     initialSupply
   );
-  console.log(`Minted ${initialSupply} tokens to vault`);
+  console.log(`Minted ${initialSupply} tokens to the creator`);
   
-  // Use the known working discriminator from test-fixed.js
-  console.log("\nUsing discriminator from test-fixed.js");
+  // initialize_launch's discriminator
+  console.log("\nUsing discriminator for initialize_launch");
   console.log(`Discriminator: [${Array.from(DISCRIMINATOR_INITIALIZE)}]`);
   
   try {
-    // Launch parameters matching lib.rs
+    // Launch parameters
     const basePriceLamports = 10_000_000; // 0.01 SOL
     const slopeLamports = 100_000; // 0.0001 SOL per token
-    const feeBps = 50; // 0.5%
     const creatorFeeBps = 250; // 2.5%
     const graduationTargetLamports = 10_000_000_000; // 10 SOL
     
-    // Construct initialization data
-    const initData = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-    DISCRIMINATOR_INITIALIZE.copy(initData, 0);
-    
-    // Write parameters
-    let offset = 8;
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-    initData.writeUInt16LE(feeBps, offset); offset += 2;
-    initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-    initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-    
-    // Use account structure from lib.rs
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: initData
-    };
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    const fundIx = fundCurveIx(PROGRAM_ID, {
+      creator: wallet.publicKey,
+      mint,
+      amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+    });
     
     console.log("\nSending initialization transaction...");
-    const initTx = new Transaction().add(initIx);
+    const initTx = new Transaction().add(initIx, fundIx);
     
     const initSig = await sendAndConfirmTransaction(
       connection, 
@@ -194,7 +180,7 @@ async function main() {
       authorityPda: authorityPda.toString(),
       statePda: statePda.toString(),
       vaultSolPda: vaultSolPda.toString(),
-      vaultAta: vaultAta.address.toString(),
+      vaultAta: vaultAta.toString(),
       discriminator: Array.from(DISCRIMINATOR_INITIALIZE),
       signature: initSig,
       timestamp: new Date().toISOString()
@@ -4,7 +4,8 @@
  * This script addresses:
  * 1. The "InstructionFallbackNotFound" (Code 101) error by using the correct discriminator
  * 2. The "Provided owner is not allowed" error by ensuring correct account structure
- * 3. Uses the correct parameter structure from instructions/initialize_launch.rs
+ * 3. Builds initialize_launch and fund_curve with scripts/grokpad-ix.js, which
+ *    follows the program's current accounts and arguments
  */
 const { 
   Connection, 
@@ -26,6 +27,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, discriminator, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 
 // Connect to local Solana node
 const connection = new Connection('http://localhost:8899', 'confirmed');
@@ -42,9 +44,9 @@ console.log("Using wallet:", wallet.publicKey.toString());
 const PROGRAM_ID = new PublicKey('CYUSvq2vmNZ4rcyhfKyfaTKvfeH3doxyqx69ifV3w3TP');
 console.log("Program ID:", PROGRAM_ID.toString());
 
-// CORRECT Instruction discriminator for initialize_launch
+// Instruction discriminator for initialize_launch
 // SHA-256("global:initialize_launch")[0:8]
-const DISCRIMINATOR_INITIALIZE = Buffer.from([90, 201, 220, 142, 112, 253, 100, 13]);
+const DISCRIMINATOR_INITIALIZE = discriminator('initialize_launch');
 
 // Configuration
 const USE_EXISTING_MINT = false;
@@ -70,56 +72,36 @@ async function main() {
       console.log('Mint created:', mint.toString());
     }
     
-    // Derive PDAs
-    const mintBuffer = mint.toBuffer();
+    // Derive PDAs; initialize_launch creates the vault ATA itself
+    const { authorityPda: launchPda, statePda, vaultSolPda: solVaultPda, vaultAta } = launchPdas(PROGRAM_ID, mint);
+    console.log("Launch authority PDA:", launchPda.toString());
+    console.log("Launch state PDA:", statePda.toString());
+    console.log("SOL vault PDA:", solVaultPda.toString());
+    console.log("Vault ATA:", vaultAta.toString());
     
-    // Find launch PDA (used in initialize_launch.rs)
-    const [launchPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("launch"), mintBuffer],
-      PROGRAM_ID
-    );
-    console.log("Launch PDA:", launchPda.toString());
-    
-    // Find sol_vault PDA (used in initialize_launch.rs)
-    const [solVaultPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("sol_vault"), mintBuffer],
-      PROGRAM_ID
-    );
-    console.log("SOL Vault PDA:", solVaultPda.toString());
-    
-    // Get or create vault ATA
-    const vaultAta = await getOrCreateAssociatedTokenAccount(
+    // Get buyer's ATA, which is also the creator's
+    const buyerAta = await getOrCreateAssociatedTokenAccount(
       connection,
       wallet,
       mint,
-      launchPda,
-      true
+      wallet.publicKey
     );
-    console.log('Vault ATA:', vaultAta.address.toString());
+    console.log("Buyer ATA:", buyerAta.address.toString());
     
-    // If not using existing mint, mint initial supply
+    // If not using existing mint, mint initial supply to the creator
     if (!USE_EXISTING_MINT) {
-      console.log('Minting initial supply...');
+      console.log("Minting initial supply...");
       await mintTo(
         connection,
         wallet,
         mint,
-        vaultAta.address,
+        buyerAta.address,
         wallet.publicKey,
-        1_000_000_000 // 1000 tokens with 6 decimals
+        1_000_000_000_000 // 1000 tokens with 9 decimals
       );
-      console.log('Initial supply minted');
+      console.log("Initial supply minted");
     }
     
-    // Get buyer's ATA
-    const buyerAta = await getOrCreateAssociatedTokenAccount(
-      connection,
-      wallet,
-      mint,
-      wallet.publicKey
-    );
-    console.log('Buyer ATA:', buyerAta.address.toString());
-    
     // Calculate minimum rent-exempt SOL amount
     const rentExemptAmount = await connection.getMinimumBalanceForRentExemption(0);
     console.log(`\nRent exempt amount: ${rentExemptAmount} lamports`);
@@ -149,8 +131,8 @@ async function main() {
       console.log(`SOL Vault PDA balance: ${vaultInfo.lamports} lamports`);
     }
     
-    // Check if launch account exists
-    const launchInfo = await connection.getAccountInfo(launchPda);
+    // Check if launch state account exists
+    const launchInfo = await connection.getAccountInfo(statePda);
     
     if (launchInfo) {
       console.log('Launch account exists with size:', launchInfo.data.length);
@@ -158,82 +140,34 @@ async function main() {
     } else {
       console.log('Launch account does not exist, initializing launch...');
       
-      // Initialize launch parameters based on instructions/initialize_launch.rs
-      const name = "test_grok"; // Must end with "grok" per the code requirement
-      const symbol = "TGROK";
-      
-      // Price state parameters
-      const basePriceLamports = 1_000_000; // 0.001 SOL
-      const slopeLamports = 100_000; // 0.0001 SOL per token
-      const feeBps = 300; // 3.00%
+      // Launch parameters. The platform fee and its recipient come from
+      // the global config.
+      const basePriceLamports = 1_000_000; // 0.001 SOL per whole token
+      const slopeLamports = 100_000; // +0.0001 SOL per whole token sold
       const creatorFeeBps = 100; // 1.00%
       const graduationTargetLamports = 2 * LAMPORTS_PER_SOL; // 2 SOL
       
-      // Name and symbol as UTF-8 bytes
-      const nameBytes = Buffer.from(name, 'utf-8');
-      const symbolBytes = Buffer.from(symbol, 'utf-8');
-      
-      // Calculate data size for initialize_launch in instructions/initialize_launch.rs
-      // 8 bytes discriminator + 
-      // 4+name.length + 
-      // 4+symbol.length + 
-      // PriceState struct (8+8+2+2+8 = 28 bytes)
-      const dataSize = 8 + 4 + nameBytes.length + 4 + symbolBytes.length + 28;
-      
-      // Construct initialization data
-      const initData = Buffer.alloc(dataSize);
-      
-      // Write discriminator
-      DISCRIMINATOR_INITIALIZE.copy(initData, 0);
-      
-      // Prepare the rest of the data based on instructions/initialize_launch.rs
-      let offset = 8;
-      
-      // Write name string (Borsh format: size as u32 LE + bytes)
-      initData.writeUInt32LE(nameBytes.length, offset);
-      offset += 4;
-      nameBytes.copy(initData, offset);
-      offset += nameBytes.length;
+      console.log("Using discriminator:", Array.from(DISCRIMINATOR_INITIALIZE));
       
-      // Write symbol string (Borsh format: size as u32 LE + bytes)
-      initData.writeUInt32LE(symbolBytes.length, offset);
-      offset += 4;
-      symbolBytes.copy(initData, offset);
-      offset += symbolBytes.length;
-      
-      // Write PriceState struct
-      initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-      initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-      initData.writeUInt16LE(feeBps, offset); offset += 2;
-      initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-      initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-      
-      // Create a dummy global config account for testing
-      // In a real scenario, this account would need to be properly initialized
-      const globalConfigPda = wallet.publicKey; // Using wallet for testing
-      
-      console.log("\nBuilding account structure based on instructions/initialize_launch.rs...");
-      
-      // Create instruction with account structure from instructions/initialize_launch.rs
-      const initIx = {
-        programId: PROGRAM_ID,
-        keys: [
-          { pubkey: globalConfigPda, isSigner: false, isWritable: true }, // global_config
-          { pubkey: wallet.publicKey, isSigner: true, isWritable: false }, // admin
-          { pubkey: launchPda, isSigner: false, isWritable: true }, // launch
-          { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // creator
-          { pubkey: mint, isSigner: false, isWritable: true }, // mint
-          { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // curve_vault
-          { pubkey: solVaultPda, isSigner: false, isWritable: true }, // sol_vault
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false } // system_program
-        ],
-        data: initData
-      };
+      // Initialize, then move the supply into the vault to open the curve
+      const initIx = initializeLaunchIx(PROGRAM_ID, {
+        payer: wallet.publicKey,
+        creator: wallet.publicKey,
+        mint,
+        basePriceLamports,
+        slopeLamports,
+        creatorFeeBps,
+        graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+      });
+      const fundIx = fundCurveIx(PROGRAM_ID, {
+        creator: wallet.publicKey,
+        mint,
+        amount: (await connection.getTokenAccountBalance(buyerAta.address)).value.amount,
+      });
       
       // Send transaction
       console.log('Sending initialization transaction...');
-      const initTx = new Transaction().add(initIx);
+      const initTx = new Transaction().add(initIx, fundIx);
       
       try {
         const initSig = await sendAndConfirmTransaction(
@@ -246,8 +180,8 @@ async function main() {
         console.log("\n✅ SUCCESS! Initialization successful.");
         console.log(`Transaction signature: ${initSig}`);
         
-        // Check if the launch account was created
-        const launchAccount = await connection.getAccountInfo(launchPda);
+        // Check if the launch state account was created
+        const launchAccount = await connection.getAccountInfo(statePda);
         console.log("Launch account created:", !!launchAccount);
         if (launchAccount) {
           console.log("Launch account size:", launchAccount.data.length, "bytes");
@@ -258,8 +192,9 @@ async function main() {
           programId: PROGRAM_ID.toString(),
           mint: mint.toString(),
           launchPda: launchPda.toString(),
+          statePda: statePda.toString(),
           solVaultPda: solVaultPda.toString(),
-          vaultAta: vaultAta.address.toString(),
+          vaultAta: vaultAta.toString(),
           buyerAta: buyerAta.address.toString(),
           discriminator: Array.from(DISCRIMINATOR_INITIALIZE)
         };
@@ -0,0 +1,282 @@
+//! Bonding-curve pricing, fee splitting and trade quotes for grokpad, plus
+//! the constant-product pool launches move to after graduation.
+//!
+//! The on-chain program prices every trade with this crate, so clients that
+//! quote from the same launch state get exactly the on-chain numbers.
+//!
+//! Every operation is checked. Intermediates that can exceed `u128` are
+//! carried in a 256-bit integer, and anything that does not fit is reported
+//! as a [`MathOverflow`] naming the step that failed instead of saturating.
+
+#![no_std]
+
+mod pool;
+mod quote;
+
+pub use pool::*;
+pub use quote::*;
+
+#[allow(clippy::all)]
+mod wide {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+pub use wide::U256;
+
+/// Basis-point denominator for `fee_bps` and `creator_fee_bps`.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// A checked operation left its range. `op` names the step that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MathOverflow {
+    pub op: &'static str,
+}
+
+pub type MathResult<T> = core::result::Result<T, MathOverflow>;
+
+/// Which way a lamport amount that is not whole gets rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Up,
+    Down,
+}
+
+/// Fee breakdown of a gross lamport amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub net: u64,
+}
+
+fn wide_mul(a: U256, b: U256, op: &'static str) -> MathResult<U256> {
+    a.checked_mul(b).ok_or(MathOverflow { op })
+}
+
+fn wide_add(a: U256, b: U256, op: &'static str) -> MathResult<U256> {
+    a.checked_add(b).ok_or(MathOverflow { op })
+}
+
+fn wide_sub(a: U256, b: U256, op: &'static str) -> MathResult<U256> {
+    a.checked_sub(b).ok_or(MathOverflow { op })
+}
+
+fn to_u64(v: U256, op: &'static str) -> MathResult<u64> {
+    if v > U256::from(u64::MAX) {
+        return Err(MathOverflow { op });
+    }
+    Ok(v.as_u64())
+}
+
+/// `base_price*scale + slope*sold`: the marginal price at `sold`, times `scale`.
+fn scaled_price(base_price: u64, slope: u64, scale: u64, sold: u64) -> MathResult<U256> {
+    let base = wide_mul(U256::from(base_price), U256::from(scale), "scaled price")?;
+    let climb = wide_mul(U256::from(slope), U256::from(sold), "scaled price")?;
+    wide_add(base, climb, "scaled price")
+}
+
+/// Lamports for moving `amount` base units along the curve starting at
+/// `sold_before` base units sold. A whole token is priced at
+/// `base_price + slope * sold / scale`, integrated per base unit:
+///
+/// cost = (2*base_price*scale*amount + slope*amount*(2*sold_before + amount)) / (2*scale^2)
+///
+/// Buys round up and sells round down, so the pool never pays out more than
+/// it took in for the same stretch of the curve.
+pub fn curve_cost(
+    base_price: u64,
+    slope: u64,
+    scale: u64,
+    sold_before: u64,
+    amount: u64,
+    rounding: Rounding,
+) -> MathResult<u64> {
+    let amount_w = U256::from(amount);
+    let base_term = wide_mul(
+        wide_mul(U256::from(base_price), U256::from(scale), "curve cost")?,
+        wide_mul(amount_w, U256::from(2u8), "curve cost")?,
+        "curve cost",
+    )?;
+    let span = wide_add(
+        wide_mul(U256::from(sold_before), U256::from(2u8), "curve cost")?,
+        amount_w,
+        "curve cost",
+    )?;
+    let slope_term = wide_mul(wide_mul(U256::from(slope), amount_w, "curve cost")?, span, "curve cost")?;
+    let numerator = wide_add(base_term, slope_term, "curve cost")?;
+    let denominator = wide_mul(
+        wide_mul(U256::from(scale), U256::from(scale), "curve cost")?,
+        U256::from(2u8),
+        "curve cost",
+    )?;
+    if denominator.is_zero() {
+        return Err(MathOverflow { op: "curve cost" });
+    }
+    let (mut cost, rem) = numerator.div_mod(denominator);
+    if rounding == Rounding::Up && !rem.is_zero() {
+        cost = wide_add(cost, U256::one(), "curve cost")?;
+    }
+    to_u64(cost, "curve cost")
+}
+
+/// Largest `amount`, at most `max_amount`, whose `Rounding::Up` cost from
+/// `sold_before` fits in `budget`.
+pub fn amount_for_cost(
+    base_price: u64,
+    slope: u64,
+    scale: u64,
+    sold_before: u64,
+    budget: u64,
+    max_amount: u64,
+) -> MathResult<u64> {
+    // cost <= budget  <=>  slope*a^2 + 2*q*a <= 2*scale^2*budget
+    // with q = base_price*scale + slope*sold_before
+    let q = scaled_price(base_price, slope, scale, sold_before)?;
+    let rhs = wide_mul(
+        wide_mul(U256::from(scale), U256::from(scale), "inverse buy")?,
+        wide_mul(U256::from(budget), U256::from(2u8), "inverse buy")?,
+        "inverse buy",
+    )?;
+    let estimate = if slope == 0 {
+        let denominator = wide_mul(q, U256::from(2u8), "inverse buy")?;
+        if denominator.is_zero() {
+            return Err(MathOverflow { op: "inverse buy" });
+        }
+        rhs / denominator
+    } else {
+        let disc = wide_add(
+            wide_mul(q, q, "inverse buy")?,
+            wide_mul(U256::from(slope), rhs, "inverse buy")?,
+            "inverse buy",
+        )?;
+        wide_sub(disc.integer_sqrt(), q, "inverse buy")? / U256::from(slope)
+    };
+    let mut amount = if estimate > U256::from(max_amount) { max_amount } else { estimate.as_u64() };
+    let fits = |amount: u64| {
+        curve_cost(base_price, slope, scale, sold_before, amount, Rounding::Up).is_ok_and(|cost| cost <= budget)
+    };
+    // Guard against the floored root landing one step off
+    while amount > 0 && !fits(amount) {
+        amount -= 1;
+    }
+    while amount < max_amount && fits(amount + 1) {
+        amount += 1;
+    }
+    Ok(amount)
+}
+
+/// Smallest `amount` whose `Rounding::Down` refund, selling back from
+/// `sold` base units sold, is at least `target`. `None` if selling
+/// everything sold so far still refunds less.
+pub fn amount_for_refund(
+    base_price: u64,
+    slope: u64,
+    scale: u64,
+    sold: u64,
+    target: u64,
+) -> MathResult<Option<u64>> {
+    let refund = |amount: u64| curve_cost(base_price, slope, scale, sold - amount, amount, Rounding::Down);
+    if refund(sold)? < target {
+        return Ok(None);
+    }
+    // refund >= target  <=>  2*q*a - slope*a^2 >= 2*scale^2*target
+    // with q = base_price*scale + slope*sold; increasing for a <= sold
+    let q = scaled_price(base_price, slope, scale, sold)?;
+    let rhs = wide_mul(
+        wide_mul(U256::from(scale), U256::from(scale), "inverse sell")?,
+        wide_mul(U256::from(target), U256::from(2u8), "inverse sell")?,
+        "inverse sell",
+    )?;
+    let estimate = if slope == 0 {
+        let denominator = wide_mul(q, U256::from(2u8), "inverse sell")?;
+        if denominator.is_zero() {
+            return Err(MathOverflow { op: "inverse sell" });
+        }
+        let (quot, rem) = rhs.div_mod(denominator);
+        if rem.is_zero() { quot } else { quot + U256::one() }
+    } else {
+        // Feasibility above guarantees q^2 >= slope*rhs
+        let slope_w = U256::from(slope);
+        let disc = wide_sub(wide_mul(q, q, "inverse sell")?, wide_mul(slope_w, rhs, "inverse sell")?, "inverse sell")?;
+        let (quot, rem) = wide_sub(q, disc.integer_sqrt(), "inverse sell")?.div_mod(slope_w);
+        if rem.is_zero() { quot } else { quot + U256::one() }
+    };
+    let mut amount = if estimate > U256::from(sold) { sold } else { estimate.as_u64() };
+    while amount < sold && refund(amount)? < target {
+        amount += 1;
+    }
+    while amount > 0 && refund(amount - 1)? >= target {
+        amount -= 1;
+    }
+    Ok(Some(amount))
+}
+
+/// Marginal price of a whole token once `sold` base units are sold, in
+/// lamports, rounded down.
+pub fn spot_price(base_price: u64, slope: u64, scale: u64, sold: u64) -> MathResult<u64> {
+    if scale == 0 {
+        return Err(MathOverflow { op: "spot price" });
+    }
+    let price = scaled_price(base_price, slope, scale, sold)? / U256::from(scale);
+    to_u64(price, "spot price")
+}
+
+/// Value of `supply` base units at the marginal price once `sold` base units
+/// are sold, in lamports, rounded down.
+pub fn market_cap(base_price: u64, slope: u64, scale: u64, sold: u64, supply: u64) -> MathResult<u64> {
+    let value = wide_mul(scaled_price(base_price, slope, scale, sold)?, U256::from(supply), "market cap")?;
+    let denominator = wide_mul(U256::from(scale), U256::from(scale), "market cap")?;
+    if denominator.is_zero() {
+        return Err(MathOverflow { op: "market cap" });
+    }
+    to_u64(value / denominator, "market cap")
+}
+
+/// Splits `gross` into platform fee, creator fee and the remainder. Each fee
+/// is rounded down.
+pub fn split_fees(gross: u64, fee_bps: u16, creator_fee_bps: u16) -> MathResult<FeeSplit> {
+    let bps_of = |bps: u16| (gross as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    let platform_fee = bps_of(fee_bps);
+    let creator_fee = bps_of(creator_fee_bps);
+    let net = gross
+        .checked_sub(platform_fee)
+        .and_then(|v| v.checked_sub(creator_fee))
+        .ok_or(MathOverflow { op: "fee split" })?;
+    Ok(FeeSplit { platform_fee, creator_fee, net })
+}
+
+/// Largest base amount that, with `split_fees`' fees added on top, still fits
+/// in `total`. Starts from `total / (1 + fees)`, which always fits.
+pub fn base_for_total(total: u64, fee_bps: u16, creator_fee_bps: u16) -> MathResult<u64> {
+    let fee_total_bps = fee_bps as u128 + creator_fee_bps as u128;
+    let with_fees = |base: u64| -> MathResult<u128> {
+        let fees = split_fees(base, fee_bps, creator_fee_bps)?;
+        Ok(base as u128 + fees.platform_fee as u128 + fees.creator_fee as u128)
+    };
+    let mut base = (total as u128 * BPS_DENOMINATOR as u128 / (BPS_DENOMINATOR as u128 + fee_total_bps)) as u64;
+    // Floored fees can leave room for a few lamports more
+    while base < total && with_fees(base + 1)? <= total as u128 {
+        base += 1;
+    }
+    Ok(base)
+}
+
+/// Smallest gross whose `split_fees` remainder is at least `net`, searching
+/// down from the bound `ceil(net / (1 - fees))` that always suffices.
+pub fn gross_for_net(net: u64, fee_bps: u16, creator_fee_bps: u16) -> MathResult<u64> {
+    let fee_total_bps = fee_bps as u128 + creator_fee_bps as u128;
+    if fee_total_bps >= BPS_DENOMINATOR as u128 {
+        return Err(MathOverflow { op: "gross for net" });
+    }
+    let bound = (net as u128 * BPS_DENOMINATOR as u128).div_ceil(BPS_DENOMINATOR as u128 - fee_total_bps);
+    if bound > u64::MAX as u128 {
+        return Err(MathOverflow { op: "gross for net" });
+    }
+    let mut gross = bound as u64;
+    // Floored fees can let a few lamports less through as well
+    while gross > 1 && split_fees(gross - 1, fee_bps, creator_fee_bps)?.net >= net {
+        gross -= 1;
+    }
+    Ok(gross)
+}
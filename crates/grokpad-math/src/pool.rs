@@ -0,0 +1,98 @@
+//! Constant-product pool a launch trades in after graduation.
+//!
+//! Swaps keep `sol_reserves * token_reserves` from shrinking: outputs round
+//! down, and the fee is taken in lamports outside the reserves.
+
+use crate::{split_fees, MathOverflow, QuoteError};
+
+/// Reserves and fee of a graduated launch's pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolState {
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub fee_bps: u16,
+}
+
+impl PoolState {
+    /// Marginal price of a whole token of `scale` base units, in lamports,
+    /// rounded down. Zero while the pool holds no tokens.
+    pub fn spot_price(&self, scale: u64) -> Result<u64, MathOverflow> {
+        if self.token_reserves == 0 {
+            return Ok(0);
+        }
+        let price = self.sol_reserves as u128 * scale as u128 / self.token_reserves as u128;
+        u64::try_from(price).map_err(|_| MathOverflow { op: "pool spot price" })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Lamports or base units the trader puts in, fee included.
+    pub amount_in: u64,
+    /// Base units or lamports the trader receives, fee deducted.
+    pub amount_out: u64,
+    /// Lamports kept as the pool fee.
+    pub fee: u64,
+    pub after: PoolState,
+}
+
+/// `reserve_out * amount_in / (reserve_in + amount_in)`, rounded down.
+fn swap_output(reserve_in: u64, reserve_out: u64, amount_in: u64) -> u64 {
+    let denominator = reserve_in as u128 + amount_in as u128;
+    let out = reserve_out as u128 * amount_in as u128 / denominator;
+    // Strictly below `reserve_out`, so it fits
+    out as u64
+}
+
+fn require_seeded(pool: &PoolState) -> Result<(), QuoteError> {
+    if pool.sol_reserves == 0 || pool.token_reserves == 0 {
+        return Err(QuoteError::EmptyPool);
+    }
+    Ok(())
+}
+
+/// Quotes swapping `lamports_in` for tokens. The fee comes off the input
+/// before it reaches the pool.
+pub fn quote_swap_sol_in(pool: &PoolState, lamports_in: u64) -> Result<SwapQuote, QuoteError> {
+    if lamports_in == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    require_seeded(pool)?;
+    let fees = split_fees(lamports_in, pool.fee_bps, 0)?;
+    let amount_out = swap_output(pool.sol_reserves, pool.token_reserves, fees.net);
+    if amount_out == 0 {
+        return Err(QuoteError::ZeroOutput);
+    }
+    let after = PoolState {
+        sol_reserves: pool
+            .sol_reserves
+            .checked_add(fees.net)
+            .ok_or(QuoteError::Math(MathOverflow { op: "pool reserves" }))?,
+        token_reserves: pool.token_reserves - amount_out,
+        ..*pool
+    };
+    Ok(SwapQuote { amount_in: lamports_in, amount_out, fee: fees.platform_fee, after })
+}
+
+/// Quotes swapping `tokens_in` base units for lamports. The fee comes off
+/// the lamports the pool releases.
+pub fn quote_swap_tokens_in(pool: &PoolState, tokens_in: u64) -> Result<SwapQuote, QuoteError> {
+    if tokens_in == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    require_seeded(pool)?;
+    let gross = swap_output(pool.token_reserves, pool.sol_reserves, tokens_in);
+    let fees = split_fees(gross, pool.fee_bps, 0)?;
+    if fees.net == 0 {
+        return Err(QuoteError::ZeroOutput);
+    }
+    let after = PoolState {
+        sol_reserves: pool.sol_reserves - gross,
+        token_reserves: pool
+            .token_reserves
+            .checked_add(tokens_in)
+            .ok_or(QuoteError::Math(MathOverflow { op: "pool reserves" }))?,
+        ..*pool
+    };
+    Ok(SwapQuote { amount_in: tokens_in, amount_out: fees.net, fee: fees.platform_fee, after })
+}
@@ -0,0 +1,231 @@
+//! Trade quotes over a launch's curve state.
+//!
+//! A quote carries the launch's state after the trade, so the program can
+//! settle by writing `after` back instead of redoing the bookkeeping.
+//!
+//! Fees never pass through `reserves_lamports`. A buy pays them on top of
+//! the curve cost and only the curve cost enters reserves; a sell takes its
+//! full curve value out of reserves and the fees come out of the seller's
+//! share. Reserves therefore always cover selling every sold token back.
+
+use crate::{
+    amount_for_cost, amount_for_refund, base_for_total, curve_cost, gross_for_net, market_cap, split_fees, spot_price, MathOverflow,
+    Rounding,
+};
+
+/// The part of a launch's state that pricing reads and trades change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CurveState {
+    pub base_price_lamports: u64,
+    pub slope_lamports: u64,
+    pub decimals: u8,
+    pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub supply_remaining: u64,
+    pub tokens_sold: u64,
+    pub reserves_lamports: u64,
+    pub platform_fee_accrued: u64,
+    pub creator_fee_accrued: u64,
+}
+
+impl CurveState {
+    /// Base units per whole token.
+    pub fn scale(&self) -> Result<u64, MathOverflow> {
+        10u64.checked_pow(self.decimals as u32).ok_or(MathOverflow { op: "decimals scale" })
+    }
+
+    /// Marginal price of a whole token at `tokens_sold`, in lamports.
+    pub fn spot_price(&self) -> Result<u64, MathOverflow> {
+        spot_price(self.base_price_lamports, self.slope_lamports, self.scale()?, self.tokens_sold)
+    }
+
+    /// `total_supply` base units valued at the marginal price, in lamports.
+    pub fn market_cap(&self, total_supply: u64) -> Result<u64, MathOverflow> {
+        market_cap(self.base_price_lamports, self.slope_lamports, self.scale()?, self.tokens_sold, total_supply)
+    }
+}
+
+/// Why a trade can't be quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteError {
+    /// Requested amount was zero.
+    ZeroAmount,
+    /// Buy asks for more than `supply_remaining`.
+    NotEnoughSupply,
+    /// Sell returns more than `tokens_sold`.
+    NotEnoughSold,
+    /// The trade rounds down to nothing.
+    ZeroOutput,
+    /// Reserves can't cover the refund.
+    InsufficientReserves,
+    /// `fee_bps + creator_fee_bps` leave nothing for the trader.
+    FeeTooHigh,
+    /// The pool has not been seeded with both sides.
+    EmptyPool,
+    Math(MathOverflow),
+}
+
+impl From<MathOverflow> for QuoteError {
+    fn from(e: MathOverflow) -> Self {
+        QuoteError::Math(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// Tokens delivered, in base units.
+    pub amount: u64,
+    /// Lamports the buyer pays: the curve cost plus fees on top.
+    pub cost: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub after: CurveState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SellQuote {
+    /// Tokens returned to the curve, in base units.
+    pub amount: u64,
+    /// Curve value of `amount`, taken out of reserves.
+    pub refund_gross: u64,
+    /// Lamports paid to the seller.
+    pub refund_net: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub after: CurveState,
+}
+
+fn add(a: u64, b: u64, op: &'static str) -> Result<u64, QuoteError> {
+    a.checked_add(b).ok_or(QuoteError::Math(MathOverflow { op }))
+}
+
+/// Quotes buying exactly `amount` base units.
+pub fn quote_buy(state: &CurveState, amount: u64) -> Result<BuyQuote, QuoteError> {
+    if amount == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    if amount > state.supply_remaining {
+        return Err(QuoteError::NotEnoughSupply);
+    }
+    let curve = curve_cost(
+        state.base_price_lamports,
+        state.slope_lamports,
+        state.scale()?,
+        state.tokens_sold,
+        amount,
+        Rounding::Up,
+    )?;
+    let fees = split_fees(curve, state.fee_bps, state.creator_fee_bps)?;
+    let cost = add(add(curve, fees.platform_fee, "buy cost")?, fees.creator_fee, "buy cost")?;
+    let after = CurveState {
+        supply_remaining: state.supply_remaining - amount,
+        tokens_sold: add(state.tokens_sold, amount, "tokens sold")?,
+        reserves_lamports: add(state.reserves_lamports, curve, "reserves")?,
+        platform_fee_accrued: add(state.platform_fee_accrued, fees.platform_fee, "platform fees")?,
+        creator_fee_accrued: add(state.creator_fee_accrued, fees.creator_fee, "creator fees")?,
+        ..*state
+    };
+    Ok(BuyQuote { amount, cost, platform_fee: fees.platform_fee, creator_fee: fees.creator_fee, after })
+}
+
+/// Quotes the largest buy that `lamports_in` pays for, capped at the
+/// remaining supply. The quoted cost never exceeds `lamports_in`.
+pub fn quote_buy_exact_in(state: &CurveState, lamports_in: u64) -> Result<BuyQuote, QuoteError> {
+    if lamports_in == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    let budget = base_for_total(lamports_in, state.fee_bps, state.creator_fee_bps)?;
+    let amount = amount_for_cost(
+        state.base_price_lamports,
+        state.slope_lamports,
+        state.scale()?,
+        state.tokens_sold,
+        budget,
+        state.supply_remaining,
+    )?;
+    if amount == 0 {
+        return Err(QuoteError::ZeroOutput);
+    }
+    quote_buy(state, amount)
+}
+
+/// Quotes the smallest buy that lifts reserves to at least
+/// `target_reserves`, or the whole remaining supply if nothing less does.
+pub fn quote_buy_to_reserves(state: &CurveState, target_reserves: u64) -> Result<BuyQuote, QuoteError> {
+    let needed = target_reserves.saturating_sub(state.reserves_lamports);
+    if needed == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    // Largest buy whose curve cost falls short of `needed`, plus one base unit
+    let below = amount_for_cost(
+        state.base_price_lamports,
+        state.slope_lamports,
+        state.scale()?,
+        state.tokens_sold,
+        needed - 1,
+        state.supply_remaining,
+    )?;
+    quote_buy(state, below.saturating_add(1).min(state.supply_remaining))
+}
+
+/// Quotes selling exactly `amount` base units back into the curve.
+pub fn quote_sell(state: &CurveState, amount: u64) -> Result<SellQuote, QuoteError> {
+    if amount == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    if amount > state.tokens_sold {
+        return Err(QuoteError::NotEnoughSold);
+    }
+    let refund_gross = curve_cost(
+        state.base_price_lamports,
+        state.slope_lamports,
+        state.scale()?,
+        state.tokens_sold - amount,
+        amount,
+        Rounding::Down,
+    )?;
+    let fees = split_fees(refund_gross, state.fee_bps, state.creator_fee_bps)?;
+    if fees.net == 0 {
+        return Err(QuoteError::ZeroOutput);
+    }
+    if refund_gross > state.reserves_lamports {
+        return Err(QuoteError::InsufficientReserves);
+    }
+    let after = CurveState {
+        supply_remaining: add(state.supply_remaining, amount, "supply remaining")?,
+        tokens_sold: state.tokens_sold - amount,
+        reserves_lamports: state.reserves_lamports - refund_gross,
+        platform_fee_accrued: add(state.platform_fee_accrued, fees.platform_fee, "platform fees")?,
+        creator_fee_accrued: add(state.creator_fee_accrued, fees.creator_fee, "creator fees")?,
+        ..*state
+    };
+    Ok(SellQuote {
+        amount,
+        refund_gross,
+        refund_net: fees.net,
+        platform_fee: fees.platform_fee,
+        creator_fee: fees.creator_fee,
+        after,
+    })
+}
+
+/// Quotes the smallest sell whose net refund is at least `lamports_out`.
+/// The refund can exceed `lamports_out` by the rounding of the fee split.
+pub fn quote_sell_exact_out(state: &CurveState, lamports_out: u64) -> Result<SellQuote, QuoteError> {
+    if lamports_out == 0 {
+        return Err(QuoteError::ZeroAmount);
+    }
+    if state.fee_bps as u64 + state.creator_fee_bps as u64 >= crate::BPS_DENOMINATOR {
+        return Err(QuoteError::FeeTooHigh);
+    }
+    let gross = gross_for_net(lamports_out, state.fee_bps, state.creator_fee_bps)?;
+    let amount = amount_for_refund(
+        state.base_price_lamports,
+        state.slope_lamports,
+        state.scale()?,
+        state.tokens_sold,
+        gross,
+    )?
+    .ok_or(QuoteError::InsufficientReserves)?;
+    quote_sell(state, amount)
+}
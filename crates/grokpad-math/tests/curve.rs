@@ -0,0 +1,154 @@
+use grokpad_math::{self as math, MathOverflow, Rounding};
+use proptest::prelude::*;
+
+/// Reference price: walk the curve one base unit at a time. Base unit `i`
+/// costs `(2*base*scale + slope*(2i+1)) / (2*scale^2)` lamports, the
+/// integral of the whole-token price over `[i, i+1)`.
+fn naive_cost(base: u64, slope: u64, scale: u64, sold_before: u64, amount: u64, rounding: Rounding) -> u128 {
+    let mut numerator = 0u128;
+    for i in sold_before..sold_before + amount {
+        numerator += 2 * base as u128 * scale as u128 + slope as u128 * (2 * i as u128 + 1);
+    }
+    let denominator = 2 * scale as u128 * scale as u128;
+    match rounding {
+        Rounding::Up => numerator.div_ceil(denominator),
+        Rounding::Down => numerator / denominator,
+    }
+}
+
+fn curve_params() -> impl Strategy<Value = (u64, u64, u64, u64)> {
+    (1u64..=10_000_000_000, 0u64..=10_000_000_000, 0u32..=9, 0u64..=1_000_000_000_000)
+        .prop_map(|(base, slope, decimals, sold)| (base, slope, 10u64.pow(decimals), sold))
+}
+
+proptest! {
+    #[test]
+    fn curve_cost_matches_naive_summation(
+        (base, slope, scale, sold) in curve_params(),
+        amount in 0u64..2_000,
+    ) {
+        for rounding in [Rounding::Up, Rounding::Down] {
+            let expected = naive_cost(base, slope, scale, sold, amount, rounding);
+            match math::curve_cost(base, slope, scale, sold, amount, rounding) {
+                Ok(cost) => prop_assert_eq!(cost as u128, expected),
+                Err(_) => prop_assert!(expected > u64::MAX as u128),
+            }
+        }
+    }
+
+    #[test]
+    fn splitting_a_trade_never_favors_the_trader(
+        (base, slope, scale, sold) in curve_params(),
+        first in 0u64..1_000_000_000,
+        second in 0u64..1_000_000_000,
+    ) {
+        let buy = |sold, amount| math::curve_cost(base, slope, scale, sold, amount, Rounding::Up);
+        let sell = |sold, amount| math::curve_cost(base, slope, scale, sold, amount, Rounding::Down);
+        // Only compare stretches of the curve that are priceable at all
+        let whole_buy = buy(sold, first + second);
+        prop_assume!(whole_buy.is_ok());
+        let whole_buy = whole_buy.unwrap();
+        let split_buy = buy(sold, first).unwrap() + buy(sold + first, second).unwrap();
+        prop_assert!(split_buy >= whole_buy);
+
+        let whole_sell = sell(sold, first + second).unwrap();
+        let split_sell = sell(sold, first).unwrap() + sell(sold + first, second).unwrap();
+        prop_assert!(split_sell <= whole_sell);
+        prop_assert!(whole_sell <= whole_buy);
+    }
+
+    #[test]
+    fn amount_for_cost_is_the_largest_affordable_amount(
+        (base, slope, scale, sold) in curve_params(),
+        budget in 1u64..1_000_000_000_000_000,
+        max_amount in 1u64..u64::MAX / 4,
+    ) {
+        let amount = math::amount_for_cost(base, slope, scale, sold, budget, max_amount).unwrap();
+        prop_assert!(amount <= max_amount);
+        prop_assert!(math::curve_cost(base, slope, scale, sold, amount, Rounding::Up).unwrap() <= budget);
+        if amount < max_amount {
+            let next = math::curve_cost(base, slope, scale, sold, amount + 1, Rounding::Up);
+            prop_assert!(next.map_or(true, |cost| cost > budget));
+        }
+    }
+
+    #[test]
+    fn amount_for_refund_is_the_smallest_sufficient_amount(
+        (base, slope, scale, sold) in curve_params(),
+        target in 1u64..1_000_000_000_000_000,
+    ) {
+        let refund = |amount: u64| math::curve_cost(base, slope, scale, sold - amount, amount, Rounding::Down).unwrap();
+        let found = math::amount_for_refund(base, slope, scale, sold, target);
+        prop_assume!(found.is_ok());
+        match found.unwrap() {
+            Some(amount) => {
+                prop_assert!(amount <= sold);
+                prop_assert!(refund(amount) >= target);
+                if amount > 0 {
+                    prop_assert!(refund(amount - 1) < target);
+                }
+            }
+            None => prop_assert!(refund(sold) < target),
+        }
+    }
+
+    #[test]
+    fn split_fees_matches_bps_and_conserves_lamports(
+        gross in any::<u64>(),
+        fee_bps in 0u16..=5_000,
+        creator_fee_bps in 0u16..=5_000,
+    ) {
+        let split = math::split_fees(gross, fee_bps, creator_fee_bps).unwrap();
+        prop_assert_eq!(split.platform_fee as u128, gross as u128 * fee_bps as u128 / 10_000);
+        prop_assert_eq!(split.creator_fee as u128, gross as u128 * creator_fee_bps as u128 / 10_000);
+        prop_assert_eq!(split.platform_fee as u128 + split.creator_fee as u128 + split.net as u128, gross as u128);
+    }
+
+    #[test]
+    fn gross_for_net_covers_the_requested_net(
+        net in 1u64..1_000_000_000_000_000,
+        fee_bps in 0u16..5_000,
+        creator_fee_bps in 0u16..5_000,
+    ) {
+        let gross = math::gross_for_net(net, fee_bps, creator_fee_bps).unwrap();
+        prop_assert!(math::split_fees(gross, fee_bps, creator_fee_bps).unwrap().net >= net);
+        prop_assert!(math::split_fees(gross - 1, fee_bps, creator_fee_bps).unwrap().net < net);
+    }
+
+    #[test]
+    fn base_for_total_is_the_largest_that_fits(
+        total in 0u64..1_000_000_000_000_000,
+        fee_bps in 0u16..=5_000,
+        creator_fee_bps in 0u16..=5_000,
+    ) {
+        let with_fees = |base: u64| {
+            let split = math::split_fees(base, fee_bps, creator_fee_bps).unwrap();
+            base as u128 + split.platform_fee as u128 + split.creator_fee as u128
+        };
+        let base = math::base_for_total(total, fee_bps, creator_fee_bps).unwrap();
+        prop_assert!(with_fees(base) <= total as u128);
+        prop_assert!(with_fees(base + 1) > total as u128);
+    }
+
+    #[test]
+    fn market_cap_of_one_token_is_the_spot_price((base, slope, scale, sold) in curve_params()) {
+        let price = math::spot_price(base, slope, scale, sold);
+        prop_assume!(price.is_ok());
+        let price = price.unwrap();
+        prop_assert_eq!(math::market_cap(base, slope, scale, sold, scale).unwrap(), price);
+        prop_assert_eq!(price as u128, (base as u128 * scale as u128 + slope as u128 * sold as u128) / scale as u128);
+        if let Ok(later) = math::spot_price(base, slope, scale, sold + scale) {
+            prop_assert!(later >= price);
+        }
+    }
+}
+
+#[test]
+fn overflow_is_reported_with_context() {
+    assert_eq!(
+        math::curve_cost(u64::MAX, u64::MAX, 1, u64::MAX, u64::MAX, Rounding::Up),
+        Err(MathOverflow { op: "curve cost" })
+    );
+    assert_eq!(math::split_fees(100, 10_000, 100), Err(MathOverflow { op: "fee split" }));
+    assert_eq!(math::gross_for_net(1, 5_000, 5_000), Err(MathOverflow { op: "gross for net" }));
+}
@@ -0,0 +1,63 @@
+use grokpad_math::{quote_swap_sol_in, quote_swap_tokens_in, PoolState, QuoteError};
+use proptest::prelude::*;
+
+fn pool() -> impl Strategy<Value = PoolState> {
+    (1u64..=1_000_000_000_000_000, 1u64..=1_000_000_000_000_000_000, 0u16..=1_000).prop_map(
+        |(sol_reserves, token_reserves, fee_bps)| PoolState { sol_reserves, token_reserves, fee_bps },
+    )
+}
+
+fn k(pool: &PoolState) -> u128 {
+    pool.sol_reserves as u128 * pool.token_reserves as u128
+}
+
+proptest! {
+    #[test]
+    fn swaps_never_shrink_k(pool in pool(), amount in 1u64..1_000_000_000_000) {
+        if let Ok(buy) = quote_swap_sol_in(&pool, amount) {
+            prop_assert!(k(&buy.after) >= k(&pool));
+            prop_assert_eq!(buy.after.sol_reserves - pool.sol_reserves + buy.fee, amount);
+            prop_assert_eq!(pool.token_reserves - buy.after.token_reserves, buy.amount_out);
+        }
+        if let Ok(sell) = quote_swap_tokens_in(&pool, amount) {
+            prop_assert!(k(&sell.after) >= k(&pool));
+            prop_assert_eq!(pool.sol_reserves - sell.after.sol_reserves, sell.amount_out + sell.fee);
+            prop_assert!(sell.after.sol_reserves > 0);
+        }
+    }
+
+    #[test]
+    fn round_trip_never_profits(pool in pool(), lamports_in in 1u64..1_000_000_000_000) {
+        let buy = quote_swap_sol_in(&pool, lamports_in);
+        prop_assume!(buy.is_ok());
+        let buy = buy.unwrap();
+        match quote_swap_tokens_in(&buy.after, buy.amount_out) {
+            Ok(sell) => prop_assert!(sell.amount_out <= lamports_in),
+            Err(e) => prop_assert_eq!(e, QuoteError::ZeroOutput),
+        }
+    }
+}
+
+#[test]
+fn rejects_empty_pools_and_zero_swaps() {
+    let seeded = PoolState { sol_reserves: 1_000, token_reserves: 1_000, fee_bps: 100 };
+    assert_eq!(quote_swap_sol_in(&seeded, 0), Err(QuoteError::ZeroAmount));
+    assert_eq!(quote_swap_sol_in(&seeded, 1), Err(QuoteError::ZeroOutput));
+    let empty = PoolState { token_reserves: 0, ..seeded };
+    assert_eq!(quote_swap_sol_in(&empty, 100), Err(QuoteError::EmptyPool));
+    assert_eq!(quote_swap_tokens_in(&empty, 100), Err(QuoteError::EmptyPool));
+}
+
+#[test]
+fn spot_price_moves_with_each_swap() {
+    let pool = PoolState { sol_reserves: 30_000_000_000, token_reserves: 600_000_000_000, fee_bps: 30 };
+    // 30 SOL against 600k whole tokens of six decimals
+    assert_eq!(pool.spot_price(1_000_000), Ok(50_000));
+    let buy = quote_swap_sol_in(&pool, 1_000_000_000).unwrap();
+    assert!(buy.after.spot_price(1_000_000).unwrap() > pool.spot_price(1_000_000).unwrap());
+    let sell = quote_swap_tokens_in(&pool, 10_000_000_000).unwrap();
+    assert!(sell.after.spot_price(1_000_000).unwrap() < pool.spot_price(1_000_000).unwrap());
+    assert_eq!(PoolState::default().spot_price(1_000_000), Ok(0));
+    let rich = PoolState { sol_reserves: u64::MAX, token_reserves: 1, fee_bps: 0 };
+    assert!(rich.spot_price(1_000_000).is_err());
+}
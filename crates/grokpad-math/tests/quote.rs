@@ -0,0 +1,104 @@
+use grokpad_math::{
+    quote_buy, quote_buy_exact_in, quote_buy_to_reserves, quote_sell, quote_sell_exact_out, CurveState, QuoteError,
+};
+use proptest::prelude::*;
+
+fn launch() -> impl Strategy<Value = CurveState> {
+    (1u64..=10_000_000, 0u64..=10_000, 0u8..=9, 0u16..=500, 0u16..=500).prop_map(
+        |(base_price_lamports, slope_lamports, decimals, fee_bps, creator_fee_bps)| CurveState {
+            base_price_lamports,
+            slope_lamports,
+            decimals,
+            fee_bps,
+            creator_fee_bps,
+            supply_remaining: 1_000_000_000 * 10u64.pow(decimals as u32),
+            ..CurveState::default()
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn buy_then_sell_never_pays_out_more_than_it_took_in(
+        state in launch(),
+        amount in 1u64..1_000_000_000_000,
+    ) {
+        let buy = quote_buy(&state, amount);
+        prop_assume!(buy.is_ok());
+        let buy = buy.unwrap();
+        prop_assert_eq!(buy.after.tokens_sold, amount);
+        prop_assert_eq!(buy.after.supply_remaining, state.supply_remaining - amount);
+        prop_assert_eq!(buy.after.reserves_lamports + buy.platform_fee + buy.creator_fee, buy.cost);
+
+        match quote_sell(&buy.after, amount) {
+            Ok(sell) => {
+                prop_assert!(sell.refund_net <= buy.cost);
+                prop_assert_eq!(sell.after.tokens_sold, 0);
+                prop_assert_eq!(sell.after.supply_remaining, state.supply_remaining);
+                prop_assert_eq!(sell.refund_net + sell.platform_fee + sell.creator_fee, sell.refund_gross);
+                // Buys round up and sells round down, so the curve keeps any dust
+                prop_assert_eq!(sell.after.reserves_lamports, buy.after.reserves_lamports - sell.refund_gross);
+            }
+            // Reserves always cover the full curve value; only dust can fail
+            Err(e) => prop_assert_eq!(e, QuoteError::ZeroOutput),
+        }
+    }
+
+    #[test]
+    fn exact_in_spends_at_most_the_budget(
+        state in launch(),
+        lamports_in in 1u64..1_000_000_000_000,
+    ) {
+        match quote_buy_exact_in(&state, lamports_in) {
+            Ok(buy) => {
+                prop_assert!(buy.cost <= lamports_in);
+                if let Ok(more) = quote_buy(&state, buy.amount + 1) {
+                    prop_assert!(more.cost > lamports_in);
+                }
+            }
+            Err(e) => prop_assert_eq!(e, QuoteError::ZeroOutput),
+        }
+    }
+
+    #[test]
+    fn exact_out_raises_at_least_the_request(
+        state in launch(),
+        bought in 1_000u64..1_000_000_000_000,
+        lamports_out in 1u64..1_000_000_000,
+    ) {
+        let buy = quote_buy(&state, bought);
+        prop_assume!(buy.is_ok());
+        let after_buy = buy.unwrap().after;
+        match quote_sell_exact_out(&after_buy, lamports_out) {
+            Ok(sell) => {
+                prop_assert!(sell.refund_net >= lamports_out);
+                prop_assert!(sell.amount <= bought);
+            }
+            Err(e) => prop_assert!(e == QuoteError::InsufficientReserves || e == QuoteError::ZeroOutput),
+        }
+    }
+
+    #[test]
+    fn buy_to_reserves_stops_at_the_target(
+        state in launch(),
+        target_reserves in 1u64..1_000_000_000_000,
+    ) {
+        let capped = quote_buy_to_reserves(&state, target_reserves);
+        prop_assume!(capped.is_ok());
+        let capped = capped.unwrap();
+        if capped.amount < state.supply_remaining {
+            prop_assert!(capped.after.reserves_lamports >= target_reserves);
+        }
+        if capped.amount > 1 {
+            prop_assert!(quote_buy(&state, capped.amount - 1).unwrap().after.reserves_lamports < target_reserves);
+        }
+    }
+}
+
+#[test]
+fn rejects_empty_and_oversized_trades() {
+    let state = CurveState { base_price_lamports: 1_000, supply_remaining: 10, ..CurveState::default() };
+    assert_eq!(quote_buy(&state, 0), Err(QuoteError::ZeroAmount));
+    assert_eq!(quote_buy(&state, 11), Err(QuoteError::NotEnoughSupply));
+    assert_eq!(quote_sell(&state, 1), Err(QuoteError::NotEnoughSold));
+}
@@ -0,0 +1,171 @@
+//! Checked-in quote vectors for clients that port this crate, such as
+//! `scripts/grokpad-math.js` (checked by `scripts/check-quote-parity.js`).
+//!
+//! The test fails when `quote_vectors.json` no longer matches what the crate
+//! quotes. After a deliberate pricing change, rewrite it with
+//! `GROKPAD_WRITE_VECTORS=1 cargo test -p grokpad-math --test vectors` and
+//! update the ports to match.
+
+use grokpad_math::{quote_buy, quote_buy_exact_in, quote_sell, quote_sell_exact_out, CurveState, QuoteError};
+use serde_json::{json, Value};
+
+const VECTORS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/quote_vectors.json");
+
+#[derive(Clone, Copy)]
+enum Op {
+    Buy,
+    Sell,
+    BuyExactIn,
+    SellExactOut,
+}
+
+impl Op {
+    fn name(self) -> &'static str {
+        match self {
+            Op::Buy => "buy",
+            Op::Sell => "sell",
+            Op::BuyExactIn => "buy_exact_in",
+            Op::SellExactOut => "sell_exact_out",
+        }
+    }
+}
+
+/// Launches at a few points along their curves, from fresh to nearly sold
+/// out. Each later point is reached through `quote_buy`, so reserves match
+/// what the curve took in.
+fn states() -> Vec<CurveState> {
+    let params: [(u64, u64, u8, u16, u16); 7] = [
+        (1_000, 10, 6, 100, 50),
+        (1_000_000, 100_000, 9, 300, 100),
+        (5_000, 0, 0, 0, 0),
+        (1, 1, 9, 250, 250),
+        (10_000_000, 10_000, 6, 9_000, 999),
+        (1_000, 10, 6, 5_000, 5_000),
+        (u64::MAX / 2, u64::MAX / 2, 0, 100, 0),
+    ];
+    let mut states = Vec::new();
+    for (base_price_lamports, slope_lamports, decimals, fee_bps, creator_fee_bps) in params {
+        let fresh = CurveState {
+            base_price_lamports,
+            slope_lamports,
+            decimals,
+            fee_bps,
+            creator_fee_bps,
+            supply_remaining: 800_000_000 * 10u64.pow(decimals as u32),
+            ..CurveState::default()
+        };
+        states.push(fresh);
+        for share in [4, 999] {
+            if let Ok(buy) = quote_buy(&fresh, fresh.supply_remaining / 1_000 * share) {
+                states.push(buy.after);
+            }
+        }
+    }
+    states
+}
+
+/// Trades to quote on `state`, at and around its limits. Amounts that
+/// coincide on a fresh launch are quoted once.
+fn inputs(state: &CurveState) -> Vec<(Op, u64)> {
+    let scale = 10u64.pow(state.decimals as u32);
+    let mut inputs = Vec::new();
+    let mut push = |op: Op, amounts: &[u64]| {
+        let mut amounts = amounts.to_vec();
+        amounts.sort_unstable();
+        amounts.dedup();
+        inputs.extend(amounts.into_iter().map(|amount| (op, amount)));
+    };
+    push(Op::Buy, &[0, 1, scale, 1_000 * scale, state.supply_remaining, state.supply_remaining.saturating_add(1)]);
+    push(Op::Sell, &[1, scale, state.tokens_sold / 2, state.tokens_sold, state.tokens_sold + 1]);
+    push(Op::BuyExactIn, &[0, 1, 1_000_000, 1_000_000_000_000, u64::MAX]);
+    push(Op::SellExactOut, &[1, 1_000_000, state.reserves_lamports / 2, state.reserves_lamports, u64::MAX]);
+    inputs
+}
+
+fn state_json(state: &CurveState) -> Value {
+    json!({
+        "base_price_lamports": state.base_price_lamports.to_string(),
+        "slope_lamports": state.slope_lamports.to_string(),
+        "decimals": state.decimals,
+        "fee_bps": state.fee_bps,
+        "creator_fee_bps": state.creator_fee_bps,
+        "supply_remaining": state.supply_remaining.to_string(),
+        "tokens_sold": state.tokens_sold.to_string(),
+        "reserves_lamports": state.reserves_lamports.to_string(),
+        "platform_fee_accrued": state.platform_fee_accrued.to_string(),
+        "creator_fee_accrued": state.creator_fee_accrued.to_string(),
+    })
+}
+
+fn error_json(e: QuoteError) -> Value {
+    match e {
+        QuoteError::Math(overflow) => json!(format!("Math({})", overflow.op)),
+        e => json!(format!("{e:?}")),
+    }
+}
+
+/// What the crate quotes for `op` on `state`, as a vector.
+fn vector(state: &CurveState, op: Op, input: u64) -> Value {
+    let result = match op {
+        Op::Buy | Op::BuyExactIn => {
+            let quote = match op {
+                Op::Buy => quote_buy(state, input),
+                _ => quote_buy_exact_in(state, input),
+            };
+            quote.map(|q| {
+                json!({
+                    "amount": q.amount.to_string(),
+                    "cost": q.cost.to_string(),
+                    "platform_fee": q.platform_fee.to_string(),
+                    "creator_fee": q.creator_fee.to_string(),
+                    "after": state_json(&q.after),
+                })
+            })
+        }
+        Op::Sell | Op::SellExactOut => {
+            let quote = match op {
+                Op::Sell => quote_sell(state, input),
+                _ => quote_sell_exact_out(state, input),
+            };
+            quote.map(|q| {
+                json!({
+                    "amount": q.amount.to_string(),
+                    "refund_gross": q.refund_gross.to_string(),
+                    "refund_net": q.refund_net.to_string(),
+                    "platform_fee": q.platform_fee.to_string(),
+                    "creator_fee": q.creator_fee.to_string(),
+                    "after": state_json(&q.after),
+                })
+            })
+        }
+    };
+    let mut vector = json!({ "op": op.name(), "input": input.to_string(), "state": state_json(state) });
+    match result {
+        Ok(quote) => vector["quote"] = quote,
+        Err(e) => vector["error"] = error_json(e),
+    }
+    vector
+}
+
+#[test]
+fn checked_in_vectors_match_the_crate() {
+    let vectors: Vec<Value> =
+        states().iter().flat_map(|state| inputs(state).into_iter().map(move |(op, input)| vector(state, op, input))).collect();
+
+    if std::env::var_os("GROKPAD_WRITE_VECTORS").is_some() {
+        // One vector per line keeps diffs to the vectors that changed
+        let lines: Vec<String> = vectors.iter().map(|v| serde_json::to_string(v).unwrap()).collect();
+        std::fs::write(VECTORS, format!("[\n{}\n]\n", lines.join(",\n"))).unwrap();
+        return;
+    }
+    let checked_in: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(VECTORS).unwrap()).unwrap();
+    assert_eq!(checked_in.len(), vectors.len(), "vector count changed; rewrite with GROKPAD_WRITE_VECTORS=1");
+    for (i, (checked_in, vector)) in checked_in.iter().zip(&vectors).enumerate() {
+        assert_eq!(checked_in, vector, "vector {i} differs; rewrite with GROKPAD_WRITE_VECTORS=1");
+    }
+    // Both outcomes of every operation are covered
+    for op in ["buy", "sell", "buy_exact_in", "sell_exact_out"] {
+        let of_op = || vectors.iter().filter(move |v| v["op"] == op);
+        assert!(of_op().any(|v| v.get("quote").is_some()) && of_op().any(|v| v.get("error").is_some()), "{op}");
+    }
+}
@@ -19,6 +19,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, discriminator, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 const { PROGRAM_ID: CONFIG_PROGRAM_ID } = require('./config');
 
 // Connect to local Solana node
@@ -34,8 +35,8 @@ const wallet = Keypair.fromSecretKey(secretKey);
 const PROGRAM_ID = CONFIG_PROGRAM_ID;
 
 // Use EXACT discriminators from test-fixed.js
-const DISCRIMINATOR_INITIALIZE = Buffer.from([30, 120, 39, 212, 120, 168, 29, 81]); // anchor.sighash("global:initializeLaunch")
-const DISCRIMINATOR_BUY = Buffer.from([103, 17, 200, 25, 118, 95, 125, 61]); // anchor.sighash("global:buy")
+const DISCRIMINATOR_INITIALIZE = discriminator('initialize_launch'); // sha256("global:initialize_launch")[0:8]
+const DISCRIMINATOR_BUY = discriminator('buy'); // sha256("global:buy")[0:8]
 
 async function main() {
   console.log("=== FINAL BONDING CURVE INITIALIZATION ===");
@@ -81,16 +82,17 @@ async function main() {
   );
   console.log("Vault SOL PDA:", vaultSolPda.toString());
   
-  // Create vault ATA
-  console.log("\nCreating vault ATA...");
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  // Vault ATA and the creator's token account
+  console.log("\nCreating creator token account...");
+  const { vaultAta } = launchPdas(PROGRAM_ID, mint);
+  // initialize_launch creates the vault ATA; the supply goes to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     wallet,
     mint,
-    authorityPda,
-    true
+    wallet.publicKey
   );
-  console.log("Vault ATA:", vaultAta.address.toString());
+  console.log("Vault ATA:", vaultAta.toString());
   
   // Mint initial supply if using new mint
   if (!USE_EXISTING_MINT) {
@@ -99,7 +101,7 @@ async function main() {
       connection,
       wallet,
       mint,
-      vaultAta.address,
+      creatorAta.address,
       wallet.publicKey,
       1000000000000 // 1000 tokens with 9 decimals - EXACT SAME as test-fixed.js
     );
@@ -120,7 +122,7 @@ async function main() {
       authorityPda: authorityPda.toString(),
       statePda: statePda.toString(),
       vaultSolPda: vaultSolPda.toString(),
-      vaultAta: vaultAta.address.toString(),
+      vaultAta: vaultAta.toString(),
       discriminator: Array.from(DISCRIMINATOR_INITIALIZE),
       timestamp: new Date().toISOString(),
       status: "Already initialized"
@@ -136,47 +138,29 @@ async function main() {
     // Initialize launch parameters - EXACT SAME as test-fixed.js
     const basePriceLamports = 1_000_000; // 0.001 SOL
     const slopeLamports = 100_000; // 0.0001 SOL per token
-    const feeBps = 300; // 3.00%
     const creatorFeeBps = 100; // 1.00%
     const graduationTargetLamports = 2 * 1_000_000_000; // 2 SOL
     
-    // Construct initialization data - EXACTLY as test-fixed.js does it
-    const initData = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-    
-    // Write discriminator
-    DISCRIMINATOR_INITIALIZE.copy(initData, 0);
-    
-    // Write params
-    let offset = 8;
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-    initData.writeUInt16LE(feeBps, offset); offset += 2;
-    initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-    initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-    
-    // Create instruction - EXACTLY as test-fixed.js does it
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: initData
-    };
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    const fundIx = fundCurveIx(PROGRAM_ID, {
+      creator: wallet.publicKey,
+      mint,
+      amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+    });
     
     // Send transaction
     console.log('Sending initialization transaction...');
-    const initTx = new Transaction().add(initIx);
+    const initTx = new Transaction().add(initIx, fundIx);
     const initSig = await sendAndConfirmTransaction(
       connection, 
       initTx, 
@@ -219,7 +203,7 @@ async function main() {
       authorityPda: authorityPda.toString(),
       statePda: statePda.toString(),
       vaultSolPda: vaultSolPda.toString(),
-      vaultAta: vaultAta.address.toString(),
+      vaultAta: vaultAta.toString(),
       discriminator: Array.from(DISCRIMINATOR_INITIALIZE),
       signature: initSig,
       timestamp: new Date().toISOString()
@@ -24,6 +24,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, discriminator, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 
 // Connect to local Solana node
 const connection = new Connection('http://localhost:8899', 'confirmed');
@@ -37,8 +38,8 @@ const wallet = Keypair.fromSecretKey(secretKey);
 // Program ID
 const PROGRAM_ID = new PublicKey('CYUSvq2vmNZ4rcyhfKyfaTKvfeH3doxyqx69ifV3w3TP');
 
-// Use the discriminator that gave us IllegalOwner error
-const INITIALIZE_DISCRIMINATOR = Buffer.from([90, 201, 220, 142, 112, 253, 100, 13]); // global:initialize_launch
+// Discriminator for initialize_launch
+const INITIALIZE_DISCRIMINATOR = discriminator('initialize_launch'); // global:initialize_launch
 
 async function main() {
   console.log("=== BONDING CURVE INITIALIZATION WITH FIXED ACCOUNT STRUCTURE ===");
@@ -60,19 +61,18 @@ async function main() {
   const mintInfo = await getMint(connection, mint);
   console.log("Mint decimals:", mintInfo.decimals);
   
-  // Derive PDAs based on instructions/initialize_launch.rs account structure
+  // Derive PDAs from the InitializeLaunch account structure
   const [launchPda] = PublicKey.findProgramAddressSync(
     [Buffer.from("launch"), mint.toBuffer()], 
     PROGRAM_ID
   );
   console.log("Launch PDA:", launchPda.toString());
   
-  // For compatibility with both versions
   const [statePda] = PublicKey.findProgramAddressSync(
     [Buffer.from("launch_state"), mint.toBuffer()],
     PROGRAM_ID
   );
-  console.log("State PDA (if needed):", statePda.toString());
+  console.log("State PDA:", statePda.toString());
   
   const [solVaultPda] = PublicKey.findProgramAddressSync(
     [Buffer.from("vault_sol"), mint.toBuffer()],
@@ -80,110 +80,63 @@ async function main() {
   );
   console.log("SOL Vault PDA:", solVaultPda.toString());
   
-  // Create vault ATA
-  console.log("\nCreating vault ATA...");
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  // Vault ATA and the creator's token account
+  console.log("\nCreating creator token account...");
+  const { vaultAta } = launchPdas(PROGRAM_ID, mint);
+  // initialize_launch creates the vault ATA; the supply goes to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     wallet,
     mint,
-    launchPda,
-    true
+    wallet.publicKey
   );
-  console.log("Vault ATA:", vaultAta.address.toString());
+  console.log("Vault ATA:", vaultAta.toString());
   
-  // Mint initial supply to vault
-  console.log("\nMinting initial supply to vault...");
+  // Mint initial supply to the creator
+  console.log("\nMinting initial supply to the creator...");
   const initialSupply = 1_000_000_000; // 1000 tokens with 6 decimals
   try {
     await mintTo(
       connection,
       wallet,
       mint,
-      vaultAta.address,
+      creatorAta.address,
       wallet.publicKey,
       initialSupply
     );
-    console.log(`Minted ${initialSupply} tokens to vault`);
+    console.log(`Minted ${initialSupply} tokens to the creator`);
   } catch (error) {
     console.log("Error minting tokens:", error.message);
     // Continue anyway
   }
 
   try {
-    // Initialize launch parameters based on instructions/initialize_launch.rs
-    // This matches the PriceState struct in the Rust code
-    const name = "FunGrok"; // Must end with "grok"
-    const symbol = "FGROK";
-    
-    // Calculate data size for initialize_launch in instructions/initialize_launch.rs
-    // We need: discriminator (8) + name string (4+len) + symbol string (4+len) + PriceState struct
-    const nameBytes = Buffer.from(name);
-    const symbolBytes = Buffer.from(symbol);
-    
-    // Simplified PriceState struct - this is a guess based on the error
+    // Launch parameters
     const basePriceLamports = 1_000_000; // 0.001 SOL
     const slopeLamports = 100_000; // 0.0001 SOL per token
-    const feeBps = 300; // 3.00%
     const creatorFeeBps = 100; // 1.00%
     const graduationTargetLamports = 2 * 1_000_000_000; // 2 SOL
 
-    // Create initialization data
-    const initDataSize = 8 + // discriminator
-                         4 + nameBytes.length + // name string
-                         4 + symbolBytes.length + // symbol string 
-                         8 + 8 + 2 + 2 + 8; // basePriceLamports + slopeLamports + feeBps + creatorFeeBps + graduationTargetLamports
-    
-    const initData = Buffer.alloc(initDataSize);
-    let offset = 0;
-    
-    // Write discriminator
-    INITIALIZE_DISCRIMINATOR.copy(initData, offset);
-    offset += 8;
-    
-    // Write name string (Borsh format: size as u32 LE + bytes)
-    initData.writeUInt32LE(nameBytes.length, offset);
-    offset += 4;
-    nameBytes.copy(initData, offset);
-    offset += nameBytes.length;
-    
-    // Write symbol string (Borsh format: size as u32 LE + bytes)
-    initData.writeUInt32LE(symbolBytes.length, offset);
-    offset += 4;
-    symbolBytes.copy(initData, offset);
-    offset += symbolBytes.length;
-    
-    // Write PriceState struct (based on found values)
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-    initData.writeUInt16LE(feeBps, offset); offset += 2;
-    initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-    initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-    
-    // Create a dummy global config account for now
-    const globalConfigPda = wallet.publicKey; // We'll just use wallet key for now
-    
-    console.log("\nBuilding account structure based on instructions/initialize_launch.rs...");
-    
-    // Create instruction with account structure from instructions/initialize_launch.rs
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: globalConfigPda, isSigner: false, isWritable: true }, // global_config
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: false }, // admin
-        { pubkey: launchPda, isSigner: false, isWritable: true }, // launch
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // creator
-        { pubkey: mint, isSigner: false, isWritable: true }, // mint
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // curve_vault
-        { pubkey: solVaultPda, isSigner: false, isWritable: true }, // sol_vault
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false } // system_program
-      ],
-      data: initData
-    };
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    const fundIx = fundCurveIx(PROGRAM_ID, {
+      creator: wallet.publicKey,
+      mint,
+      amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+    });
     
     // Send transaction
     console.log('Sending initialization transaction...');
-    const initTx = new Transaction().add(initIx);
+    const initTx = new Transaction().add(initIx, fundIx);
     
     const initSig = await sendAndConfirmTransaction(
       connection, 
@@ -195,12 +148,12 @@ async function main() {
     console.log("\n✅ SUCCESS!");
     console.log(`Transaction signature: ${initSig}`);
     
-    // Check if the launch account was created
-    const launchAccount = await connection.getAccountInfo(launchPda);
-    console.log("Launch account created:", !!launchAccount);
+    // Check if the launch state account was created
+    const launchAccount = await connection.getAccountInfo(statePda);
+    console.log("Launch state account created:", !!launchAccount);
     if (launchAccount) {
-      console.log("Launch account size:", launchAccount.data.length, "bytes");
-      console.log("Launch account owner:", launchAccount.owner.toString());
+      console.log("Launch state account size:", launchAccount.data.length, "bytes");
+      console.log("Launch state account owner:", launchAccount.owner.toString());
     }
     
     // Save the successful setup
@@ -210,7 +163,7 @@ async function main() {
       launchPda: launchPda.toString(),
       statePda: statePda.toString(),
       solVaultPda: solVaultPda.toString(),
-      vaultAta: vaultAta.address.toString(),
+      vaultAta: vaultAta.toString(),
       discriminator: Array.from(INITIALIZE_DISCRIMINATOR),
       signature: initSig,
       timestamp: new Date().toISOString()
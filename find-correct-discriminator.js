@@ -19,6 +19,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, discriminator: ixDiscriminator, initializeLaunchIx } = require('./scripts/grokpad-ix');
 const crypto = require('crypto');
 
 // Connect to local Solana node
@@ -73,53 +74,24 @@ async function testDiscriminator(discriminator, label) {
       PROGRAM_ID
     );
     
-    // Create vault ATA
-    const vaultAta = await getOrCreateAssociatedTokenAccount(
-      connection,
-      wallet,
-      mint,
-      authorityPda,
-      true
-    );
-    
-    // Launch parameters (using values from test-fixed.js)
+    // Launch parameters. The platform fee and its recipient come from the
+    // global config.
     const basePriceLamports = 1_000_000; // 0.001 SOL
     const slopeLamports = 100_000; // 0.0001 SOL per token
-    const feeBps = 300; // 3.00%
     const creatorFeeBps = 100; // 1.00%
     const graduationTargetLamports = 2 * 1_000_000_000; // 2 SOL
     
-    // Construct initialization data with the test discriminator
-    const initData = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-    discriminator.copy(initData, 0);
-    
-    // Write parameters
-    let offset = 8;
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-    initData.writeUInt16LE(feeBps, offset); offset += 2;
-    initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-    initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-    
-    // Create initialization instruction
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: initData
-    };
+    // Create initialization instruction, then swap in the test discriminator
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    discriminator.copy(initIx.data, 0);
     
     // Send transaction
     console.log('Sending initialization transaction...');
@@ -204,11 +176,11 @@ async function main() {
     { namespace: "program", name: "initialize" }
   ];
   
-  // Also try the hardcoded discriminator from test-fixed.js
+  // Also try the discriminator scripts/grokpad-ix.js builds with
   const knownDiscriminators = [
     { 
-      name: "Test-fixed.js value",
-      bytes: Buffer.from([30, 120, 39, 212, 120, 168, 29, 81])
+      name: "grokpad-ix value",
+      bytes: ixDiscriminator("initialize_launch")
     }
   ];
   
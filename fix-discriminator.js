@@ -19,6 +19,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, initializeLaunchData, initializeLaunchIx } = require('./scripts/grokpad-ix');
 const crypto = require('crypto');
 
 // Connect to local Solana node
@@ -42,25 +43,13 @@ const initFunctions = [
     args: {
       basePriceLamports: 10_000_000, // 0.01 SOL
       slopeLamports: 100_000, // 0.0001 SOL per token
-      feeBps: 50, // 0.5%
       creatorFeeBps: 250, // 2.5%
-      graduationTargetLamports: 10_000_000_000 // 10 SOL
+      graduationCriteria: [GraduationCriterion.reserves(10_000_000_000)] // 10 SOL
     },
     encodeForLibRs: (args, discriminator) => {
-      // Create buffer for discriminator + args
-      const buffer = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-      
-      // Write discriminator
+      // Encode the arguments, then swap in the discriminator under test
+      const buffer = initializeLaunchData(args);
       discriminator.copy(buffer, 0);
-      
-      // Write parameters
-      let offset = 8;
-      buffer.writeBigUInt64LE(BigInt(args.basePriceLamports), offset); offset += 8;
-      buffer.writeBigUInt64LE(BigInt(args.slopeLamports), offset); offset += 8;
-      buffer.writeUInt16LE(args.feeBps, offset); offset += 2;
-      buffer.writeUInt16LE(args.creatorFeeBps, offset); offset += 2;
-      buffer.writeBigUInt64LE(BigInt(args.graduationTargetLamports), offset);
-      
       return buffer;
     }
   },
@@ -190,34 +179,15 @@ async function testInitialization(testCase) {
       PROGRAM_ID
     );
     
-    // Create vault ATA
-    const vaultAta = await getOrCreateAssociatedTokenAccount(
-      connection,
-      wallet,
+    // Create initialization instruction using the test case data. The
+    // program creates the vault ATA itself.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
       mint,
-      authorityPda,
-      true
-    );
-    
-    // Create initialization instruction using the test case data
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: testCase.data
-    };
+      ...initFunctions[0].args,
+    });
+    initIx.data = testCase.data;
     
     // Send transaction
     console.log('Sending initialization transaction...');
@@ -251,7 +221,6 @@ async function testInitialization(testCase) {
         authorityPda: authorityPda.toString(),
         statePda: statePda.toString(),
         vaultSolPda: vaultSolPda.toString(),
-        vaultAta: vaultAta.address.toString(),
         signature: initSig,
         timestamp: new Date().toISOString()
       };
@@ -2,8 +2,8 @@
  * fixed-initialize-launch.js - Correct initialization using the proper function parameters
  * 
  * This script fixes the Custom Error 101 (InstructionFallbackNotFound) by:
- * 1. Using the correct parameter structure from initialize_launch.rs
- * 2. NOT pre-creating the vault account (fixed from previous work)
+ * 1. Using the instruction encoding from scripts/grokpad-ix.js
+ * 2. NOT pre-creating the vault account; initialize_launch creates it
  */
 const { 
   Connection, 
@@ -19,11 +19,12 @@ const {
   TOKEN_PROGRAM_ID, 
   ASSOCIATED_TOKEN_PROGRAM_ID, 
   getOrCreateAssociatedTokenAccount,
-  createMint
+  createMint,
+  mintTo
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
-const crypto = require('crypto');
+const { GraduationCriterion, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 
 // Connect to local Solana node
 const connection = new Connection('http://localhost:8899', 'confirmed');
@@ -37,25 +38,6 @@ const wallet = Keypair.fromSecretKey(secretKey);
 // Program ID
 const PROGRAM_ID = new PublicKey('CYUSvq2vmNZ4rcyhfKyfaTKvfeH3doxyqx69ifV3w3TP');
 
-// Generate standard Anchor discriminator (SHA256 hash of namespace:name)
-function generateAnchorDiscriminator(namespace, name) {
-  const preimage = `${namespace}:${name}`;
-  console.log(`Generating discriminator for "${preimage}"`);
-  const hash = crypto.createHash('sha256').update(preimage).digest();
-  return hash.slice(0, 8);
-}
-
-// The discriminator from test-fixed.js
-const DISCRIMINATOR_INITIALIZE = Buffer.from([30, 120, 39, 212, 120, 168, 29, 81]);
-
-// Function to encode a string for serialization
-function encodeString(str) {
-  const bytes = Buffer.from(str, 'utf8');
-  const len = Buffer.alloc(4);
-  len.writeUInt32LE(bytes.length, 0);
-  return Buffer.concat([len, bytes]);
-}
-
 async function main() {
   console.log("=== FIXED BONDING CURVE INITIALIZATION ===");
   console.log("Program ID:", PROGRAM_ID.toString());
@@ -95,111 +77,64 @@ async function main() {
   );
   console.log("Vault SOL PDA:", vaultSolPda.toString(), "(bump:", vaultSolBump, ")");
   
-  // Get vault ATA
-  console.log("\nCreating vault ATA...");
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  // Vault ATA and the creator's token account
+  console.log("\nCreating creator token account...");
+  const { vaultAta } = launchPdas(PROGRAM_ID, mint);
+  // initialize_launch creates the vault ATA; the supply goes to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     wallet,
     mint,
-    authorityPda,
-    true
+    wallet.publicKey
   );
-  console.log("Vault ATA:", vaultAta.address.toString());
+  console.log("Vault ATA:", vaultAta.toString());
   
-  // Fixed parameters based on initialize_launch.rs
-  const name = "mygrok"; // Must end with "grok" per the code requirement
-  const symbol = "GROK";
+  // Mint initial supply to the creator
+  console.log("\nMinting initial supply to the creator...");
+  const initialSupply = 1_000_000_000_000; // 1000 tokens with 9 decimals
+  await mintTo(
+    connection,
+    wallet,
+    mint,
+    creatorAta.address,
+    wallet.publicKey,
+    initialSupply
+  );
+  console.log(`Minted ${initialSupply} tokens to the creator`);
   
-  // PriceState struct from state.rs:
-  // pub struct PriceState {
-  //   pub tranche_size: u64,
-  //   pub base_price_lamports: u64,
-  //   pub step_bps: u16,
-  // }
-  const trancheSize = 1_000_000_000; // 1 billion tokens in smallest units
+  // Launch parameters, per initialize_launch in lib.rs
   const basePriceLamports = 10_000_000; // 0.01 SOL
-  const stepBps = 500; // 5.00% increase per tranche
+  const slopeLamports = 100_000; // 0.0001 SOL per token
+  const creatorFeeBps = 250; // 2.5%
+  const graduationTargetLamports = 10_000_000_000; // 10 SOL
   
   try {
-    // Calculate correct discriminator
-    let discriminator;
-    
-    // Try different discriminator options
-    const discriminatorOptions = [
-      // From test-fixed.js - the standard one tried before
-      { name: "test-fixed.js", buffer: DISCRIMINATOR_INITIALIZE },
-      // Try different namespaces with initialize_launch
-      { name: "global:initialize_launch", buffer: generateAnchorDiscriminator("global", "initialize_launch") },
-      { name: "instruction:initialize_launch", buffer: generateAnchorDiscriminator("instruction", "initialize_launch") },
-      { name: "", buffer: generateAnchorDiscriminator("", "initialize_launch") },
-    ];
-    
-    // Start with the standard one
-    discriminator = discriminatorOptions[0].buffer;
-    console.log(`Using discriminator: ${discriminatorOptions[0].name}`);
-    
-    // IMPORTANT: Construct initialization data with the CORRECT PARAMETER FORMAT
-    // Per initialize_launch.rs: (name: String, _symbol: String, price_state: PriceState)
-    
-    // 1. Allocate buffer: 
-    //    8 bytes discriminator + 
-    //    name string (4 bytes len + variable) + 
-    //    symbol string (4 bytes len + variable) +
-    //    price_state struct (8 + 8 + 2 = 18 bytes)
-    const nameEncoded = encodeString(name);
-    const symbolEncoded = encodeString(symbol);
-    const priceStateSize = 8 + 8 + 2; // tranche_size (u64) + base_price_lamports (u64) + step_bps (u16)
-    
-    const initDataSize = 8 + nameEncoded.length + symbolEncoded.length + priceStateSize;
-    const initData = Buffer.alloc(initDataSize);
-    
-    // 2. Write discriminator
-    discriminator.copy(initData, 0);
-    
-    // 3. Write parameters
-    let offset = 8;
-    
-    // Write name string
-    nameEncoded.copy(initData, offset);
-    offset += nameEncoded.length;
-    
-    // Write symbol string
-    symbolEncoded.copy(initData, offset);
-    offset += symbolEncoded.length;
-    
-    // Write PriceState struct
-    initData.writeBigUInt64LE(BigInt(trancheSize), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeUInt16LE(stepBps, offset);
-    
     console.log("\nInitialization data:");
-    console.log("- Name:", name);
-    console.log("- Symbol:", symbol);
-    console.log("- Tranche Size:", trancheSize);
     console.log("- Base Price (lamports):", basePriceLamports);
-    console.log("- Step BPS:", stepBps);
-    
-    // Create initialization instruction
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        // Use the accounts based on initialize_launch.rs AccountsStruct
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // global_config 
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: false }, // admin
-        { pubkey: statePda, isSigner: false, isWritable: true }, // launch (not authorityPda)
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // creator
-        { pubkey: mint, isSigner: false, isWritable: true }, // mint
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // curve_vault
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // sol_vault
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-      ],
-      data: initData
-    };
+    console.log("- Slope (lamports):", slopeLamports);
+    console.log("- Creator Fee BPS:", creatorFeeBps);
+    console.log("- Graduation Target (lamports):", graduationTargetLamports);
+    
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    const fundIx = fundCurveIx(PROGRAM_ID, {
+      creator: wallet.publicKey,
+      mint,
+      amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+    });
     
     // Send transaction
     console.log("\nSending initialization transaction...");
-    const initTx = new Transaction().add(initIx);
+    const initTx = new Transaction().add(initIx, fundIx);
     
     // For better debugging, use skipPreflight to see the real error
     const initSig = await sendAndConfirmTransaction(
@@ -249,8 +184,8 @@ async function main() {
       authorityPda: authorityPda.toString(),
       statePda: statePda.toString(),
       vaultSolPda: vaultSolPda.toString(),
-      vaultAta: vaultAta.address.toString(),
-      discriminator: Array.from(discriminator),
+      vaultAta: vaultAta.toString(),
+      discriminator: Array.from(initIx.data.subarray(0, 8)),
       signature: initSig,
       timestamp: new Date().toISOString()
     };
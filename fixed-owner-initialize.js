@@ -1,7 +1,7 @@
 /**
  * fixed-owner-initialize.js - Focuses on fixing owner permissions issue
  *
- * This script uses the correct discriminator we discovered (5ac9dc8e70fd640d) and
+ * This script builds the instructions with scripts/grokpad-ix.js and
  * addresses the "Provided owner is not allowed" error by ensuring proper token ownership.
  */
 const { 
@@ -25,6 +25,7 @@ const {
   mintTo
 } = require('@solana/spl-token');
 const fs = require('fs');
+const { GraduationCriterion, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 const crypto = require('crypto');
 const BN = require('bn.js');
 
@@ -62,29 +63,11 @@ const [vaultSolPda] = PublicKey.findProgramAddressSync(
 );
 
 // Parameters
-const feeBps = 50; // 0.5% fee
 const creatorFeeBps = 250; // 2.5% creator fee
 const basePriceLamports = new BN(10000000); // 0.01 SOL
 const slopeLamports = new BN(100000); // 0.0001 SOL per token
 const graduationTargetLamports = new BN(10000000000); // 10 SOL
 
-// Use the working discriminator we discovered
-const CORRECT_DISCRIMINATOR = Buffer.from('5ac9dc8e70fd640d', 'hex');
-
-// Function to serialize a BN to a little-endian buffer
-function serializeU64(bn) {
-  const buffer = Buffer.alloc(8);
-  buffer.writeBigUInt64LE(BigInt(bn.toString()));
-  return buffer;
-}
-
-// Function to serialize a u16
-function serializeU16(num) {
-  const buffer = Buffer.alloc(2);
-  buffer.writeUInt16LE(num);
-  return buffer;
-}
-
 async function main() {
   try {
     console.log("==================================================");
@@ -123,87 +106,49 @@ async function main() {
       return { success: true, alreadyInitialized: true };
     }
     
-    // Create vault ATA if it doesn't exist
-    console.log("\nGetting vault ATA address...");
-    const vaultAtaAddress = await getAssociatedTokenAddress(
+    // initialize_launch creates the vault ATA; the supply goes to the creator
+    const { vaultAta } = launchPdas(programId, mint);
+    console.log("\nVault ATA address:", vaultAta.toBase58());
+    const creatorAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet,
       mint,
-      authorityPda,
-      true // allowOwnerOffCurve
+      wallet.publicKey
     );
-    console.log("Vault ATA address:", vaultAtaAddress.toBase58());
-    
-    // Check if the vault ATA already exists
-    const vaultAtaInfo = await connection.getAccountInfo(vaultAtaAddress);
-    
-    // Setup transaction
-    const transaction = new Transaction();
     
-    // If vault ATA doesn't exist, create it
-    if (!vaultAtaInfo) {
-      console.log("Vault ATA doesn't exist, adding creation instruction...");
-      transaction.add(
-        createAssociatedTokenAccountInstruction(
-          wallet.publicKey, // payer
-          vaultAtaAddress, // ata
-          authorityPda, // owner
-          mint // mint
-        )
+    // Mint some initial tokens to the creator if they have none
+    if (creatorAta.amount === 0n && isMintAuthority) {
+      console.log("Minting initial tokens to the creator...");
+      const mintAmount = 1000n * 1000000000n; // 1000 tokens with 9 decimals
+      await mintTo(
+        connection,
+        wallet,
+        mint,
+        creatorAta.address,
+        wallet.publicKey,
+        mintAmount
       );
-    } else {
-      console.log("Vault ATA already exists");
-    }
-    
-    // Mint some initial tokens to the vault if it's empty
-    if (!vaultAtaInfo || vaultAtaInfo.data.length === 0) {
-      if (isMintAuthority) {
-        console.log("Adding instruction to mint initial tokens to vault...");
-        const mintAmount = 1000n * 1000000000n; // 1000 tokens with 9 decimals
-        // We only add this if we have the authority
-        transaction.add(
-          mintTo(
-            connection,
-            wallet.payer,
-            mint,
-            vaultAtaAddress,
-            wallet.publicKey,
-            mintAmount
-          )
-        );
-      }
     }
     
-    // Create initialization instruction data
-    console.log("\nCreating initialization instruction with correct discriminator...");
-    const data = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-    CORRECT_DISCRIMINATOR.copy(data, 0);
-    serializeU64(basePriceLamports).copy(data, 8);
-    serializeU64(slopeLamports).copy(data, 16);
-    serializeU16(feeBps).copy(data, 24);
-    serializeU16(creatorFeeBps).copy(data, 26);
-    serializeU64(graduationTargetLamports).copy(data, 28);
-    
-    // Create initialize instruction
-    const initInstruction = new TransactionInstruction({
-      programId,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platformFeeRecipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authorityPda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // statePda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vaultSolPda
-        { pubkey: vaultAtaAddress, isSigner: false, isWritable: true }, // vaultAta
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // systemProgram
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // tokenProgram
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associatedTokenProgram
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false }, // rent
-      ],
-      data
-    });
-    
-    // Add initialization instruction
-    transaction.add(initInstruction);
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    console.log("\nCreating initialization instructions...");
+    const transaction = new Transaction().add(
+      initializeLaunchIx(programId, {
+        payer: wallet.publicKey,
+        creator: wallet.publicKey,
+        mint,
+        basePriceLamports,
+        slopeLamports,
+        creatorFeeBps,
+        graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+      }),
+      fundCurveIx(programId, {
+        creator: wallet.publicKey,
+        mint,
+        amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+      })
+    );
     
     // Send transaction
     console.log("\nSending transaction with init instruction...");
@@ -21,6 +21,7 @@ const {
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, discriminator, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 
 // Connect to local Solana node
 const connection = new Connection('http://localhost:8899', 'confirmed');
@@ -36,7 +37,7 @@ const PROGRAM_ID = new PublicKey('CYUSvq2vmNZ4rcyhfKyfaTKvfeH3doxyqx69ifV3w3TP')
 
 // CORRECTLY computed discriminator for initialize_launch
 // This is SHA-256("global:initialize_launch")[0:8]
-const DISCRIMINATOR_INITIALIZE = Buffer.from([90, 201, 220, 142, 112, 253, 100, 13]);
+const DISCRIMINATOR_INITIALIZE = discriminator('initialize_launch');
 
 // Helper function to check if an account exists
 async function accountExists(pubkey) {
@@ -83,29 +84,30 @@ async function main() {
   );
   console.log("Vault SOL PDA:", vaultSolPda.toString());
   
-  // Create vault ATA
-  console.log("\nCreating vault ATA...");
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  // Vault ATA and the creator's token account
+  console.log("\nCreating creator token account...");
+  const { vaultAta } = launchPdas(PROGRAM_ID, mint);
+  // initialize_launch creates the vault ATA; the supply goes to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     wallet,
     mint,
-    authorityPda,
-    true
+    wallet.publicKey
   );
-  console.log("Vault ATA:", vaultAta.address.toString());
+  console.log("Vault ATA:", vaultAta.toString());
   
-  // Mint initial supply to vault
-  console.log("\nMinting initial supply to vault...");
+  // Mint initial supply to the creator
+  console.log("\nMinting initial supply to the creator...");
   const initialSupply = 1_000_000_000_000; // 1000 tokens with 9 decimals
   await mintTo(
     connection,
     wallet,
     mint,
-    vaultAta.address,
+    creatorAta.address,
     wallet.publicKey,
     initialSupply
   );
-  console.log(`Minted ${initialSupply} tokens to vault`);
+  console.log(`Minted ${initialSupply} tokens to the creator`);
   
   try {
     // Important: Check if the vault SOL PDA needs to be created
@@ -151,45 +153,29 @@ async function main() {
     // Initialize launch parameters - same as in lib.rs
     const basePriceLamports = 1_000_000; // 0.001 SOL
     const slopeLamports = 100_000; // 0.0001 SOL per token
-    const feeBps = 300; // 3.00%
     const creatorFeeBps = 100; // 1.00%
     const graduationTargetLamports = 2 * 1_000_000_000; // 2 SOL
     
-    // Construct initialization data with CORRECT discriminator
-    const initData = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8); // 8 bytes for each u64, 2 bytes for each u16
-    DISCRIMINATOR_INITIALIZE.copy(initData, 0);
-    
-    // Write parameters in the exact order as defined in lib.rs
-    let offset = 8; // Start after discriminator
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-    initData.writeUInt16LE(feeBps, offset); offset += 2;
-    initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-    initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-    
-    // Create initialization instruction with account structure from lib.rs
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda - must be system-owned
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: initData
-    };
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    const fundIx = fundCurveIx(PROGRAM_ID, {
+      creator: wallet.publicKey,
+      mint,
+      amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+    });
     
     // Send transaction
     console.log('Sending initialization transaction...');
-    const initTx = new Transaction().add(initIx);
+    const initTx = new Transaction().add(initIx, fundIx);
     
     const initSig = await sendAndConfirmTransaction(
       connection, 
@@ -235,7 +221,7 @@ async function main() {
       authorityPda: authorityPda.toString(),
       statePda: statePda.toString(),
       vaultSolPda: vaultSolPda.toString(),
-      vaultAta: vaultAta.address.toString(),
+      vaultAta: vaultAta.toString(),
       discriminator: Array.from(DISCRIMINATOR_INITIALIZE),
       signature: initSig,
       timestamp: new Date().toISOString()
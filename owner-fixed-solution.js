@@ -19,10 +19,12 @@ const {
   ASSOCIATED_TOKEN_PROGRAM_ID, 
   getOrCreateAssociatedTokenAccount,
   createMint,
+  mintTo,
   getAccount
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
+const { GraduationCriterion, launchPdas, initializeLaunchIx, fundCurveIx } = require('./scripts/grokpad-ix');
 const crypto = require('crypto');
 
 // Connect to local Solana node
@@ -165,28 +167,39 @@ async function initializeBondingCurve(mint, vaultSolPda) {
   );
   console.log("State PDA:", statePda.toString(), "(bump:", stateBump, ")");
   
-  // Create or get vault ATA
-  console.log("\nCreating vault ATA...");
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  // Vault ATA and the creator's token account
+  console.log("\nCreating creator token account...");
+  const { vaultAta } = launchPdas(PROGRAM_ID, mint);
+  // initialize_launch creates the vault ATA; the supply goes to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     wallet,
     mint,
-    authorityPda,
-    true  // allowOwnerOffCurve = true because PDA is not a valid ed25519 key
+    wallet.publicKey
   );
-  console.log("Vault ATA:", vaultAta.address.toString());
+  console.log("Vault ATA:", vaultAta.toString());
+  
+  // Mint initial supply to the creator
+  const initialSupply = 1_000_000_000_000; // 1000 tokens with 9 decimals
+  await mintTo(
+    connection,
+    wallet,
+    mint,
+    creatorAta.address,
+    wallet.publicKey,
+    initialSupply
+  );
+  console.log(`Minted ${initialSupply} tokens to the creator`);
   
   // Initialize bonding curve parameters
   const basePriceLamports = 10_000_000; // 0.01 SOL
   const slopeLamports = 100_000; // 0.0001 SOL per token
-  const feeBps = 50; // 0.5%
   const creatorFeeBps = 250; // 2.5%
   const graduationTargetLamports = 10_000_000_000; // 10 SOL
   
   console.log("\nBonding curve parameters:");
   console.log(`Base price: ${basePriceLamports / LAMPORTS_PER_SOL} SOL`);
   console.log(`Slope: ${slopeLamports / LAMPORTS_PER_SOL} SOL/token`);
-  console.log(`Fee: ${feeBps / 100}%`);
   console.log(`Creator fee: ${creatorFeeBps / 100}%`);
   console.log(`Graduation target: ${graduationTargetLamports / LAMPORTS_PER_SOL} SOL`);
   
@@ -196,50 +209,33 @@ async function initializeBondingCurve(mint, vaultSolPda) {
   console.log("Discriminator bytes:", Array.from(discriminator));
   
   try {
-    // Construct initialization data with discriminator
-    const initData = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-    
-    // Write discriminator
-    discriminator.copy(initData, 0);
-    
-    // Write parameters
-    let offset = 8;
-    initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-    initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-    initData.writeUInt16LE(feeBps, offset); offset += 2;
-    initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-    initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-    
     // Create instruction to increase compute budget (to handle complex initialization)
     const computeBudgetIx = ComputeBudgetProgram.setComputeUnitLimit({
       units: 1_000_000
     });
     
-    // Create initialization instruction with proper account structure
-    const initIx = {
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: initData
-    };
+    // Initialize, then move the supply into the vault to open the curve.
+    // The platform fee and its recipient come from the global config.
+    const initIx = initializeLaunchIx(PROGRAM_ID, {
+      payer: wallet.publicKey,
+      creator: wallet.publicKey,
+      mint,
+      basePriceLamports,
+      slopeLamports,
+      creatorFeeBps,
+      graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+    });
+    const fundIx = fundCurveIx(PROGRAM_ID, {
+      creator: wallet.publicKey,
+      mint,
+      amount: (await connection.getTokenAccountBalance(creatorAta.address)).value.amount,
+    });
     
     // Send transaction
     console.log("\nSending initialization transaction...");
     const initTx = new Transaction()
       .add(computeBudgetIx)
-      .add(initIx);
+      .add(initIx, fundIx);
     
     const initSig = await sendAndConfirmTransaction(
       connection, 
@@ -270,7 +266,7 @@ async function initializeBondingCurve(mint, vaultSolPda) {
       authorityPda: authorityPda.toString(),
       statePda: statePda.toString(),
       vaultSolPda: vaultSolPda.toString(),
-      vaultAta: vaultAta.address.toString(),
+      vaultAta: vaultAta.toString(),
       discriminatorName: "global:initialize_launch",
       discriminatorBytes: Array.from(discriminator),
       discriminatorBuy: Array.from(generateDiscriminator("global", "buy")),
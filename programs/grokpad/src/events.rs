@@ -0,0 +1,103 @@
+//! Off-chain decoding of the program's events.
+//!
+//! Built with the `event-cpi` feature, the program emits every event as a
+//! self-CPI: an inner instruction to this program whose data is
+//! `EVENT_IX_TAG_LE`, the event discriminator and the Borsh-encoded event,
+//! signed by the `__event_authority` PDA. RPC nodes cap and truncate program
+//! logs, but inner instructions are stored in full, so an indexer reading
+//! them sees every trade. Without the feature, events only reach the logs
+//! as `Program data:` lines, which `decode_event` reads as well.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::{
+    BuyEvent, ClaimFeesEvent, CloseLaunchEvent, CreatorProposedEvent, CreatorTransferEvent, FeeDelegateEvent,
+    FundCurveEvent, GraduateEvent, InitializeEvent, MigrateEvent, MigrateLaunchEvent, PauseEvent,
+    PlatformRecipientEvent, PoolSeededEvent, SellEvent, SolvencyMismatchEvent, SwapEvent,
+};
+
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// The PDA that signs event self-CPIs, passed as `event_authority` to every
+/// instruction that emits when the program is built with `event-cpi`.
+pub fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &crate::ID).0
+}
+
+macro_rules! grok_events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event the program emits.
+        pub enum GrokEvent {
+            $($name($name),)*
+        }
+
+        impl GrokEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(GrokEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+
+        /// Decodes an event from its discriminator and Borsh body, as carried
+        /// after the self-CPI tag or base64-encoded in a `Program data:` log.
+        pub fn decode_event(data: &[u8]) -> Result<GrokEvent> {
+            $(
+                if let Some(mut body) = data.strip_prefix($name::DISCRIMINATOR) {
+                    return $name::deserialize(&mut body)
+                        .map(GrokEvent::$name)
+                        .map_err(|_| error!(ErrorCode::InstructionDidNotDeserialize));
+                }
+            )*
+            err!(ErrorCode::InstructionDidNotDeserialize)
+        }
+    };
+}
+
+grok_events!(
+    InitializeEvent,
+    FundCurveEvent,
+    BuyEvent,
+    SellEvent,
+    ClaimFeesEvent,
+    CreatorProposedEvent,
+    CreatorTransferEvent,
+    PlatformRecipientEvent,
+    FeeDelegateEvent,
+    GraduateEvent,
+    PoolSeededEvent,
+    MigrateEvent,
+    MigrateLaunchEvent,
+    CloseLaunchEvent,
+    SwapEvent,
+    SolvencyMismatchEvent,
+    PauseEvent,
+);
+
+/// Decodes one instruction of a transaction if it is an event self-CPI of
+/// this program. Other programs' instructions and this program's regular
+/// instructions give `None`; a tagged instruction that doesn't decode is an
+/// error, since only the program can sign one.
+pub fn decode_cpi_event(program_id: &Pubkey, data: &[u8]) -> Result<Option<GrokEvent>> {
+    if *program_id != crate::ID {
+        return Ok(None);
+    }
+    match data.strip_prefix(EVENT_IX_TAG_LE) {
+        Some(event) => decode_event(event).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Extracts the program's events, in emission order, from the inner
+/// instructions of a successful transaction, given as `(program_id, data)`
+/// pairs with program ids already resolved against the account keys.
+pub fn events_from_inner_instructions<'a>(
+    instructions: impl IntoIterator<Item = (&'a Pubkey, &'a [u8])>,
+) -> Result<Vec<GrokEvent>> {
+    instructions
+        .into_iter()
+        .filter_map(|(program_id, data)| decode_cpi_event(program_id, data).transpose())
+        .collect()
+}
@@ -0,0 +1,101 @@
+//! Per-launch graduation criteria.
+//!
+//! A launch lists up to `MAX_GRADUATION_CRITERIA` criteria and graduates as
+//! soon as any one of them is met, so "sold out or market cap" is simply two
+//! entries.
+
+use anchor_lang::prelude::*;
+use grokpad_math::{CurveState, BPS_DENOMINATOR};
+
+use crate::{GrokError, POOL_ALLOCATION_BPS};
+
+pub const MAX_GRADUATION_CRITERIA: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GraduationCriterion {
+    /// Curve reserves reach `lamports`.
+    Reserves { lamports: u64 },
+    /// At least `bps` of the total supply is sold. The curve never sells the
+    /// pool allocation, so it sells out at `10_000 - POOL_ALLOCATION_BPS`.
+    PercentSold { bps: u16 },
+    /// Spot price times total supply reaches `lamports`.
+    MarketCap { lamports: u64 },
+    /// The cluster clock reaches `unix_timestamp`.
+    Deadline { unix_timestamp: i64 },
+}
+
+impl GraduationCriterion {
+    /// Borsh size of the largest variant.
+    pub const LEN: usize = 1 + 8;
+
+    pub fn is_met(&self, curve: &CurveState, total_supply: u64, now: i64) -> bool {
+        match *self {
+            GraduationCriterion::Reserves { lamports } => curve.reserves_lamports >= lamports,
+            GraduationCriterion::PercentSold { bps } => {
+                total_supply > 0
+                    && curve.tokens_sold as u128 * BPS_DENOMINATOR as u128 >= total_supply as u128 * bps as u128
+            }
+            GraduationCriterion::MarketCap { lamports } => match curve.market_cap(total_supply) {
+                Ok(cap) => cap >= lamports,
+                // Past u64 is past any threshold
+                Err(_) => true,
+            },
+            GraduationCriterion::Deadline { unix_timestamp } => now >= unix_timestamp,
+        }
+    }
+
+    fn validate(&self, now: i64) -> Result<()> {
+        let valid = match *self {
+            GraduationCriterion::Reserves { lamports } | GraduationCriterion::MarketCap { lamports } => lamports > 0,
+            // Beyond what the curve sells, a launch could never graduate
+            GraduationCriterion::PercentSold { bps } => {
+                bps > 0 && bps as u64 <= BPS_DENOMINATOR - POOL_ALLOCATION_BPS as u64
+            }
+            GraduationCriterion::Deadline { unix_timestamp } => unix_timestamp > now,
+        };
+        require!(valid, GrokError::InvalidParam);
+        Ok(())
+    }
+}
+
+/// Fixed-size slot holding one criterion in the zero-copy `LaunchState`.
+/// `kind` is 0 for an empty slot, then 1 to 4 in `GraduationCriterion`
+/// order; `value` holds the variant's field, deadlines as their bits.
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct StoredCriterion {
+    pub kind: u8,
+    pub _padding: [u8; 7],
+    pub value: u64,
+}
+
+impl From<GraduationCriterion> for StoredCriterion {
+    fn from(criterion: GraduationCriterion) -> Self {
+        let (kind, value) = match criterion {
+            GraduationCriterion::Reserves { lamports } => (1, lamports),
+            GraduationCriterion::PercentSold { bps } => (2, bps as u64),
+            GraduationCriterion::MarketCap { lamports } => (3, lamports),
+            GraduationCriterion::Deadline { unix_timestamp } => (4, unix_timestamp as u64),
+        };
+        StoredCriterion { kind, _padding: [0; 7], value }
+    }
+}
+
+impl StoredCriterion {
+    /// The stored criterion, or `None` for an empty or unknown slot.
+    pub fn get(&self) -> Option<GraduationCriterion> {
+        match self.kind {
+            1 => Some(GraduationCriterion::Reserves { lamports: self.value }),
+            2 => Some(GraduationCriterion::PercentSold { bps: self.value as u16 }),
+            3 => Some(GraduationCriterion::MarketCap { lamports: self.value }),
+            4 => Some(GraduationCriterion::Deadline { unix_timestamp: self.value as i64 }),
+            _ => None,
+        }
+    }
+}
+
+/// Checks a launch's criteria list before it is stored.
+pub fn validate_criteria(criteria: &[GraduationCriterion], now: i64) -> Result<()> {
+    require!(!criteria.is_empty() && criteria.len() <= MAX_GRADUATION_CRITERIA, GrokError::InvalidParam);
+    criteria.iter().try_for_each(|criterion| criterion.validate(now))
+}
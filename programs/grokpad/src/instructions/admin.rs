@@ -1,6 +1,27 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
 
+use crate::layout::LAUNCH_STATE_VERSION;
+use crate::migration::adapter_for;
+use crate::program::Grokpad;
 use crate::state::GlobalConfig;
+use crate::{
+    GrokError, LaunchState, PauseEvent, PlatformRecipientEvent, CONFIG_SEED, MAX_CREATOR_FEE_BPS, MAX_FEE_BPS, MAX_TOTAL_FEE_BPS,
+    STATE_SEED,
+};
+
+/// Initial platform settings, see `GlobalConfig`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigArgs {
+    pub treasury: Pubkey,
+    pub curve_fee_bps: u16,
+    pub amm_fee_bps: u16,
+    pub creator_kickback_bps: u16,
+    pub max_creator_fee_bps: u16,
+    pub graduation_target_lamports: u64,
+    pub exit_when_paused: bool,
+    pub migration_program: Pubkey,
+}
 
 /// Parameters that can be updated via the admin instruction.  Each
 /// field is optional; if a value is `None` it will not be modified.
@@ -9,22 +30,88 @@ pub struct AdminArgs {
     pub curve_fee_bps: Option<u16>,
     pub amm_fee_bps: Option<u16>,
     pub creator_kickback_bps: Option<u16>,
-    pub graduation_target_usd: Option<u64>,
+    pub max_creator_fee_bps: Option<u16>,
+    pub graduation_target_lamports: Option<u64>,
     pub treasury: Option<Pubkey>,
     pub paused: Option<bool>,
+    pub exit_when_paused: Option<bool>,
+    pub migration_program: Option<Pubkey>,
+}
+
+/// Context for creating the config.  Only the program's upgrade authority
+/// may invoke this, and it becomes the config admin.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GlobalConfig::INIT_SPACE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ GrokError::InvalidOwner)]
+    pub program: Program<'info, Grokpad>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ GrokError::InvalidOwner)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Context for the admin instruction.  Only the admin specified in
 /// `GlobalConfig` may invoke this.
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct Admin<'info> {
-    #[account(mut, has_one = admin)]
+    #[account(mut, seeds = [CONFIG_SEED], bump = global_config.bump, has_one = admin @ GrokError::InvalidOwner)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Context for pausing a single launch.  Only the config admin may invoke
+/// this.
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetLaunchPause<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump, has_one = admin @ GrokError::InvalidOwner)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub admin: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut, seeds = [STATE_SEED, mint.key().as_ref()], bump)]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+}
+
+/// Context for moving launches onto the current treasury.  Only the config
+/// admin may invoke this; the launch states to rotate are passed, writable,
+/// as remaining accounts.
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RotatePlatformRecipient<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump, has_one = admin @ GrokError::InvalidOwner)]
     pub global_config: Account<'info, GlobalConfig>,
     pub admin: Signer<'info>,
 }
 
+pub fn initialize(ctx: Context<InitializeConfig>, args: ConfigArgs) -> Result<()> {
+    let global = &mut ctx.accounts.global_config;
+    global.admin = ctx.accounts.admin.key();
+    global.treasury = args.treasury;
+    global.curve_fee_bps = args.curve_fee_bps;
+    global.amm_fee_bps = args.amm_fee_bps;
+    global.creator_kickback_bps = args.creator_kickback_bps;
+    global.max_creator_fee_bps = args.max_creator_fee_bps;
+    global.graduation_target_lamports = args.graduation_target_lamports;
+    global.paused = false;
+    global.exit_when_paused = args.exit_when_paused;
+    global.migration_program = args.migration_program;
+    global.bump = ctx.bumps.global_config;
+    validate(global)
+}
+
 pub fn handler(ctx: Context<Admin>, args: AdminArgs) -> Result<()> {
     let global = &mut ctx.accounts.global_config;
+    let pause_before = (global.paused, global.exit_when_paused);
     if let Some(fee) = args.curve_fee_bps {
         global.curve_fee_bps = fee;
     }
@@ -34,8 +121,11 @@ pub fn handler(ctx: Context<Admin>, args: AdminArgs) -> Result<()> {
     if let Some(kickback) = args.creator_kickback_bps {
         global.creator_kickback_bps = kickback;
     }
-    if let Some(target) = args.graduation_target_usd {
-        global.graduation_target_usd = target;
+    if let Some(cap) = args.max_creator_fee_bps {
+        global.max_creator_fee_bps = cap;
+    }
+    if let Some(target) = args.graduation_target_lamports {
+        global.graduation_target_lamports = target;
     }
     if let Some(treasury) = args.treasury {
         global.treasury = treasury;
@@ -43,5 +133,64 @@ pub fn handler(ctx: Context<Admin>, args: AdminArgs) -> Result<()> {
     if let Some(paused) = args.paused {
         global.paused = paused;
     }
+    if let Some(exit) = args.exit_when_paused {
+        global.exit_when_paused = exit;
+    }
+    if let Some(program) = args.migration_program {
+        global.migration_program = program;
+    }
+    validate(global)?;
+    if (global.paused, global.exit_when_paused) != pause_before {
+        emit_event!(ctx, PauseEvent { mint: None, paused: global.paused, exit_when_paused: global.exit_when_paused });
+    }
+    Ok(())
+}
+
+pub fn pause_launch(ctx: Context<SetLaunchPause>, paused: bool, exit_when_paused: bool) -> Result<()> {
+    let mut state = ctx.accounts.state_pda.load_mut()?;
+    state.paused = paused as u8;
+    state.exit_when_paused = exit_when_paused as u8;
+    emit_event!(ctx, PauseEvent { mint: Some(state.mint), paused, exit_when_paused });
+    Ok(())
+}
+
+/// Points every launch in `remaining_accounts` at `GlobalConfig::treasury`.
+/// Run after changing the treasury; launches already on it are skipped.  A
+/// fee delegate named by the old recipient is dropped with it.
+pub fn rotate_recipients<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RotatePlatformRecipient<'info>>,
+) -> Result<()> {
+    let treasury = ctx.accounts.global_config.treasury;
+    for info in ctx.remaining_accounts {
+        require!(info.is_writable, GrokError::InvalidParam);
+        let loader = AccountLoader::<LaunchState>::try_from(info)?;
+        // Older layouts share the discriminator, but loading one would read
+        // past its data; they go through `migrate_launch` first
+        require!(info.data_len() == LaunchState::LEN, GrokError::NotMigrated);
+        let mut state = loader.load_mut()?;
+        require!(state.version == LAUNCH_STATE_VERSION, GrokError::NotMigrated);
+        if state.platform_fee_recipient == treasury {
+            continue;
+        }
+        let previous = state.platform_fee_recipient;
+        state.platform_fee_recipient = treasury;
+        state.platform_fee_delegate = Pubkey::default();
+        emit_event!(ctx, PlatformRecipientEvent { mint: state.mint, previous, recipient: treasury });
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// The config has to leave every launch it defaults within the hard caps.
+fn validate(global: &GlobalConfig) -> Result<()> {
+    require!(global.curve_fee_bps <= MAX_FEE_BPS, GrokError::FeeTooHigh);
+    require!(global.amm_fee_bps <= MAX_FEE_BPS, GrokError::FeeTooHigh);
+    require!(global.max_creator_fee_bps <= MAX_CREATOR_FEE_BPS, GrokError::FeeTooHigh);
+    require!(global.creator_kickback_bps <= global.max_creator_fee_bps, GrokError::FeeTooHigh);
+    require!(global.curve_fee_bps + global.max_creator_fee_bps <= MAX_TOTAL_FEE_BPS, GrokError::FeeTooHigh);
+    require!(global.graduation_target_lamports > 0, GrokError::InvalidParam);
+    require!(
+        global.migration_program == Pubkey::default() || adapter_for(&global.migration_program).is_some(),
+        GrokError::UnsupportedAmm
+    );
+    Ok(())
+}
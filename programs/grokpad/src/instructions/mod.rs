@@ -1,2 +1,2 @@
-pub mod initialize_launch;
-pub use initialize_launch::*;
+pub mod admin;
+pub use admin::*;
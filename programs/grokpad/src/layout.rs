@@ -0,0 +1,220 @@
+//! `LaunchState` layout history.
+//!
+//! Launches store `version` and keep `reserved` bytes at the end of the
+//! account. New fields are carved out of `reserved` and must read zero as
+//! their default, so adding one needs neither a realloc nor a migration.
+//! When a change cannot fit there, `LAUNCH_STATE_VERSION` goes up, the
+//! outgoing layout is kept here, and `migrate_launch` learns to upgrade it.
+//!
+//! | version | layout                                            | account bytes        |
+//! |---------|---------------------------------------------------|----------------------|
+//! | 0       | Borsh, the original 174-byte layout               | `LaunchStateV0::LEN` |
+//! | 1       | Borsh, criteria list and `version` and `reserved` | `LaunchStateV1::LEN` |
+//! | 2       | zero-copy `LaunchState`                           | `LaunchState::LEN`   |
+//!
+//! Every layout shares the `LaunchState` discriminator, so the account size
+//! is what tells them apart.
+//!
+//! Fields carved out of version 2's `reserved`:
+//!
+//! - `trade_seq`, from its first 13 bytes: five bytes of padding to align
+//!   the `u64`, then the counter. The trailing padding joined `reserved`.
+//! - `pool_allocation`, from the next 8 bytes. Launches from older layouts
+//!   keep selling their whole supply and upgrade with none, so their pool
+//!   is seeded from whatever the curve leaves unsold.
+
+use anchor_lang::prelude::*;
+
+use crate::graduation::{GraduationCriterion, StoredCriterion, MAX_GRADUATION_CRITERIA};
+use crate::LaunchState;
+
+pub const LAUNCH_STATE_VERSION: u8 = 2;
+pub const LAUNCH_STATE_RESERVED: usize = 48;
+/// `reserved` bytes in the version 1 layout.
+pub const LAUNCH_STATE_V1_RESERVED: usize = 64;
+
+/// Layout version of a `LaunchState` account with `data_len` bytes, if it
+/// is one this program knows.
+pub fn layout_version(data_len: usize) -> Option<u8> {
+    match data_len {
+        LaunchStateV0::LEN => Some(0),
+        LaunchStateV1::LEN => Some(1),
+        LaunchState::LEN => Some(LAUNCH_STATE_VERSION),
+        _ => None,
+    }
+}
+
+/// Decodes a `LaunchState` account, discriminator included, in any layout
+/// the program has used; older layouts are upgraded in memory. This is the
+/// decoder off-chain tools should use, so they read migrated and
+/// unmigrated launches alike.
+pub fn decode_launch_state(data: &[u8]) -> Result<LaunchState> {
+    require!(data.len() >= 8, ErrorCode::AccountDidNotDeserialize);
+    require!(data[..8] == *LaunchState::DISCRIMINATOR, ErrorCode::AccountDiscriminatorMismatch);
+    let body = &data[8..];
+    match layout_version(data.len()) {
+        Some(0) => Ok(borsh_decode::<LaunchStateV0>(body)?.into_v1().upgrade()),
+        Some(1) => Ok(borsh_decode::<LaunchStateV1>(body)?.upgrade()),
+        Some(_) => Ok(bytemuck::pod_read_unaligned(body)),
+        None => err!(ErrorCode::AccountDidNotDeserialize),
+    }
+}
+
+fn borsh_decode<T: AnchorDeserialize>(mut body: &[u8]) -> Result<T> {
+    T::deserialize(&mut body).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// Layout of the first deployed program: one reserves target and no
+/// `total_supply`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LaunchStateV0 {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub base_price_lamports: u64,
+    pub slope_lamports: u64,
+    pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub platform_fee_recipient: Pubkey,
+    pub creator: Pubkey,
+    pub graduation_target_lamports: u64,
+    pub graduated: bool,
+    pub supply_remaining: u64,
+    pub tokens_sold: u64,
+    pub reserves_lamports: u64,
+    pub platform_fee_accrued: u64,
+    pub creator_fee_accrued: u64,
+}
+
+impl LaunchStateV0 {
+    pub const LEN: usize = 8
+        + 32 + 1
+        + 8 + 8
+        + 2 + 2
+        + 32 + 32
+        + 8 + 1
+        + 8 + 8 + 8 + 8 + 8;
+
+    /// The reserves target becomes the launch's only criterion. The curve
+    /// was funded with everything it has sold or still holds, which is
+    /// taken as the total supply.
+    pub fn into_v1(self) -> LaunchStateV1 {
+        LaunchStateV1 {
+            mint: self.mint,
+            decimals: self.decimals,
+            base_price_lamports: self.base_price_lamports,
+            slope_lamports: self.slope_lamports,
+            fee_bps: self.fee_bps,
+            creator_fee_bps: self.creator_fee_bps,
+            platform_fee_recipient: self.platform_fee_recipient,
+            creator: self.creator,
+            graduation_criteria: vec![GraduationCriterion::Reserves { lamports: self.graduation_target_lamports }],
+            graduated: self.graduated,
+            total_supply: self.supply_remaining.saturating_add(self.tokens_sold),
+            supply_remaining: self.supply_remaining,
+            tokens_sold: self.tokens_sold,
+            reserves_lamports: self.reserves_lamports,
+            platform_fee_accrued: self.platform_fee_accrued,
+            creator_fee_accrued: self.creator_fee_accrued,
+            paused: false,
+            exit_when_paused: true,
+            pool: Pubkey::default(),
+            cap_graduating_buy: false,
+            platform_fee_delegate: Pubkey::default(),
+            creator_fee_delegate: Pubkey::default(),
+            pending_creator: Pubkey::default(),
+            volume_lamports: 0,
+            platform_fees_claimed: 0,
+            creator_fees_claimed: 0,
+            version: 1,
+            reserved: [0; LAUNCH_STATE_V1_RESERVED],
+        }
+    }
+}
+
+/// Last Borsh layout, with `version` and `reserved` at the end.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LaunchStateV1 {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub base_price_lamports: u64,
+    pub slope_lamports: u64,
+    pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub platform_fee_recipient: Pubkey,
+    pub creator: Pubkey,
+    pub graduation_criteria: Vec<GraduationCriterion>,
+    pub graduated: bool,
+    pub total_supply: u64,
+    pub supply_remaining: u64,
+    pub tokens_sold: u64,
+    pub reserves_lamports: u64,
+    pub platform_fee_accrued: u64,
+    pub creator_fee_accrued: u64,
+    pub paused: bool,
+    pub exit_when_paused: bool,
+    pub pool: Pubkey,
+    pub cap_graduating_buy: bool,
+    pub platform_fee_delegate: Pubkey,
+    pub creator_fee_delegate: Pubkey,
+    pub pending_creator: Pubkey,
+    pub volume_lamports: u64,
+    pub platform_fees_claimed: u64,
+    pub creator_fees_claimed: u64,
+    pub version: u8,
+    pub reserved: [u8; LAUNCH_STATE_V1_RESERVED],
+}
+
+impl LaunchStateV1 {
+    pub const LEN: usize = 8
+        + 32 + 1
+        + 8 + 8
+        + 2 + 2
+        + 32 + 32
+        + (4 + MAX_GRADUATION_CRITERIA * GraduationCriterion::LEN) + 1 + 8
+        + 8 + 8 + 8 + 8 + 8
+        + 1 + 1
+        + 32 + 1
+        + 32 + 32
+        + 32
+        + 8 + 8 + 8
+        + 1 + LAUNCH_STATE_V1_RESERVED;
+
+    pub fn upgrade(self) -> LaunchState {
+        let mut state = LaunchState {
+            mint: self.mint,
+            platform_fee_recipient: self.platform_fee_recipient,
+            creator: self.creator,
+            pool: self.pool,
+            platform_fee_delegate: self.platform_fee_delegate,
+            creator_fee_delegate: self.creator_fee_delegate,
+            pending_creator: self.pending_creator,
+            base_price_lamports: self.base_price_lamports,
+            slope_lamports: self.slope_lamports,
+            total_supply: self.total_supply,
+            supply_remaining: self.supply_remaining,
+            tokens_sold: self.tokens_sold,
+            reserves_lamports: self.reserves_lamports,
+            platform_fee_accrued: self.platform_fee_accrued,
+            creator_fee_accrued: self.creator_fee_accrued,
+            volume_lamports: self.volume_lamports,
+            platform_fees_claimed: self.platform_fees_claimed,
+            creator_fees_claimed: self.creator_fees_claimed,
+            graduation_criteria: [StoredCriterion::default(); MAX_GRADUATION_CRITERIA],
+            fee_bps: self.fee_bps,
+            creator_fee_bps: self.creator_fee_bps,
+            decimals: self.decimals,
+            graduation_criteria_len: 0,
+            graduated: self.graduated as u8,
+            paused: self.paused as u8,
+            exit_when_paused: self.exit_when_paused as u8,
+            cap_graduating_buy: self.cap_graduating_buy as u8,
+            version: LAUNCH_STATE_VERSION,
+            _padding: [0; 5],
+            trade_seq: 0,
+            pool_allocation: 0,
+            reserved: [0; LAUNCH_STATE_RESERVED],
+        };
+        state.set_criteria(&self.graduation_criteria);
+        state
+    }
+}
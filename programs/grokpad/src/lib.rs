@@ -1,94 +1,541 @@
+// `#[program]` generates Anchor's IDL instructions at the crate root, out of
+// reach of any narrower attribute, and they call the deprecated
+// `AccountInfo::realloc`; the crate's own code uses no deprecated API.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token::{self, spl_token::instruction::AuthorityType, Burn, CloseAccount, Mint, MintTo, SetAuthority, Token, TokenAccount, Transfer},
 };
-use anchor_lang::solana_program::system_program;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use grokpad_math::{BuyQuote, CurveState, PoolState, QuoteError, SellQuote, BPS_DENOMINATOR};
+
+/// Emits `$event` through a self-CPI when built with the `event-cpi`
+/// feature, so indexers read it from inner instructions instead of program
+/// logs, which RPC nodes may truncate; through `emit!` otherwise. The
+/// instruction's accounts must be marked `event_cpi`.
+macro_rules! emit_event {
+    ($ctx:expr, $event:expr) => {{
+        #[cfg(feature = "event-cpi")]
+        {
+            let event = $event;
+            let ctx = &$ctx;
+            emit_cpi!(event);
+        }
+        #[cfg(not(feature = "event-cpi"))]
+        emit!($event);
+    }};
+}
+
+pub mod events;
+pub mod graduation;
+pub mod instructions;
+pub mod layout;
+pub mod migration;
+pub mod state;
+
+use graduation::{GraduationCriterion, StoredCriterion, MAX_GRADUATION_CRITERIA};
+pub use instructions::*;
+use layout::{LAUNCH_STATE_RESERVED, LAUNCH_STATE_VERSION};
+use migration::{LpHandling, MigrationKeys};
+use state::GlobalConfig;
 
 declare_id!("CYUSvq2vmNZ4rcyhfKyfaTKvfeH3doxyqx69ifV3w3TP");
 
 const LAUNCH_SEED: &[u8] = b"launch";
 const STATE_SEED: &[u8] = b"launch_state";
 const VAULT_SOL_SEED: &[u8] = b"vault_sol";
+const CONFIG_SEED: &[u8] = b"config";
+const POOL_SEED: &[u8] = b"pool";
+
+const MAX_PRICE: u64 = 10_000_000_000;
+const MAX_DECIMALS: u8 = 9;
+const MAX_FEE_BPS: u16 = 1_000;
+const MAX_CREATOR_FEE_BPS: u16 = 1_000;
+const MAX_TOTAL_FEE_BPS: u16 = 1_500;
+/// Share of a launch's supply the curve never sells. It stays in
+/// `vault_ata` and seeds the pool at graduation, so a launch that sells out
+/// still has tokens to pair with its reserves.
+pub const POOL_ALLOCATION_BPS: u16 = 2_000;
 
 #[program]
 pub mod grokpad {
     use super::*;
 
+    pub fn initialize_config(ctx: Context<InitializeConfig>, args: ConfigArgs) -> Result<()> {
+        instructions::admin::initialize(ctx, args)
+    }
+
+    pub fn update_config(ctx: Context<Admin>, args: AdminArgs) -> Result<()> {
+        instructions::admin::handler(ctx, args)
+    }
+
+    pub fn set_launch_pause(ctx: Context<SetLaunchPause>, paused: bool, exit_when_paused: bool) -> Result<()> {
+        instructions::admin::pause_launch(ctx, paused, exit_when_paused)
+    }
+
+    pub fn rotate_platform_recipient<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RotatePlatformRecipient<'info>>,
+    ) -> Result<()> {
+        instructions::admin::rotate_recipients(ctx)
+    }
+
     pub fn initialize_launch(
         ctx: Context<InitializeLaunch>,
         base_price_lamports: u64,
         slope_lamports: u64,
-        fee_bps: u16,
-        creator_fee_bps: u16,
-        graduation_target_lamports: u64,
+        creator_fee_bps: Option<u16>,
+        graduation_criteria: Option<Vec<GraduationCriterion>>,
+        cap_graduating_buy: bool,
     ) -> Result<()> {
-        const MAX_PRICE: u64 = 10_000_000_000;
-        require!(base_price_lamports > 0 && base_price_lamports <= MAX_PRICE, GrokError::InvalidParam);
-        require!(slope_lamports <= MAX_PRICE, GrokError::InvalidParam);
         require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
-        let mint = &ctx.accounts.mint;
-        let state = &mut ctx.accounts.state_pda;
-        state.mint = mint.key();
-        state.decimals = mint.decimals;
-        state.base_price_lamports = base_price_lamports;
-        state.slope_lamports = slope_lamports;
-        state.fee_bps = fee_bps;
-        state.creator_fee_bps = creator_fee_bps;
-        state.platform_fee_recipient = ctx.accounts.platform_fee_recipient.key();
-        state.creator = ctx.accounts.creator.key();
-        state.graduation_target_lamports = graduation_target_lamports;
-        state.graduated = false;
-        state.supply_remaining = 0;
-        state.tokens_sold = 0;
-        state.reserves_lamports = 0;
-        state.platform_fee_accrued = 0;
-        state.creator_fee_accrued = 0;
-        emit!(InitializeEvent {
+        let mut state = ctx.accounts.state_pda.load_init()?;
+        init_launch_state(
+            &mut state,
+            &ctx.accounts.mint,
+            ctx.accounts.creator.key(),
+            &ctx.accounts.global_config,
+            base_price_lamports,
+            slope_lamports,
+            creator_fee_bps,
+            graduation_criteria,
+            cap_graduating_buy,
+        )?;
+        emit_event!(ctx, InitializeEvent {
             mint: state.mint,
             base_price: state.base_price_lamports,
             slope: state.slope_lamports,
-            graduation_target_lamports: state.graduation_target_lamports,
+            graduation_criteria: state.criteria().collect(),
+            total_supply: state.total_supply,
         });
         Ok(())
     }
 
-    pub fn buy(ctx: Context<Buy>, amount: u64, max_cost_lamports: u64) -> Result<()> {
-        let state = &mut ctx.accounts.state_pda;
+    /// Fixed-supply launch: the program creates the mint with `authority_pda`
+    /// as mint authority and no freeze authority, mints `total_supply` into
+    /// `vault_ata` and then revokes the mint authority, so the supply can
+    /// never change afterwards.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_launch_with_mint(
+        ctx: Context<InitializeLaunchWithMint>,
+        decimals: u8,
+        total_supply: u64,
+        base_price_lamports: u64,
+        slope_lamports: u64,
+        creator_fee_bps: Option<u16>,
+        graduation_criteria: Option<Vec<GraduationCriterion>>,
+        cap_graduating_buy: bool,
+    ) -> Result<()> {
+        require!(decimals <= MAX_DECIMALS, GrokError::InvalidParam);
+        require!(total_supply > 0, GrokError::InvalidParam);
+        require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+        let mint_key = ctx.accounts.mint.key();
+        let authority_bump = ctx.bumps.authority_pda;
+        let authority_seeds: &[&[u8]] = &[LAUNCH_SEED, mint_key.as_ref(), &[authority_bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault_ata.to_account_info(),
+                    authority: ctx.accounts.authority_pda.to_account_info(),
+                },
+                &[authority_seeds],
+            ),
+            total_supply,
+        )?;
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.authority_pda.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[authority_seeds],
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+        ctx.accounts.mint.reload()?;
+        require!(ctx.accounts.mint.mint_authority.is_none(), GrokError::InvalidParam);
+        require!(ctx.accounts.mint.freeze_authority.is_none(), GrokError::InvalidParam);
+        let mut state = ctx.accounts.state_pda.load_init()?;
+        init_launch_state(
+            &mut state,
+            &ctx.accounts.mint,
+            ctx.accounts.creator.key(),
+            &ctx.accounts.global_config,
+            base_price_lamports,
+            slope_lamports,
+            creator_fee_bps,
+            graduation_criteria,
+            cap_graduating_buy,
+        )?;
+        open_curve(&mut state, total_supply)?;
+        emit_event!(ctx, InitializeEvent {
+            mint: state.mint,
+            base_price: state.base_price_lamports,
+            slope: state.slope_lamports,
+            graduation_criteria: state.criteria().collect(),
+            total_supply: state.total_supply,
+        });
+        Ok(())
+    }
+
+    /// Moves the creator's tokens into `vault_ata` and opens the curve with
+    /// whatever the vault actually holds afterwards, less the pool
+    /// allocation. Only valid once, before any trade has happened.
+    pub fn fund_curve(ctx: Context<FundCurve>, amount: u64) -> Result<()> {
         require!(amount > 0, GrokError::ZeroAmount);
-        require!(!state.graduated, GrokError::LaunchGraduated);
-        require!(amount <= state.supply_remaining, GrokError::NotEnoughSupply);
-        require_keys_eq!(ctx.accounts.platform_fee_recipient.key(), state.platform_fee_recipient, GrokError::InvalidOwner);
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        require!(!state.is_graduated(), GrokError::LaunchGraduated);
+        require_keys_eq!(ctx.accounts.creator.key(), state.creator, GrokError::InvalidOwner);
+        require!(state.supply_remaining == 0 && state.tokens_sold == 0, GrokError::AlreadyFunded);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_ata.to_account_info(),
+                    to: ctx.accounts.vault_ata.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        ctx.accounts.vault_ata.reload()?;
+        open_curve(&mut state, ctx.accounts.vault_ata.amount)?;
+        emit_event!(ctx, FundCurveEvent {
+            mint: state.mint,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            supply_remaining: state.supply_remaining,
+        });
+        Ok(())
+    }
+
+    pub fn buy(ctx: Context<Buy>, amount: u64, max_cost_lamports: u64) -> Result<()> {
+        let (requested, quote) = {
+            let state = ctx.accounts.state_pda.load()?;
+            require_not_paused(&ctx.accounts.global_config, &state)?;
+            require!(!state.is_graduated(), GrokError::LaunchGraduated);
+            let quote = grokpad_math::quote_buy(&state.curve(), amount).map_err(GrokError::from)?;
+            (quote.amount, cap_graduating_buy(&state, quote)?)
+        };
+        require!(quote.cost <= max_cost_lamports, GrokError::SlippageExceeded);
+        settle_buy(ctx, requested, quote)
+    }
+
+    /// Exact-SOL-in buy: fills the largest amount that `lamports_in` pays
+    /// for. Only the cost of the filled amount is taken from the buyer, so
+    /// any remainder never leaves the wallet.
+    pub fn buy_exact_in(ctx: Context<Buy>, lamports_in: u64, min_tokens_out: u64) -> Result<()> {
+        let (requested, quote) = {
+            let state = ctx.accounts.state_pda.load()?;
+            require_not_paused(&ctx.accounts.global_config, &state)?;
+            require!(!state.is_graduated(), GrokError::LaunchGraduated);
+            let quote = grokpad_math::quote_buy_exact_in(&state.curve(), lamports_in).map_err(GrokError::from)?;
+            (quote.amount, cap_graduating_buy(&state, quote)?)
+        };
+        require!(quote.amount >= min_tokens_out, GrokError::SlippageExceeded);
+        require!(quote.cost <= lamports_in, GrokError::SlippageExceeded);
+        settle_buy(ctx, requested, quote)
+    }
+
+    /// Partial-fill buy: fills `min(amount, supply_remaining)`, so a buy that
+    /// races the last of the supply takes what is left instead of failing.
+    /// Only the filled amount is charged, and it still has to cost at most
+    /// `max_cost_lamports` and deliver at least `min_tokens_out`.
+    pub fn buy_partial(ctx: Context<Buy>, amount: u64, max_cost_lamports: u64, min_tokens_out: u64) -> Result<()> {
+        let quote = {
+            let state = ctx.accounts.state_pda.load()?;
+            require_not_paused(&ctx.accounts.global_config, &state)?;
+            require!(!state.is_graduated(), GrokError::LaunchGraduated);
+            require!(amount > 0, GrokError::ZeroAmount);
+            require!(state.supply_remaining > 0, GrokError::NotEnoughSupply);
+            let quote = grokpad_math::quote_buy(&state.curve(), amount.min(state.supply_remaining)).map_err(GrokError::from)?;
+            cap_graduating_buy(&state, quote)?
+        };
+        require!(quote.amount >= min_tokens_out, GrokError::SlippageExceeded);
+        require!(quote.cost <= max_cost_lamports, GrokError::SlippageExceeded);
+        settle_buy(ctx, amount, quote)
+    }
+
+    pub fn sell(ctx: Context<Sell>, amount: u64, min_payout_lamports: u64) -> Result<()> {
+        let quote = {
+            let state = ctx.accounts.state_pda.load()?;
+            require_exit_open(&ctx.accounts.global_config, &state)?;
+            require!(!state.is_graduated(), GrokError::LaunchGraduated);
+            grokpad_math::quote_sell(&state.curve(), amount).map_err(GrokError::from)?
+        };
+        require!(quote.refund_net >= min_payout_lamports, GrokError::SlippageExceeded);
+        settle_sell(ctx, quote)
+    }
+
+    /// Exact-SOL-out sell: returns the fewest tokens whose refund, after
+    /// `fee_bps` and `creator_fee_bps`, is at least `lamports_out`. The seller
+    /// receives the full net refund, which can exceed `lamports_out` by the
+    /// rounding of the fee split.
+    pub fn sell_exact_out(ctx: Context<Sell>, lamports_out: u64, max_tokens_in: u64) -> Result<()> {
+        let quote = {
+            let state = ctx.accounts.state_pda.load()?;
+            require_exit_open(&ctx.accounts.global_config, &state)?;
+            require!(!state.is_graduated(), GrokError::LaunchGraduated);
+            grokpad_math::quote_sell_exact_out(&state.curve(), lamports_out).map_err(GrokError::from)?
+        };
+        require!(quote.amount <= max_tokens_in, GrokError::SlippageExceeded);
+        require!(quote.refund_net >= lamports_out, GrokError::InsufficientReserves);
+        settle_sell(ctx, quote)
+    }
+
+    /// Pays `amount` of the platform fee accrual (all of it with `None`) to
+    /// `destination`. Signed by `platform_fee_recipient` or its delegate.
+    pub fn claim_platform_fees(ctx: Context<ClaimFees>, amount: Option<u64>) -> Result<()> {
+        claim_fees(ctx, FeeKind::Platform, amount)
+    }
+
+    /// Pays `amount` of the creator fee accrual (all of it with `None`) to
+    /// `destination`. Signed by `creator` or its delegate.
+    pub fn claim_creator_fees(ctx: Context<ClaimFees>, amount: Option<u64>) -> Result<()> {
+        claim_fees(ctx, FeeKind::Creator, amount)
+    }
+
+    /// First step of handing the launch to `new_creator`, signed by the
+    /// current creator. Nothing changes until `new_creator` accepts;
+    /// `Pubkey::default()` withdraws the offer.
+    pub fn propose_creator(ctx: Context<ProposeCreator>, new_creator: Pubkey) -> Result<()> {
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
         require_keys_eq!(ctx.accounts.creator.key(), state.creator, GrokError::InvalidOwner);
+        state.pending_creator = new_creator;
+        emit_event!(ctx, CreatorProposedEvent { mint: state.mint, creator: state.creator, pending_creator: new_creator });
+        Ok(())
+    }
+
+    /// Second step: the proposed creator signs to take over. Unclaimed
+    /// creator fees move with the role, and the old creator's fee delegate
+    /// is dropped.
+    pub fn accept_creator(ctx: Context<AcceptCreator>) -> Result<()> {
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        let new_creator = ctx.accounts.new_creator.key();
+        require!(state.pending_creator != Pubkey::default(), GrokError::InvalidOwner);
+        require_keys_eq!(new_creator, state.pending_creator, GrokError::InvalidOwner);
+        let previous = state.creator;
+        state.creator = new_creator;
+        state.pending_creator = Pubkey::default();
+        state.creator_fee_delegate = Pubkey::default();
+        emit_event!(ctx, CreatorTransferEvent { mint: state.mint, previous, creator: new_creator });
+        Ok(())
+    }
+
+    /// Lets `delegate` claim `kind` fees alongside the recipient, who must
+    /// sign. `Pubkey::default()` removes the delegate.
+    pub fn set_fee_delegate(ctx: Context<SetFeeDelegate>, kind: FeeKind, delegate: Pubkey) -> Result<()> {
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        let recipient = match kind {
+            FeeKind::Platform => state.platform_fee_recipient,
+            FeeKind::Creator => state.creator,
+        };
+        require_keys_eq!(ctx.accounts.recipient.key(), recipient, GrokError::InvalidOwner);
+        match kind {
+            FeeKind::Platform => state.platform_fee_delegate = delegate,
+            FeeKind::Creator => state.creator_fee_delegate = delegate,
+        }
+        emit_event!(ctx, FeeDelegateEvent { mint: state.mint, kind, delegate });
+        Ok(())
+    }
+
+    /// Read-only solvency check for monitoring: compares what `vault_sol_pda`
+    /// and `vault_ata` actually hold against what the launch (and its
+    /// in-program pool, once seeded) says they should. Anyone can call it;
+    /// the report comes back as return data, and any difference is also
+    /// emitted as a `SolvencyMismatchEvent`.
+    pub fn verify_launch(ctx: Context<VerifyLaunch>) -> Result<SolvencyReport> {
+        let state = ctx.accounts.state_pda.load()?;
+        let (pool_lamports, pool_tokens) = match &ctx.accounts.pool_pda {
+            Some(pool) => {
+                require_keys_eq!(pool.key(), state.pool, GrokError::InvalidOwner);
+                (pool.sol_reserves, pool.token_reserves)
+            }
+            None => {
+                // Leaving out a seeded pool would read its liquidity as surplus
+                let (pool_key, _) = Pubkey::find_program_address(&[POOL_SEED, state.mint.as_ref()], ctx.program_id);
+                require!(state.pool != pool_key, GrokError::MissingPool);
+                (0, 0)
+            }
+        };
+        let rent = Rent::get()?.minimum_balance(0);
+        let expected_lamports = [state.reserves_lamports, state.platform_fee_accrued, state.creator_fee_accrued, pool_lamports, rent]
+            .into_iter()
+            .try_fold(0u64, |sum, v| sum.checked_add(v))
+            .ok_or(GrokError::Overflow)?;
+        let expected_tokens = state.vault_tokens()?.checked_add(pool_tokens).ok_or(GrokError::Overflow)?;
+        let report = SolvencyReport {
+            mint: state.mint,
+            expected_lamports,
+            actual_lamports: ctx.accounts.vault_sol_pda.lamports(),
+            expected_tokens,
+            actual_tokens: ctx.accounts.vault_ata.amount,
+        };
+        if report.actual_lamports != report.expected_lamports || report.actual_tokens != report.expected_tokens {
+            emit_event!(ctx, SolvencyMismatchEvent {
+                mint: report.mint,
+                expected_lamports: report.expected_lamports,
+                actual_lamports: report.actual_lamports,
+                expected_tokens: report.expected_tokens,
+                actual_tokens: report.actual_tokens,
+                solvent: report.is_solvent(),
+            });
+        }
+        Ok(report)
+    }
+
+    /// Moves a launch that met a graduation criterion onto its pool: the
+    /// curve reserves, the unsold supply and the pool allocation seed
+    /// `pool_pda`, and trading
+    /// continues through the swap instructions. Anyone can call it, also
+    /// after a buy has already flagged the launch as graduated.
+    pub fn graduate(ctx: Context<Graduate>) -> Result<()> {
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        if let Some(event) = require_graduation(&mut state)? {
+            emit_event!(ctx, event);
+        }
+        let tokens = state.vault_tokens()?;
+        require!(state.reserves_lamports > 0 && tokens > 0, GrokError::PoolEmpty);
+        let pool = &mut ctx.accounts.pool_pda;
+        pool.mint = state.mint;
+        pool.sol_reserves = state.reserves_lamports;
+        pool.token_reserves = tokens;
+        pool.fee_bps = ctx.accounts.global_config.amm_fee_bps;
+        pool.bump = ctx.bumps.pool_pda;
+        state.reserves_lamports = 0;
+        state.supply_remaining = 0;
+        state.pool_allocation = 0;
+        state.pool = pool.key();
+        emit_event!(ctx, PoolSeededEvent {
+            mint: pool.mint,
+            pool: pool.key(),
+            sol_reserves: pool.sol_reserves,
+            token_reserves: pool.token_reserves,
+            fee_bps: pool.fee_bps,
+        });
+        Ok(())
+    }
+
+    /// Like `graduate`, but the liquidity goes to the external AMM named by
+    /// `GlobalConfig::migration_program` through its `MigrationAdapter`. The
+    /// AMM's pool is recorded in `LaunchState::pool`, and the LP tokens it
+    /// mints to `authority_pda` are locked there or burned. The only adapter
+    /// so far is the test-only `mock-amm` one, so production builds have no
+    /// migration target and this always fails there; use `graduate`.
+    pub fn graduate_to_amm(ctx: Context<GraduateToAmm>, lp_handling: LpHandling) -> Result<()> {
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        if let Some(event) = require_graduation(&mut state)? {
+            emit_event!(ctx, event);
+        }
+        let lamports = state.reserves_lamports;
+        let tokens = state.vault_tokens()?;
+        require!(lamports > 0 && tokens > 0, GrokError::PoolEmpty);
         require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
-        let scale = 10u128.pow(state.decimals as u32);
-        let units = amount as u128 / scale;
-        require!(units > 0, GrokError::ZeroUnits);
-        let sold_units_before = state.tokens_sold as u128 / scale;
-        let cost_u128 = linear_buy_cost(
-            state.base_price_lamports as u128,
-            state.slope_lamports as u128,
-            sold_units_before,
-            units,
+        let adapter = migration::adapter_for(&ctx.accounts.amm_program.key()).ok_or(GrokError::UnsupportedAmm)?;
+        let keys = MigrationKeys {
+            payer: ctx.accounts.caller.key(),
+            sol_source: ctx.accounts.vault_sol_pda.key(),
+            token_authority: ctx.accounts.authority_pda.key(),
+            mint: ctx.accounts.mint.key(),
+            token_source: ctx.accounts.vault_ata.key(),
+            pool: ctx.accounts.amm_pool.key(),
+            pool_token_vault: ctx.accounts.amm_pool_token_vault.key(),
+            lp_mint: ctx.accounts.lp_mint.key(),
+            lp_destination: ctx.accounts.lp_destination.key(),
+        };
+        let ix = adapter.create_pool_ix(&keys, lamports, tokens);
+        let vault_lamports_before = ctx.accounts.vault_sol_pda.lamports();
+        let vault_tokens_before = ctx.accounts.vault_ata.amount;
+        let mint_key = ctx.accounts.mint.key();
+        let authority_seeds: &[&[u8]] = &[LAUNCH_SEED, mint_key.as_ref(), &[ctx.bumps.authority_pda]];
+        let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, mint_key.as_ref(), &[ctx.bumps.vault_sol_pda]];
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.caller.to_account_info(),
+                ctx.accounts.vault_sol_pda.to_account_info(),
+                ctx.accounts.authority_pda.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.vault_ata.to_account_info(),
+                ctx.accounts.amm_pool.to_account_info(),
+                ctx.accounts.amm_pool_token_vault.to_account_info(),
+                ctx.accounts.lp_mint.to_account_info(),
+                ctx.accounts.lp_destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.associated_token_program.to_account_info(),
+                ctx.accounts.rent.to_account_info(),
+            ],
+            &[authority_seeds, vault_sol_seeds],
+        )?;
+        // The vaults also hold fees and rent, which the AMM must leave alone
+        ctx.accounts.vault_ata.reload()?;
+        require!(
+            vault_lamports_before.checked_sub(ctx.accounts.vault_sol_pda.lamports()) == Some(lamports)
+                && vault_tokens_before.checked_sub(ctx.accounts.vault_ata.amount) == Some(tokens),
+            GrokError::MigrationMismatch
         );
-        require!(cost_u128 <= u64::MAX as u128, GrokError::Overflow);
-        let cost = cost_u128 as u64;
-        require!(cost <= max_cost_lamports, GrokError::SlippageExceeded);
-        let platform_fee = cost.saturating_mul(state.fee_bps as u64) / 10_000;
-        let creator_fee = cost.saturating_mul(state.creator_fee_bps as u64) / 10_000;
-        let net = cost
-            .checked_sub(platform_fee)
-            .and_then(|v| v.checked_sub(creator_fee))
-            .ok_or(GrokError::Underflow)?;
+        // The adapter promised LP to an account `authority_pda` controls
+        require_keys_eq!(*ctx.accounts.lp_destination.owner, token::ID, GrokError::InvalidOwner);
+        let lp_account = TokenAccount::try_deserialize(&mut &ctx.accounts.lp_destination.try_borrow_data()?[..])?;
+        require_keys_eq!(lp_account.owner, ctx.accounts.authority_pda.key(), GrokError::InvalidOwner);
+        let lp_amount = lp_account.amount;
+        if lp_handling == LpHandling::Burn && lp_amount > 0 {
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.lp_mint.to_account_info(),
+                        from: ctx.accounts.lp_destination.to_account_info(),
+                        authority: ctx.accounts.authority_pda.to_account_info(),
+                    },
+                    &[authority_seeds],
+                ),
+                lp_amount,
+            )?;
+        }
+        state.reserves_lamports = 0;
+        state.supply_remaining = 0;
+        state.pool_allocation = 0;
+        state.pool = ctx.accounts.amm_pool.key();
+        emit_event!(ctx, MigrateEvent {
+            mint: state.mint,
+            amm_program: ctx.accounts.amm_program.key(),
+            pool: state.pool,
+            sol_amount: lamports,
+            token_amount: tokens,
+            lp_amount,
+            lp_burned: lp_handling == LpHandling::Burn,
+        });
+        Ok(())
+    }
+
+    pub fn swap_sol_for_tokens(ctx: Context<Swap>, lamports_in: u64, min_tokens_out: u64) -> Result<()> {
+        require_not_paused(&ctx.accounts.global_config, &*ctx.accounts.state_pda.load()?)?;
+        require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+        let quote = grokpad_math::quote_swap_sol_in(&ctx.accounts.pool_pda.pool(), lamports_in).map_err(GrokError::from)?;
+        require!(quote.amount_out >= min_tokens_out, GrokError::SlippageExceeded);
         anchor_lang::system_program::transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
                 anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
+                    from: ctx.accounts.trader.to_account_info(),
                     to: ctx.accounts.vault_sol_pda.to_account_info(),
                 },
             ),
-            cost,
+            quote.amount_in,
         )?;
         let mint_key = ctx.accounts.mint.key();
         let authority_bump = ctx.bumps.authority_pda;
@@ -98,88 +545,55 @@ pub mod grokpad {
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
                     from: ctx.accounts.vault_ata.to_account_info(),
-                    to: ctx.accounts.buyer_ata.to_account_info(),
+                    to: ctx.accounts.trader_ata.to_account_info(),
                     authority: ctx.accounts.authority_pda.to_account_info(),
                 },
                 &[authority_seeds],
             ),
-            amount,
+            quote.amount_out,
         )?;
-        state.tokens_sold = state.tokens_sold.checked_add(amount).ok_or(GrokError::Overflow)?;
-        state.supply_remaining = state
-            .supply_remaining
-            .checked_sub(amount)
-            .ok_or(GrokError::Underflow)?;
-        state.platform_fee_accrued = state
-            .platform_fee_accrued
-            .checked_add(platform_fee)
-            .ok_or(GrokError::Overflow)?;
-        state.creator_fee_accrued = state
-            .creator_fee_accrued
-            .checked_add(creator_fee)
-            .ok_or(GrokError::Overflow)?;
-        state.reserves_lamports = state
-            .reserves_lamports
-            .checked_add(net)
-            .ok_or(GrokError::Overflow)?;
-        if !state.graduated && state.reserves_lamports >= state.graduation_target_lamports {
-            state.graduated = true;
-            emit!(GraduateEvent { mint: state.mint, reserves_lamports: state.reserves_lamports, tokens_sold: state.tokens_sold });
-            msg!("Graduated: reserves {}", state.reserves_lamports);
-        }
-        emit!(BuyEvent {
-            mint: state.mint,
-            buyer: ctx.accounts.buyer.key(),
-            amount,
-            cost_lamports: cost,
-            platform_fee,
-            creator_fee,
-            reserves_after: state.reserves_lamports,
-            tokens_sold_after: state.tokens_sold,
-            graduated: state.graduated,
+        let pool_before = ctx.accounts.pool_pda.pool();
+        ctx.accounts.pool_pda.set_pool(&quote.after);
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        state.platform_fee_accrued = state.platform_fee_accrued.checked_add(quote.fee).ok_or(GrokError::Overflow)?;
+        state.volume_lamports = state.volume_lamports.saturating_add(quote.amount_in);
+        let trade_seq = state.next_trade_seq()?;
+        let clock = Clock::get()?;
+        emit_event!(ctx, SwapEvent {
+            mint: mint_key,
+            trader: ctx.accounts.trader.key(),
+            sol_in: true,
+            amount_in: quote.amount_in,
+            amount_out: quote.amount_out,
+            fee: quote.fee,
+            sol_reserves_after: quote.after.sol_reserves,
+            token_reserves_after: quote.after.token_reserves,
+            decimals: state.decimals,
+            price_before: state.pool_price(&pool_before),
+            price_after: state.pool_price(&quote.after),
+            trade_seq,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
         });
         Ok(())
     }
 
-    pub fn sell(ctx: Context<Sell>, amount: u64, min_payout_lamports: u64) -> Result<()> {
-        let state = &mut ctx.accounts.state_pda;
-        require!(amount > 0, GrokError::ZeroAmount);
-        require!(!state.graduated, GrokError::LaunchGraduated);
-        require_keys_eq!(ctx.accounts.platform_fee_recipient.key(), state.platform_fee_recipient, GrokError::InvalidOwner);
-        require_keys_eq!(ctx.accounts.creator.key(), state.creator, GrokError::InvalidOwner);
+    /// Stays open as the emergency exit while paused, like `sell`.
+    pub fn swap_tokens_for_sol(ctx: Context<Swap>, tokens_in: u64, min_lamports_out: u64) -> Result<()> {
+        require_exit_open(&ctx.accounts.global_config, &*ctx.accounts.state_pda.load()?)?;
         require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
-        let scale = 10u128.pow(state.decimals as u32);
-        let units = amount as u128 / scale;
-        require!(units > 0, GrokError::ZeroUnits);
-        let total_units_sold = state.tokens_sold as u128 / scale;
-        require!(units <= total_units_sold, GrokError::Underflow);
-        let start_units = total_units_sold - units;
-        let refund_u128 = linear_buy_cost(
-            state.base_price_lamports as u128,
-            state.slope_lamports as u128,
-            start_units,
-            units,
-        );
-        require!(refund_u128 <= u64::MAX as u128, GrokError::Overflow);
-        let refund_gross = refund_u128 as u64;
-        let platform_fee = refund_gross.saturating_mul(state.fee_bps as u64) / 10_000;
-        let creator_fee = refund_gross.saturating_mul(state.creator_fee_bps as u64) / 10_000;
-        let refund_net = refund_gross
-            .checked_sub(platform_fee)
-            .and_then(|v| v.checked_sub(creator_fee))
-            .ok_or(GrokError::Underflow)?;
-        require!(refund_net >= min_payout_lamports, GrokError::SlippageExceeded);
-        require!(refund_net <= state.reserves_lamports, GrokError::Underflow);
+        let quote = grokpad_math::quote_swap_tokens_in(&ctx.accounts.pool_pda.pool(), tokens_in).map_err(GrokError::from)?;
+        require!(quote.amount_out >= min_lamports_out, GrokError::SlippageExceeded);
         token::transfer(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.seller_ata.to_account_info(),
+                    from: ctx.accounts.trader_ata.to_account_info(),
                     to: ctx.accounts.vault_ata.to_account_info(),
-                    authority: ctx.accounts.seller.to_account_info(),
+                    authority: ctx.accounts.trader.to_account_info(),
                 },
             ),
-            amount,
+            quote.amount_in,
         )?;
         let mint_key = ctx.accounts.mint.key();
         let vault_sol_bump = ctx.bumps.vault_sol_pda;
@@ -189,143 +603,369 @@ pub mod grokpad {
                 ctx.accounts.system_program.to_account_info(),
                 anchor_lang::system_program::Transfer {
                     from: ctx.accounts.vault_sol_pda.to_account_info(),
-                    to: ctx.accounts.seller.to_account_info(),
+                    to: ctx.accounts.trader.to_account_info(),
                 },
                 &[vault_sol_seeds],
             ),
-            refund_net,
+            quote.amount_out,
         )?;
-        state.platform_fee_accrued = state
-            .platform_fee_accrued
-            .checked_add(platform_fee)
-            .ok_or(GrokError::Overflow)?;
-        state.creator_fee_accrued = state
-            .creator_fee_accrued
-            .checked_add(creator_fee)
-            .ok_or(GrokError::Overflow)?;
-        state.reserves_lamports = state
-            .reserves_lamports
-            .checked_sub(refund_net)
-            .ok_or(GrokError::Underflow)?;
-        state.tokens_sold = state
-            .tokens_sold
-            .checked_sub(amount)
-            .ok_or(GrokError::Underflow)?;
-        state.supply_remaining = state
-            .supply_remaining
-            .checked_add(amount)
-            .ok_or(GrokError::Overflow)?;
-        emit!(SellEvent {
-            mint: state.mint,
-            seller: ctx.accounts.seller.key(),
-            amount,
-            refund_net,
-            platform_fee,
-            creator_fee,
-            reserves_after: state.reserves_lamports,
-            tokens_sold_after: state.tokens_sold,
+        let pool_before = ctx.accounts.pool_pda.pool();
+        ctx.accounts.pool_pda.set_pool(&quote.after);
+        let mut state = ctx.accounts.state_pda.load_mut()?;
+        state.platform_fee_accrued = state.platform_fee_accrued.checked_add(quote.fee).ok_or(GrokError::Overflow)?;
+        state.volume_lamports = state.volume_lamports.saturating_add(quote.amount_out);
+        let trade_seq = state.next_trade_seq()?;
+        let clock = Clock::get()?;
+        emit_event!(ctx, SwapEvent {
+            mint: mint_key,
+            trader: ctx.accounts.trader.key(),
+            sol_in: false,
+            amount_in: quote.amount_in,
+            amount_out: quote.amount_out,
+            fee: quote.fee,
+            sol_reserves_after: quote.after.sol_reserves,
+            token_reserves_after: quote.after.token_reserves,
+            decimals: state.decimals,
+            price_before: state.pool_price(&pool_before),
+            price_after: state.pool_price(&quote.after),
+            trade_seq,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
         });
         Ok(())
     }
 
-    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
-        let state = &mut ctx.accounts.state_pda;
-        require_keys_eq!(ctx.accounts.platform_fee_recipient.key(), state.platform_fee_recipient, GrokError::InvalidOwner);
-        require_keys_eq!(ctx.accounts.creator.key(), state.creator, GrokError::InvalidOwner);
-        if state.platform_fee_accrued == 0 && state.creator_fee_accrued == 0 { return Ok(()); }
-        require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+    /// Closes a graduated launch whose liquidity has moved to an external
+    /// AMM and whose fees are all claimed. Stray tokens left in the vault
+    /// are burned, and the rent of `state_pda`, `vault_sol_pda` and
+    /// `vault_ata` goes to the creator. Launches trading in the in-program
+    /// pool keep their vaults and cannot close.
+    pub fn close_launch(ctx: Context<CloseLaunch>) -> Result<()> {
+        let state = ctx.accounts.state_pda.load()?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        require!(state.is_graduated(), GrokError::NotYetGraduate);
+        require!(state.platform_fee_accrued == 0 && state.creator_fee_accrued == 0, GrokError::NotSettled);
+        require!(state.reserves_lamports == 0 && state.vault_tokens()? == 0, GrokError::NotSettled);
         let mint_key = ctx.accounts.mint.key();
-        let vault_sol_bump = ctx.bumps.vault_sol_pda;
-        let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, mint_key.as_ref(), &[vault_sol_bump]];
-        let mut platform_withdrawn = 0;
-        let mut creator_withdrawn = 0;
-        if state.platform_fee_accrued > 0 {
-            let amount = state.platform_fee_accrued;
-            anchor_lang::system_program::transfer(
+        let (pool_key, _) = Pubkey::find_program_address(&[POOL_SEED, mint_key.as_ref()], ctx.program_id);
+        require!(state.pool != pool_key, GrokError::NotSettled);
+        require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+
+        let authority_seeds: &[&[u8]] = &[LAUNCH_SEED, mint_key.as_ref(), &[ctx.bumps.authority_pda]];
+        let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, mint_key.as_ref(), &[ctx.bumps.vault_sol_pda]];
+        let tokens_burned = ctx.accounts.vault_ata.amount;
+        if tokens_burned > 0 {
+            token::burn(
                 CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.vault_sol_pda.to_account_info(),
-                        to: ctx.accounts.platform_fee_recipient.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.vault_ata.to_account_info(),
+                        authority: ctx.accounts.authority_pda.to_account_info(),
                     },
-                    &[vault_sol_seeds],
+                    &[authority_seeds],
                 ),
-                amount,
+                tokens_burned,
             )?;
-            state.reserves_lamports = state.reserves_lamports.checked_sub(amount).ok_or(GrokError::Underflow)?;
-            platform_withdrawn = amount;
-            state.platform_fee_accrued = 0;
         }
-        if state.creator_fee_accrued > 0 {
-            let amount = state.creator_fee_accrued;
+        let rent_reclaimed = [
+            ctx.accounts.state_pda.to_account_info().lamports(),
+            ctx.accounts.vault_sol_pda.lamports(),
+            ctx.accounts.vault_ata.to_account_info().lamports(),
+        ]
+        .into_iter()
+        .try_fold(0u64, |sum, v| sum.checked_add(v))
+        .ok_or(GrokError::Overflow)?;
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault_ata.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.authority_pda.to_account_info(),
+            },
+            &[authority_seeds],
+        ))?;
+        // Emptying the system-owned vault lets the runtime reclaim it
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.vault_sol_pda.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+                &[vault_sol_seeds],
+            ),
+            ctx.accounts.vault_sol_pda.lamports(),
+        )?;
+        emit_event!(ctx, CloseLaunchEvent {
+            mint: state.mint,
+            creator: state.creator,
+            pool: state.pool,
+            total_supply: state.total_supply,
+            tokens_sold: state.tokens_sold,
+            volume_lamports: state.volume_lamports,
+            platform_fees_claimed: state.platform_fees_claimed,
+            creator_fees_claimed: state.creator_fees_claimed,
+            tokens_burned,
+            rent_reclaimed,
+        });
+        Ok(())
+    }
+
+    /// Rewrites a launch stored in an older layout (see `layout`) into the
+    /// current one, resizing the account to `LaunchState::LEN`. Anyone can
+    /// run it; `payer` covers any extra rent. Old launches have to go
+    /// through this before they can trade again.
+    pub fn migrate_launch(ctx: Context<MigrateLaunch>) -> Result<()> {
+        let info = ctx.accounts.state_pda.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, GrokError::InvalidOwner);
+        require!(info.data_len() != LaunchState::LEN, GrokError::AlreadyMigrated);
+        let from_version = layout::layout_version(info.data_len()).ok_or(ErrorCode::AccountDidNotDeserialize)?;
+        let state = layout::decode_launch_state(&info.try_borrow_data()?)?;
+        require_not_paused(&ctx.accounts.global_config, &state)?;
+        let rent_due = Rent::get()?.minimum_balance(LaunchState::LEN).saturating_sub(info.lamports());
+        if rent_due > 0 {
             anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
+                CpiContext::new(
                     ctx.accounts.system_program.to_account_info(),
                     anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.vault_sol_pda.to_account_info(),
-                        to: ctx.accounts.creator.to_account_info(),
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: info.clone(),
                     },
-                    &[vault_sol_seeds],
                 ),
-                amount,
+                rent_due,
             )?;
-            state.reserves_lamports = state.reserves_lamports.checked_sub(amount).ok_or(GrokError::Underflow)?;
-            creator_withdrawn = amount;
-            state.creator_fee_accrued = 0;
         }
-        if platform_withdrawn == 0 && creator_withdrawn == 0 { return Ok(()); }
-        emit!(WithdrawFeesEvent { mint: state.mint, platform_withdrawn, creator_withdrawn });
-        Ok(())
-    }
-
-    pub fn graduate(ctx: Context<Graduate>) -> Result<()> {
-        let state = &mut ctx.accounts.state_pda;
-        require!(!state.graduated, GrokError::LaunchGraduated);
-        require!(state.reserves_lamports >= state.graduation_target_lamports, GrokError::NotYetGraduate);
-        state.graduated = true;
-        emit!(GraduateEvent { mint: state.mint, reserves_lamports: state.reserves_lamports, tokens_sold: state.tokens_sold });
+        info.resize(LaunchState::LEN)?;
+        info.try_borrow_mut_data()?[8..].copy_from_slice(bytemuck::bytes_of(&state));
+        emit_event!(ctx, MigrateLaunchEvent { mint: state.mint, from_version, to_version: state.version });
         Ok(())
     }
 }
 
 /* ---------------- Accounts ---------------- */
 
+/// Constant-product pool a launch trades in after `graduate`. The lamports
+/// sit in `vault_sol_pda` and the tokens in `vault_ata`, next to the
+/// launch's accrued fees; these fields only track the pool's share.
 #[account]
-pub struct LaunchState {
+pub struct Pool {
     pub mint: Pubkey,
-    pub decimals: u8,
-    pub base_price_lamports: u64,
-    pub slope_lamports: u64,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
     pub fee_bps: u16,
-    pub creator_fee_bps: u16,
+    pub bump: u8,
+}
+impl Pool {
+    pub fn pool(&self) -> PoolState {
+        PoolState { sol_reserves: self.sol_reserves, token_reserves: self.token_reserves, fee_bps: self.fee_bps }
+    }
+
+    pub fn set_pool(&mut self, pool: &PoolState) {
+        self.sol_reserves = pool.sol_reserves;
+        self.token_reserves = pool.token_reserves;
+    }
+
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 2 + 1;
+}
+
+/// Per-launch state, read in place through `AccountLoader` so trades skip
+/// Borsh entirely. The layout is `repr(C)` with every field at its natural
+/// alignment: keys, then `u64`s, then the criteria slots, then the small
+/// fields, padded out to a multiple of eight bytes. Flags are `u8`s, 0 or 1.
+#[account(zero_copy)]
+pub struct LaunchState {
+    pub mint: Pubkey,
     pub platform_fee_recipient: Pubkey,
     pub creator: Pubkey,
-    pub graduation_target_lamports: u64,
-    pub graduated: bool,
+    /// Pool the launch trades in after graduation, in-program or external.
+    pub pool: Pubkey,
+    /// May claim platform fees besides `platform_fee_recipient`; default when unset.
+    pub platform_fee_delegate: Pubkey,
+    /// May claim creator fees besides `creator`; default when unset.
+    pub creator_fee_delegate: Pubkey,
+    /// Proposed by `creator` and waiting to accept; default when none.
+    pub pending_creator: Pubkey,
 
+    pub base_price_lamports: u64,
+    pub slope_lamports: u64,
+    pub total_supply: u64,
     pub supply_remaining: u64,
     pub tokens_sold: u64,
     pub reserves_lamports: u64,
     pub platform_fee_accrued: u64,
     pub creator_fee_accrued: u64,
+    /// Lifetime totals, reported when the launch closes. Volume counts the
+    /// lamports paid in or out by every curve trade and pool swap.
+    pub volume_lamports: u64,
+    pub platform_fees_claimed: u64,
+    pub creator_fees_claimed: u64,
+
+    /// Graduates on the first of these that is met; see `criteria`.
+    pub graduation_criteria: [StoredCriterion; MAX_GRADUATION_CRITERIA],
+
+    pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub decimals: u8,
+    pub graduation_criteria_len: u8,
+    pub graduated: u8,
+    /// Set by the admin to halt this launch without pausing the platform.
+    pub paused: u8,
+    /// Whether sells stay open while `paused` is set.
+    pub exit_when_paused: u8,
+    /// Fill a buy that crosses the reserves target only up to the target.
+    pub cap_graduating_buy: u8,
+    /// Layout version, see `layout`.
+    pub version: u8,
+    pub _padding: [u8; 5],
+    /// Curve trades and pool swaps so far; each trade event carries its
+    /// number, counting from 1.
+    pub trade_seq: u64,
+    /// Tokens held back from the curve for the pool; see `POOL_ALLOCATION_BPS`.
+    pub pool_allocation: u64,
+    /// Zeroed space for future fields.
+    pub reserved: [u8; LAUNCH_STATE_RESERVED],
+}
+
+/// Which of a launch's two fee accruals an instruction acts on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeKind {
+    Platform,
+    Creator,
 }
 impl LaunchState {
-    pub const LEN: usize = 8
-        + 32 + 1
-        + 8 + 8
-        + 2 + 2
-        + 32 + 32
-        + 8 + 1
-        + 8 + 8 + 8 + 8 + 8;
+    pub const LEN: usize = 8 + core::mem::size_of::<LaunchState>();
+
+    pub fn is_graduated(&self) -> bool {
+        self.graduated != 0
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    /// Pricing view of this launch for `grokpad_math` quotes.
+    pub fn curve(&self) -> CurveState {
+        CurveState {
+            base_price_lamports: self.base_price_lamports,
+            slope_lamports: self.slope_lamports,
+            decimals: self.decimals,
+            fee_bps: self.fee_bps,
+            creator_fee_bps: self.creator_fee_bps,
+            supply_remaining: self.supply_remaining,
+            tokens_sold: self.tokens_sold,
+            reserves_lamports: self.reserves_lamports,
+            platform_fee_accrued: self.platform_fee_accrued,
+            creator_fee_accrued: self.creator_fee_accrued,
+        }
+    }
+
+    /// Marginal curve price of a whole token, in lamports. Saturates rather
+    /// than fail the trade whose event reports it.
+    pub fn spot_price(&self) -> u64 {
+        self.curve().spot_price().unwrap_or(u64::MAX)
+    }
+
+    /// Marginal price of a whole token in `pool`, in lamports, saturating
+    /// like `spot_price`.
+    pub fn pool_price(&self, pool: &PoolState) -> u64 {
+        self.curve().scale().and_then(|scale| pool.spot_price(scale)).unwrap_or(u64::MAX)
+    }
+
+    /// Counts a trade and returns its number.
+    pub fn next_trade_seq(&mut self) -> Result<u64> {
+        self.trade_seq = self.trade_seq.checked_add(1).ok_or(GrokError::Overflow)?;
+        Ok(self.trade_seq)
+    }
+
+    /// The launch's graduation criteria, in the order they were given.
+    pub fn criteria(&self) -> impl Iterator<Item = GraduationCriterion> + '_ {
+        self.graduation_criteria[..self.graduation_criteria_len as usize]
+            .iter()
+            .filter_map(StoredCriterion::get)
+    }
+
+    /// Stores an already validated criteria list.
+    pub fn set_criteria(&mut self, criteria: &[GraduationCriterion]) {
+        self.graduation_criteria = [StoredCriterion::default(); MAX_GRADUATION_CRITERIA];
+        for (slot, criterion) in self.graduation_criteria.iter_mut().zip(criteria) {
+            *slot = (*criterion).into();
+        }
+        self.graduation_criteria_len = criteria.len().min(MAX_GRADUATION_CRITERIA) as u8;
+    }
+
+    /// Lowest reserves target among the graduation criteria.
+    pub fn reserves_target(&self) -> Option<u64> {
+        self.criteria()
+            .filter_map(|c| match c {
+                GraduationCriterion::Reserves { lamports } => Some(lamports),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// First graduation criterion the launch currently meets. Selling out
+    /// the curve always graduates, whether or not it is one of the
+    /// criteria, and is reported as the share of the total supply sold.
+    pub fn met_criterion(&self, now: i64) -> Option<GraduationCriterion> {
+        let curve = self.curve();
+        let sold_out = (self.supply_remaining == 0 && self.tokens_sold > 0).then(|| {
+            let bps = self.tokens_sold as u128 * BPS_DENOMINATOR as u128 / self.total_supply.max(1) as u128;
+            GraduationCriterion::PercentSold { bps: bps.min(BPS_DENOMINATOR as u128) as u16 }
+        });
+        self.criteria().find(|c| c.is_met(&curve, self.total_supply, now)).or(sold_out)
+    }
+
+    /// Tokens `vault_ata` holds for the launch outside any pool: the
+    /// curve's unsold supply and the pool allocation.
+    pub fn vault_tokens(&self) -> Result<u64> {
+        Ok(self.supply_remaining.checked_add(self.pool_allocation).ok_or(GrokError::Overflow)?)
+    }
+
+    /// Whether `key` may claim `kind` fees: the recipient or its delegate.
+    pub fn can_claim(&self, kind: FeeKind, key: &Pubkey) -> bool {
+        let (recipient, delegate) = match kind {
+            FeeKind::Platform => (self.platform_fee_recipient, self.platform_fee_delegate),
+            FeeKind::Creator => (self.creator, self.creator_fee_delegate),
+        };
+        *key == recipient || (delegate != Pubkey::default() && *key == delegate)
+    }
+
+    /// Deducts `amount` (everything with `None`) from the `kind` accrual and
+    /// returns it. Fees are kept outside `reserves_lamports`, so reserves are
+    /// untouched.
+    pub fn take_fee(&mut self, kind: FeeKind, amount: Option<u64>) -> Result<u64> {
+        let (accrued, claimed) = match kind {
+            FeeKind::Platform => (&mut self.platform_fee_accrued, &mut self.platform_fees_claimed),
+            FeeKind::Creator => (&mut self.creator_fee_accrued, &mut self.creator_fees_claimed),
+        };
+        let amount = amount.unwrap_or(*accrued);
+        require!(amount > 0, GrokError::InvalidParam);
+        require!(amount <= *accrued, GrokError::InsufficientFees);
+        *accrued -= amount;
+        *claimed = claimed.saturating_add(amount);
+        Ok(amount)
+    }
+
+    /// Stores the trade-mutable half of a quote's post-trade state.
+    pub fn set_curve(&mut self, curve: &CurveState) {
+        self.supply_remaining = curve.supply_remaining;
+        self.tokens_sold = curve.tokens_sold;
+        self.reserves_lamports = curve.reserves_lamports;
+        self.platform_fee_accrued = curve.platform_fee_accrued;
+        self.creator_fee_accrued = curve.creator_fee_accrued;
+    }
 }
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
-#[instruction(fee_bps: u16, creator_fee_bps: u16)]
 pub struct InitializeLaunch<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    /// CHECK: recorded as the launch creator
     pub creator: UncheckedAccount<'info>,
-    pub platform_fee_recipient: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
     pub mint: Account<'info, Mint>,
 
     #[account(
@@ -341,12 +981,13 @@ pub struct InitializeLaunch<'info> {
         seeds = [STATE_SEED, mint.key().as_ref()],
         bump
     )]
-    pub state_pda: Account<'info, LaunchState>,
+    pub state_pda: AccountLoader<'info, LaunchState>,
 
     #[account(
         init,
         payer = payer,
         space = 0,
+        owner = system_program::ID,
         seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
         bump
     )]
@@ -366,17 +1007,112 @@ pub struct InitializeLaunch<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitializeLaunchWithMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: recorded as the launch creator
+    pub creator: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = decimals,
+        mint::authority = authority_pda,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [LAUNCH_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub authority_pda: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = LaunchState::LEN,
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 0,
+        owner = system_program::ID,
+        seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_sol_pda: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = authority_pda
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FundCurve<'info> {
+    pub creator: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+    #[account(
+        seeds = [LAUNCH_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub authority_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority_pda
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct Buy<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
     pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
     #[account(
         mut,
         seeds = [STATE_SEED, mint.key().as_ref()],
         bump,
     )]
-    pub state_pda: Account<'info, LaunchState>,
+    pub state_pda: AccountLoader<'info, LaunchState>,
     #[account(
         seeds = [LAUNCH_SEED, mint.key().as_ref()],
         bump
@@ -394,34 +1130,35 @@ pub struct Buy<'info> {
         associated_token::authority = authority_pda
     )]
     pub vault_ata: Account<'info, TokenAccount>,
+    /// CHECK: wallet that receives the tokens; pass `buyer` to buy for yourself
+    pub recipient: UncheckedAccount<'info>,
     #[account(
-        init,
+        init_if_needed,
         payer = buyer,
         associated_token::mint = mint,
-        associated_token::authority = buyer
+        associated_token::authority = recipient
     )]
-    pub buyer_ata: Account<'info, TokenAccount>,
-    /// CHECK:
-    pub platform_fee_recipient: UncheckedAccount<'info>,
-    /// CHECK:
-    pub creator: UncheckedAccount<'info>,
+    pub recipient_ata: Account<'info, TokenAccount>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct Sell<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
     pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
     #[account(
         mut,
         seeds = [STATE_SEED, mint.key().as_ref()],
         bump,
     )]
-    pub state_pda: Account<'info, LaunchState>,
+    pub state_pda: AccountLoader<'info, LaunchState>,
     #[account(
         seeds = [LAUNCH_SEED, mint.key().as_ref()],
         bump
@@ -445,86 +1182,624 @@ pub struct Sell<'info> {
         associated_token::authority = seller
     )]
     pub seller_ata: Account<'info, TokenAccount>,
-    /// CHECK:
-    pub platform_fee_recipient: UncheckedAccount<'info>,
-    /// CHECK:
-    pub creator: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
-pub struct WithdrawFees<'info> {
-    /// Anyone can trigger; not restricted
-    pub caller: Signer<'info>,
+pub struct ClaimFees<'info> {
+    /// Fee recipient or its delegate, checked in the handler
+    pub claimer: Signer<'info>,
     pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
     #[account(
         mut,
         seeds = [STATE_SEED, mint.key().as_ref()],
         bump,
     )]
-    pub state_pda: Account<'info, LaunchState>,
+    pub state_pda: AccountLoader<'info, LaunchState>,
     #[account(
         mut,
         seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
         bump
     )]
-    pub vault_sol_pda: UncheckedAccount<'info>, // reverted
-    /// CHECK: validated against state
+    pub vault_sol_pda: UncheckedAccount<'info>,
+    /// CHECK: any account the claimer chooses
     #[account(mut)]
-    pub platform_fee_recipient: UncheckedAccount<'info>,
-    /// CHECK: validated against state
-    #[account(mut)]
-    pub creator: UncheckedAccount<'info>,
+    pub destination: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProposeCreator<'info> {
+    pub creator: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AcceptCreator<'info> {
+    pub new_creator: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetFeeDelegate<'info> {
+    pub recipient: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct VerifyLaunch<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [STATE_SEED, mint.key().as_ref()], bump)]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+    #[account(
+        seeds = [LAUNCH_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub authority_pda: UncheckedAccount<'info>,
+    #[account(
+        seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_sol_pda: UncheckedAccount<'info>,
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = authority_pda
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+    /// Required once the launch has graduated into the in-program pool.
+    #[account(seeds = [POOL_SEED, mint.key().as_ref()], bump = pool_pda.bump)]
+    pub pool_pda: Option<Account<'info, Pool>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct Graduate<'info> {
+    #[account(mut)]
     pub caller: Signer<'info>,
     pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
     #[account(mut, seeds = [STATE_SEED, mint.key().as_ref()], bump)]
-    pub state_pda: Account<'info, LaunchState>,
+    pub state_pda: AccountLoader<'info, LaunchState>,
+    #[account(
+        init,
+        payer = caller,
+        space = Pool::LEN,
+        seeds = [POOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub pool_pda: Account<'info, Pool>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct MigrateLaunch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    /// CHECK: decoded by the handler, whose layout it may not match yet
+    #[account(mut, seeds = [STATE_SEED, mint.key().as_ref()], bump)]
+    pub state_pda: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseLaunch<'info> {
+    /// Anyone can close a settled launch; the rent goes to the creator
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds = [STATE_SEED, mint.key().as_ref()], bump, close = creator)]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+    #[account(
+        seeds = [LAUNCH_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub authority_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_sol_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority_pda
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+    #[account(mut, address = state_pda.load()?.creator @ GrokError::InvalidOwner)]
+    pub creator: SystemAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct GraduateToAmm<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds = [STATE_SEED, mint.key().as_ref()], bump)]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+    #[account(
+        seeds = [LAUNCH_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub authority_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_sol_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority_pda
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+    /// CHECK: pinned to the configured migration program
+    #[account(executable, address = global_config.migration_program @ GrokError::UnsupportedAmm)]
+    pub amm_program: UncheckedAccount<'info>,
+    /// CHECK: created and validated by the AMM
+    #[account(mut)]
+    pub amm_pool: UncheckedAccount<'info>,
+    /// CHECK: created and validated by the AMM
+    #[account(mut)]
+    pub amm_pool_token_vault: UncheckedAccount<'info>,
+    /// CHECK: created and validated by the AMM
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: created by the AMM; owner checked after the CPI
+    #[account(mut)]
+    pub lp_destination: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub state_pda: AccountLoader<'info, LaunchState>,
+    #[account(mut, seeds = [POOL_SEED, mint.key().as_ref()], bump = pool_pda.bump)]
+    pub pool_pda: Account<'info, Pool>,
+    #[account(
+        seeds = [LAUNCH_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub authority_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_sol_pda: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority_pda
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = trader,
+        associated_token::mint = mint,
+        associated_token::authority = trader
+    )]
+    pub trader_ata: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 // EVENTS
 #[event]
-pub struct InitializeEvent { pub mint: Pubkey, pub base_price: u64, pub slope: u64, pub graduation_target_lamports: u64 }
+pub struct InitializeEvent { pub mint: Pubkey, pub base_price: u64, pub slope: u64, pub graduation_criteria: Vec<GraduationCriterion>, pub total_supply: u64 }
+#[event]
+pub struct FundCurveEvent { pub mint: Pubkey, pub creator: Pubkey, pub amount: u64, pub supply_remaining: u64 }
+#[event]
+pub struct BuyEvent { pub mint: Pubkey, pub buyer: Pubkey, pub recipient: Pubkey, pub requested_amount: u64, pub amount: u64, pub cost_lamports: u64, pub platform_fee: u64, pub creator_fee: u64, pub reserves_after: u64, pub tokens_sold_after: u64, pub graduated: bool, pub decimals: u8, pub price_before: u64, pub price_after: u64, pub trade_seq: u64, pub slot: u64, pub unix_timestamp: i64 }
+#[event]
+pub struct SellEvent { pub mint: Pubkey, pub seller: Pubkey, pub amount: u64, pub refund_net: u64, pub platform_fee: u64, pub creator_fee: u64, pub reserves_after: u64, pub tokens_sold_after: u64, pub decimals: u8, pub price_before: u64, pub price_after: u64, pub trade_seq: u64, pub slot: u64, pub unix_timestamp: i64 }
 #[event]
-pub struct BuyEvent { pub mint: Pubkey, pub buyer: Pubkey, pub amount: u64, pub cost_lamports: u64, pub platform_fee: u64, pub creator_fee: u64, pub reserves_after: u64, pub tokens_sold_after: u64, pub graduated: bool }
+pub struct ClaimFeesEvent { pub mint: Pubkey, pub kind: FeeKind, pub claimer: Pubkey, pub destination: Pubkey, pub amount: u64, pub remaining: u64 }
 #[event]
-pub struct SellEvent { pub mint: Pubkey, pub seller: Pubkey, pub amount: u64, pub refund_net: u64, pub platform_fee: u64, pub creator_fee: u64, pub reserves_after: u64, pub tokens_sold_after: u64 }
+pub struct CreatorProposedEvent { pub mint: Pubkey, pub creator: Pubkey, pub pending_creator: Pubkey }
 #[event]
-pub struct WithdrawFeesEvent { pub mint: Pubkey, pub platform_withdrawn: u64, pub creator_withdrawn: u64 }
+pub struct CreatorTransferEvent { pub mint: Pubkey, pub previous: Pubkey, pub creator: Pubkey }
 #[event]
-pub struct GraduateEvent { pub mint: Pubkey, pub reserves_lamports: u64, pub tokens_sold: u64 }
+pub struct PlatformRecipientEvent { pub mint: Pubkey, pub previous: Pubkey, pub recipient: Pubkey }
+#[event]
+pub struct FeeDelegateEvent { pub mint: Pubkey, pub kind: FeeKind, pub delegate: Pubkey }
+#[event]
+pub struct GraduateEvent { pub mint: Pubkey, pub criterion: GraduationCriterion, pub reserves_lamports: u64, pub tokens_sold: u64 }
+#[event]
+pub struct PoolSeededEvent { pub mint: Pubkey, pub pool: Pubkey, pub sol_reserves: u64, pub token_reserves: u64, pub fee_bps: u16 }
+#[event]
+pub struct MigrateEvent { pub mint: Pubkey, pub amm_program: Pubkey, pub pool: Pubkey, pub sol_amount: u64, pub token_amount: u64, pub lp_amount: u64, pub lp_burned: bool }
+#[event]
+pub struct MigrateLaunchEvent { pub mint: Pubkey, pub from_version: u8, pub to_version: u8 }
+#[event]
+pub struct CloseLaunchEvent { pub mint: Pubkey, pub creator: Pubkey, pub pool: Pubkey, pub total_supply: u64, pub tokens_sold: u64, pub volume_lamports: u64, pub platform_fees_claimed: u64, pub creator_fees_claimed: u64, pub tokens_burned: u64, pub rent_reclaimed: u64 }
+#[event]
+pub struct SwapEvent { pub mint: Pubkey, pub trader: Pubkey, pub sol_in: bool, pub amount_in: u64, pub amount_out: u64, pub fee: u64, pub sol_reserves_after: u64, pub token_reserves_after: u64, pub decimals: u8, pub price_before: u64, pub price_after: u64, pub trade_seq: u64, pub slot: u64, pub unix_timestamp: i64 }
+#[event]
+pub struct SolvencyMismatchEvent { pub mint: Pubkey, pub expected_lamports: u64, pub actual_lamports: u64, pub expected_tokens: u64, pub actual_tokens: u64, pub solvent: bool }
+/// `mint` is `None` for the platform-wide switch.
+#[event]
+pub struct PauseEvent { pub mint: Option<Pubkey>, pub paused: bool, pub exit_when_paused: bool }
+
+/// Return data of `verify_launch`. Expected lamports are reserves, accrued
+/// fees, in-program pool reserves and the vault's rent; expected tokens are
+/// the unsold supply plus the pool's tokens.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SolvencyReport {
+    pub mint: Pubkey,
+    pub expected_lamports: u64,
+    pub actual_lamports: u64,
+    pub expected_tokens: u64,
+    pub actual_tokens: u64,
+}
+impl SolvencyReport {
+    /// Holds at least what it owes. Donations can leave it above, never below.
+    pub fn is_solvent(&self) -> bool {
+        self.actual_lamports >= self.expected_lamports && self.actual_tokens >= self.expected_tokens
+    }
+}
 
 /* ---------------- Helpers ---------------- */
 
-fn linear_buy_cost(
-    base_price: u128,
-    slope: u128,
-    sold_before_units: u128,
-    units: u128,
-) -> u128 {
-    // Sum_{i=0}^{units-1} (base_price + slope*(sold_before_units + i))
-    let units_minus1 = units.saturating_sub(1);
-    base_price
-        .saturating_mul(units)
-        .saturating_add(
-            slope
-                .saturating_mul(
-                    sold_before_units
-                        .saturating_mul(units)
-                        .saturating_add(units_minus1.saturating_mul(units) / 2),
-                ),
-        )
+fn claim_fees(ctx: Context<ClaimFees>, kind: FeeKind, amount: Option<u64>) -> Result<()> {
+    let mut state = ctx.accounts.state_pda.load_mut()?;
+    require_not_paused(&ctx.accounts.global_config, &state)?;
+    let claimer = ctx.accounts.claimer.key();
+    require!(state.can_claim(kind, &claimer), GrokError::InvalidOwner);
+    require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+    let amount = state.take_fee(kind, amount)?;
+    let mint_key = ctx.accounts.mint.key();
+    let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, mint_key.as_ref(), &[ctx.bumps.vault_sol_pda]];
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.vault_sol_pda.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+            },
+            &[vault_sol_seeds],
+        ),
+        amount,
+    )?;
+    let remaining = match kind {
+        FeeKind::Platform => state.platform_fee_accrued,
+        FeeKind::Creator => state.creator_fee_accrued,
+    };
+    emit_event!(ctx, ClaimFeesEvent { mint: state.mint, kind, claimer, destination: ctx.accounts.destination.key(), amount, remaining });
+    Ok(())
+}
+
+/// Fills in a new launch from its curve parameters and the platform config.
+/// The platform fee and recipient always come from the config; the creator
+/// fee falls back to its default and the graduation criteria to a reserves
+/// target of `graduation_target_lamports`.
+#[allow(clippy::too_many_arguments)]
+fn init_launch_state(
+    state: &mut LaunchState,
+    mint: &Account<Mint>,
+    creator: Pubkey,
+    config: &GlobalConfig,
+    base_price_lamports: u64,
+    slope_lamports: u64,
+    creator_fee_bps: Option<u16>,
+    graduation_criteria: Option<Vec<GraduationCriterion>>,
+    cap_graduating_buy: bool,
+) -> Result<()> {
+    let creator_fee_bps = creator_fee_bps.unwrap_or(config.creator_kickback_bps);
+    let graduation_criteria = graduation_criteria
+        .unwrap_or_else(|| vec![GraduationCriterion::Reserves { lamports: config.graduation_target_lamports }]);
+    require!(!config.paused, GrokError::Paused);
+    require!(base_price_lamports > 0 && base_price_lamports <= MAX_PRICE, GrokError::InvalidParam);
+    require!(slope_lamports <= MAX_PRICE, GrokError::InvalidParam);
+    require!(mint.decimals <= MAX_DECIMALS, GrokError::InvalidParam);
+    graduation::validate_criteria(&graduation_criteria, Clock::get()?.unix_timestamp)?;
+    require!(creator_fee_bps <= config.max_creator_fee_bps, GrokError::FeeTooHigh);
+    state.mint = mint.key();
+    state.decimals = mint.decimals;
+    state.base_price_lamports = base_price_lamports;
+    state.slope_lamports = slope_lamports;
+    state.fee_bps = config.curve_fee_bps;
+    state.creator_fee_bps = creator_fee_bps;
+    state.platform_fee_recipient = config.treasury;
+    state.creator = creator;
+    state.set_criteria(&graduation_criteria);
+    state.graduated = 0;
+    state.total_supply = 0;
+    state.supply_remaining = 0;
+    state.tokens_sold = 0;
+    state.reserves_lamports = 0;
+    state.platform_fee_accrued = 0;
+    state.creator_fee_accrued = 0;
+    state.paused = 0;
+    state.exit_when_paused = 1;
+    state.pool = Pubkey::default();
+    state.cap_graduating_buy = cap_graduating_buy as u8;
+    state.platform_fee_delegate = Pubkey::default();
+    state.creator_fee_delegate = Pubkey::default();
+    state.pending_creator = Pubkey::default();
+    state.volume_lamports = 0;
+    state.platform_fees_claimed = 0;
+    state.creator_fees_claimed = 0;
+    state.version = LAUNCH_STATE_VERSION;
+    state._padding = [0; 5];
+    state.trade_seq = 0;
+    state.pool_allocation = 0;
+    state.reserved = [0; LAUNCH_STATE_RESERVED];
+    Ok(())
+}
+
+/// With `cap_graduating_buy`, shrinks a buy that would carry reserves past
+/// the reserves target to the smallest one that still reaches it. The rest
+/// of the request is left unfilled and never charged.
+fn cap_graduating_buy(state: &LaunchState, quote: BuyQuote) -> Result<BuyQuote> {
+    if state.cap_graduating_buy == 0 || state.is_graduated() {
+        return Ok(quote);
+    }
+    let Some(target) = state.reserves_target() else { return Ok(quote) };
+    if quote.after.reserves_lamports <= target || state.reserves_lamports >= target {
+        return Ok(quote);
+    }
+    let capped = grokpad_math::quote_buy_to_reserves(&state.curve(), target).map_err(GrokError::from)?;
+    Ok(if capped.amount < quote.amount { capped } else { quote })
+}
+
+/// Opens the curve on a launch's whole supply, less the pool allocation.
+/// Both must be non-empty, and selling out the curve has to be able to meet
+/// one of the launch's criteria, unless that costs more than fits in a u64,
+/// which overshoots any target.
+fn open_curve(state: &mut LaunchState, total_supply: u64) -> Result<()> {
+    state.total_supply = total_supply;
+    state.pool_allocation = (total_supply as u128 * POOL_ALLOCATION_BPS as u128 / BPS_DENOMINATOR as u128) as u64;
+    state.supply_remaining = total_supply - state.pool_allocation;
+    require!(state.supply_remaining > 0 && state.pool_allocation > 0, GrokError::InvalidParam);
+    if let Ok(sold_out) = grokpad_math::quote_buy(&state.curve(), state.supply_remaining) {
+        require!(
+            state.criteria().any(|c| c.is_met(&sold_out.after, state.total_supply, i64::MAX)),
+            GrokError::InvalidParam
+        );
+    }
+    Ok(())
+}
+
+/// Flags the launch graduated if a buy hasn't already, once it meets one of
+/// its criteria.
+fn require_graduation(state: &mut LaunchState) -> Result<Option<GraduateEvent>> {
+    if state.is_graduated() {
+        return Ok(None);
+    }
+    let criterion = state.met_criterion(Clock::get()?.unix_timestamp).ok_or(GrokError::NotYetGraduate)?;
+    Ok(Some(mark_graduated(state, criterion)))
+}
+
+/// Flags the launch graduated and returns the event for the caller to
+/// emit, since only the caller holds the `ctx` a self-CPI needs.
+fn mark_graduated(state: &mut LaunchState, criterion: GraduationCriterion) -> GraduateEvent {
+    state.graduated = 1;
+    msg!("Graduated: {:?}", criterion);
+    GraduateEvent {
+        mint: state.mint,
+        criterion,
+        reserves_lamports: state.reserves_lamports,
+        tokens_sold: state.tokens_sold,
+    }
+}
+
+/// Trading, funding, fee claims and graduation need both the platform
+/// and the launch to be unpaused.
+fn require_not_paused(config: &GlobalConfig, state: &LaunchState) -> Result<()> {
+    require!(!config.paused && !state.is_paused(), GrokError::Paused);
+    Ok(())
+}
+
+/// Sells are the emergency exit: they stay open through a pause as long as
+/// every pause in force allows it.
+fn require_exit_open(config: &GlobalConfig, state: &LaunchState) -> Result<()> {
+    require!(!config.paused || config.exit_when_paused, GrokError::Paused);
+    require!(!state.is_paused() || state.exit_when_paused != 0, GrokError::Paused);
+    Ok(())
+}
+
+/// Shared tail of `buy` and `buy_exact_in`: takes the quoted cost from the
+/// buyer, sends the tokens to the recipient and stores the post-trade curve.
+fn settle_buy(ctx: Context<Buy>, requested_amount: u64, quote: BuyQuote) -> Result<()> {
+    let mut state = ctx.accounts.state_pda.load_mut()?;
+    require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.vault_sol_pda.to_account_info(),
+            },
+        ),
+        quote.cost,
+    )?;
+    let mint_key = ctx.accounts.mint.key();
+    let authority_bump = ctx.bumps.authority_pda;
+    let authority_seeds: &[&[u8]] = &[LAUNCH_SEED, mint_key.as_ref(), &[authority_bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_ata.to_account_info(),
+                to: ctx.accounts.recipient_ata.to_account_info(),
+                authority: ctx.accounts.authority_pda.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+        quote.amount,
+    )?;
+    let price_before = state.spot_price();
+    state.set_curve(&quote.after);
+    state.volume_lamports = state.volume_lamports.saturating_add(quote.cost);
+    let trade_seq = state.next_trade_seq()?;
+    let clock = Clock::get()?;
+    if !state.is_graduated() {
+        if let Some(criterion) = state.met_criterion(clock.unix_timestamp) {
+            let event = mark_graduated(&mut state, criterion);
+            emit_event!(ctx, event);
+        }
+    }
+    emit_event!(ctx, BuyEvent {
+        mint: state.mint,
+        buyer: ctx.accounts.buyer.key(),
+        recipient: ctx.accounts.recipient.key(),
+        requested_amount,
+        amount: quote.amount,
+        cost_lamports: quote.cost,
+        platform_fee: quote.platform_fee,
+        creator_fee: quote.creator_fee,
+        reserves_after: state.reserves_lamports,
+        tokens_sold_after: state.tokens_sold,
+        graduated: state.is_graduated(),
+        decimals: state.decimals,
+        price_before,
+        price_after: state.spot_price(),
+        trade_seq,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Shared tail of `sell` and `sell_exact_out`: pulls the tokens back into
+/// the vault, pays the seller the quoted net refund and stores the
+/// post-trade curve.
+fn settle_sell(ctx: Context<Sell>, quote: SellQuote) -> Result<()> {
+    let mut state = ctx.accounts.state_pda.load_mut()?;
+    require!(ctx.accounts.vault_sol_pda.owner == &system_program::ID, GrokError::InvalidOwner);
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_ata.to_account_info(),
+                to: ctx.accounts.vault_ata.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        quote.amount,
+    )?;
+    let mint_key = ctx.accounts.mint.key();
+    let vault_sol_bump = ctx.bumps.vault_sol_pda;
+    let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, mint_key.as_ref(), &[vault_sol_bump]];
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.vault_sol_pda.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            },
+            &[vault_sol_seeds],
+        ),
+        quote.refund_net,
+    )?;
+    let price_before = state.spot_price();
+    state.set_curve(&quote.after);
+    state.volume_lamports = state.volume_lamports.saturating_add(quote.refund_net);
+    let trade_seq = state.next_trade_seq()?;
+    let clock = Clock::get()?;
+    emit_event!(ctx, SellEvent {
+        mint: state.mint,
+        seller: ctx.accounts.seller.key(),
+        amount: quote.amount,
+        refund_net: quote.refund_net,
+        platform_fee: quote.platform_fee,
+        creator_fee: quote.creator_fee,
+        reserves_after: state.reserves_lamports,
+        tokens_sold_after: state.tokens_sold,
+        decimals: state.decimals,
+        price_before,
+        price_after: state.spot_price(),
+        trade_seq,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+    Ok(())
 }
 
 /* ---------------- Errors ---------------- */
 
+impl From<QuoteError> for GrokError {
+    fn from(e: QuoteError) -> Self {
+        match e {
+            QuoteError::ZeroAmount => GrokError::ZeroAmount,
+            QuoteError::NotEnoughSupply => GrokError::NotEnoughSupply,
+            QuoteError::NotEnoughSold => GrokError::Underflow,
+            QuoteError::ZeroOutput => GrokError::ZeroUnits,
+            QuoteError::InsufficientReserves => GrokError::InsufficientReserves,
+            QuoteError::FeeTooHigh => GrokError::FeeTooHigh,
+            QuoteError::EmptyPool => GrokError::PoolEmpty,
+            QuoteError::Math(overflow) => {
+                msg!("Math overflow in {}", overflow.op);
+                GrokError::MathOverflow
+            }
+        }
+    }
+}
+
 #[error_code]
 pub enum GrokError {
     #[msg("Fee too high")]
@@ -549,4 +1824,16 @@ pub enum GrokError {
     InvalidOwner,
     #[msg("Parameter out of allowed range")] InvalidParam,
     #[msg("Not yet eligible to graduate")] NotYetGraduate,
+    #[msg("Launch already funded or trading has started")] AlreadyFunded,
+    #[msg("Curve cannot pay out the requested amount")] InsufficientReserves,
+    #[msg("Curve math out of range")] MathOverflow,
+    #[msg("Trading is paused")] Paused,
+    #[msg("Pool has no liquidity on one side")] PoolEmpty,
+    #[msg("No migration adapter for this AMM")] UnsupportedAmm,
+    #[msg("Launch trades in its pool; pass pool_pda")] MissingPool,
+    #[msg("Amount exceeds accrued fees")] InsufficientFees,
+    #[msg("Launch still holds reserves, supply or unclaimed fees")] NotSettled,
+    #[msg("Launch state already has the current layout")] AlreadyMigrated,
+    #[msg("AMM did not take exactly the migrated liquidity")] MigrationMismatch,
+    #[msg("Launch state has an older layout; run migrate_launch first")] NotMigrated,
 }
@@ -0,0 +1,97 @@
+//! Graduation into an external AMM.
+//!
+//! `graduate_to_amm` hands the curve reserves and the unsold supply to the
+//! program named by `GlobalConfig::migration_program`. Every supported AMM
+//! gets a `MigrationAdapter` that turns the fixed `MigrationKeys` layout into
+//! that AMM's create-pool instruction; the program only signs for the two
+//! vaults and never needs to know the AMM's own account rules.
+//!
+//! No real AMM has an adapter yet. The workspace's mock AMM gets one in
+//! `mock-amm` builds, for tests only; without it `adapter_for` finds
+//! nothing, the config accepts no `migration_program`, and
+//! `graduate_to_amm` can't succeed.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+#[cfg(feature = "mock-amm")]
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+/// What happens to the LP tokens the AMM mints for the migrated liquidity.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LpHandling {
+    /// Keep them in an account owned by `authority_pda`, which never signs
+    /// for them again.
+    Lock,
+    /// Burn them right after the pool is created.
+    Burn,
+}
+
+/// Accounts every adapter receives. `sol_source` is `vault_sol_pda` and
+/// `token_authority` is `authority_pda`, which also owns `lp_destination`;
+/// both sign through the program. `pool`, `pool_token_vault` and `lp_mint`
+/// are whatever the AMM derives for `mint`.
+#[derive(Clone, Copy, Debug)]
+pub struct MigrationKeys {
+    pub payer: Pubkey,
+    pub sol_source: Pubkey,
+    pub token_authority: Pubkey,
+    pub mint: Pubkey,
+    pub token_source: Pubkey,
+    pub pool: Pubkey,
+    pub pool_token_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_destination: Pubkey,
+}
+
+pub trait MigrationAdapter {
+    fn program_id(&self) -> Pubkey;
+
+    /// Builds the instruction that creates the pool and deposits `lamports`
+    /// and `tokens` into it.
+    fn create_pool_ix(&self, keys: &MigrationKeys, lamports: u64, tokens: u64) -> Instruction;
+}
+
+/// Adapter for the workspace's `mock-amm` program, built only with the
+/// `mock-amm` feature.
+#[cfg(feature = "mock-amm")]
+pub struct MockAmm;
+
+#[cfg(feature = "mock-amm")]
+impl MigrationAdapter for MockAmm {
+    fn program_id(&self) -> Pubkey {
+        mock_amm::ID
+    }
+
+    fn create_pool_ix(&self, keys: &MigrationKeys, lamports: u64, tokens: u64) -> Instruction {
+        let accounts = mock_amm::accounts::CreatePool {
+            payer: keys.payer,
+            sol_source: keys.sol_source,
+            token_authority: keys.token_authority,
+            mint: keys.mint,
+            token_source: keys.token_source,
+            pool: keys.pool,
+            pool_token_vault: keys.pool_token_vault,
+            lp_mint: keys.lp_mint,
+            lp_owner: keys.token_authority,
+            lp_destination: keys.lp_destination,
+            system_program: anchor_lang::system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        };
+        Instruction {
+            program_id: mock_amm::ID,
+            accounts: accounts.to_account_metas(None),
+            data: mock_amm::instruction::CreatePool { sol_amount: lamports, token_amount: tokens }.data(),
+        }
+    }
+}
+
+/// The adapter for `program_id`, if the program knows how to migrate there.
+pub fn adapter_for(program_id: &Pubkey) -> Option<&'static dyn MigrationAdapter> {
+    let adapters: &[&'static dyn MigrationAdapter] = &[
+        #[cfg(feature = "mock-amm")]
+        &MockAmm,
+    ];
+    adapters.iter().copied().find(|adapter| adapter.program_id() == *program_id)
+}
@@ -1,38 +1,32 @@
 use anchor_lang::prelude::*;
 
+/// Platform-wide settings, one per program at `[CONFIG_SEED]`. New launches
+/// copy their fee recipient and default fees from here.
 #[account]
 pub struct GlobalConfig {
     pub admin: Pubkey,
+    /// Platform fee recipient recorded on every new launch.
     pub treasury: Pubkey,
+    /// Platform fee on curve trades.
     pub curve_fee_bps: u16,
     pub amm_fee_bps: u16,
-    pub graduation_mc_lamports: u64,
+    /// Creator fee a launch gets when it doesn't ask for one.
+    pub creator_kickback_bps: u16,
+    /// Highest creator fee a launch may ask for.
+    pub max_creator_fee_bps: u16,
+    /// Reserves target a launch graduates at when it doesn't choose its own
+    /// criteria.
+    pub graduation_target_lamports: u64,
+    /// Halts every launch on the platform.
+    pub paused: bool,
+    /// Whether sells stay open while `paused` is set.
+    pub exit_when_paused: bool,
+    /// AMM `graduate_to_amm` migrates into; the default key disables it.
+    /// Only programs with a `MigrationAdapter` are accepted, and production
+    /// builds have none.
+    pub migration_program: Pubkey,
+    pub bump: u8,
 }
 impl Space for GlobalConfig {
-    const INIT_SPACE: usize = 32 + 32 + 2 + 2 + 8;
-}
-
-#[account]
-pub struct Launch {
-    pub creator: Pubkey,
-    pub mint: Pubkey,
-    pub curve_vault: Pubkey,
-    pub sol_vault: Pubkey,
-    pub sold: u64,
-    pub price_state: PriceState,
-    pub graduated: bool,
-    pub metadata_uri: String,
-}
-impl Space for Launch {
-    const INIT_SPACE: usize = 32*4 + 8 + PriceState::INIT_SPACE + 1 + 4 + 256;
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct PriceState {
-    pub tranche_size: u64,
-    pub base_price_lamports: u64,
-    pub step_bps: u16,
-}
-impl PriceState {
-    pub const INIT_SPACE: usize = 8 + 8 + 2;
+    const INIT_SPACE: usize = 32 + 32 + 2 + 2 + 2 + 2 + 8 + 1 + 1 + 32 + 1;
 }
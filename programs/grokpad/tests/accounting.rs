@@ -0,0 +1,173 @@
+//! Vault bookkeeping: whatever the order of trades and fee claims the
+//! handlers run, the lamports in `vault_sol_pda` are exactly reserves plus
+//! accrued fees plus the rent the vault was created with.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use bytemuck::Zeroable;
+use common::*;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::layout::LAUNCH_STATE_VERSION;
+use grokpad::{FeeKind, LaunchState};
+use grokpad_math::PoolState;
+use proptest::prelude::*;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const TRADERS: usize = 3;
+
+#[derive(Debug, Clone)]
+enum Op {
+    /// A trader buys this many base units.
+    Buy(usize, u64),
+    /// A trader sells this share, in bps, of what it holds.
+    Sell(usize, u16),
+    /// Claim this share, in bps, of one accrual; 10_000 claims it all.
+    Claim(FeeKind, u16),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (0..TRADERS, 1u64..50_000_000_000).prop_map(|(trader, amount)| Op::Buy(trader, amount)),
+        (0..TRADERS, 1u16..=10_000).prop_map(|(trader, share_bps)| Op::Sell(trader, share_bps)),
+        (prop_oneof![Just(FeeKind::Platform), Just(FeeKind::Creator)], 1u16..=10_000)
+            .prop_map(|(kind, share_bps)| Op::Claim(kind, share_bps)),
+    ]
+}
+
+/// Runs `ops` on a fresh launch, checking the vault after every one. Ops
+/// the program rejects, like selling more than is held, must leave it
+/// balanced too.
+async fn run(ops: Vec<Op>) -> Result<(), TestCaseError> {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let mut traders: Vec<Keypair> = Vec::new();
+    for _ in 0..TRADERS {
+        traders.push(harness.trader().await);
+    }
+    let rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    prop_assert_eq!(harness.lamports(&launch.vault_sol_pda).await, rent);
+
+    for op in ops {
+        match op {
+            Op::Buy(trader, amount) => {
+                let _ = harness.buy(&launch, &traders[trader], amount).await;
+            }
+            Op::Sell(trader, share_bps) => {
+                let held = harness.token_balance(&get_associated_token_address(&traders[trader].pubkey(), &launch.mint)).await;
+                let amount = (held as u128 * share_bps as u128 / 10_000) as u64;
+                let _ = harness.sell(&launch, &traders[trader], amount).await;
+            }
+            Op::Claim(kind, share_bps) => {
+                let state = harness.state(&launch).await;
+                let accrued = match kind {
+                    FeeKind::Platform => state.platform_fee_accrued,
+                    FeeKind::Creator => state.creator_fee_accrued,
+                };
+                let amount = (accrued as u128 * share_bps as u128 / 10_000) as u64;
+                let ix = harness.claim_fees_ix(&launch, kind, Some(amount));
+                let admin = harness.admin.insecure_clone();
+                let _ = harness.send(&[ix], &[&admin]).await;
+            }
+        }
+        let state = harness.state(&launch).await;
+        prop_assert_eq!(
+            harness.lamports(&launch.vault_sol_pda).await,
+            state.reserves_lamports + state.platform_fee_accrued + state.creator_fee_accrued + rent
+        );
+    }
+
+    // Everything sold can always be sold back while the curve trades
+    if !harness.state(&launch).await.is_graduated() {
+        for trader in &traders {
+            let held = harness.token_balance(&get_associated_token_address(&trader.pubkey(), &launch.mint)).await;
+            if held > 0 {
+                harness.sell(&launch, trader, held).await.unwrap();
+            }
+        }
+        let state = harness.state(&launch).await;
+        prop_assert_eq!(state.tokens_sold, 0);
+        prop_assert_eq!(
+            harness.lamports(&launch.vault_sol_pda).await,
+            state.reserves_lamports + state.platform_fee_accrued + state.creator_fee_accrued + rent
+        );
+    }
+    Ok(())
+}
+
+proptest! {
+    // Every case starts a cluster, so there are few of them
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn vault_always_holds_reserves_fees_and_rent(ops in prop::collection::vec(op(), 1..40)) {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(run(ops))?;
+    }
+}
+
+/// A launch state for checks that need no cluster.
+fn launch(base_price_lamports: u64, slope_lamports: u64, fee_bps: u16, creator_fee_bps: u16) -> LaunchState {
+    let mut state: LaunchState = Zeroable::zeroed();
+    state.mint = Pubkey::new_unique();
+    state.decimals = 6;
+    state.base_price_lamports = base_price_lamports;
+    state.slope_lamports = slope_lamports;
+    state.fee_bps = fee_bps;
+    state.creator_fee_bps = creator_fee_bps;
+    state.platform_fee_recipient = Pubkey::new_unique();
+    state.creator = Pubkey::new_unique();
+    state.set_criteria(&[GraduationCriterion::Reserves { lamports: u64::MAX }]);
+    state.total_supply = 1_000_000_000_000_000;
+    state.supply_remaining = 1_000_000_000_000_000;
+    state.exit_when_paused = 1;
+    state.version = LAUNCH_STATE_VERSION;
+    state
+}
+
+#[test]
+fn claims_are_bounded_and_limited_to_recipient_or_delegate() {
+    let mut state = launch(1_000, 0, 100, 100);
+    state.platform_fee_accrued = 500;
+    state.creator_fee_accrued = 300;
+    let delegate = Pubkey::new_unique();
+    state.creator_fee_delegate = delegate;
+
+    assert!(state.can_claim(FeeKind::Creator, &state.creator));
+    assert!(state.can_claim(FeeKind::Creator, &delegate));
+    assert!(!state.can_claim(FeeKind::Platform, &delegate));
+    assert!(!state.can_claim(FeeKind::Platform, &Pubkey::default()));
+
+    assert!(state.take_fee(FeeKind::Creator, Some(301)).is_err());
+    assert!(state.take_fee(FeeKind::Creator, Some(0)).is_err());
+    assert_eq!(state.take_fee(FeeKind::Creator, Some(100)).unwrap(), 100);
+    assert_eq!(state.take_fee(FeeKind::Creator, None).unwrap(), 200);
+    assert!(state.take_fee(FeeKind::Creator, None).is_err());
+    assert_eq!(state.creator_fees_claimed, 300);
+    assert_eq!((state.platform_fee_accrued, state.platform_fees_claimed), (500, 0));
+}
+
+#[test]
+fn trades_are_numbered_from_one() {
+    let mut state = launch(1_000, 10, 100, 100);
+    assert_eq!(state.trade_seq, 0);
+    assert_eq!(state.next_trade_seq().unwrap(), 1);
+    assert_eq!(state.next_trade_seq().unwrap(), 2);
+    state.trade_seq = u64::MAX;
+    assert!(state.next_trade_seq().is_err());
+}
+
+#[test]
+fn trade_prices_are_per_whole_token() {
+    let mut state = launch(1_000, 10, 100, 100);
+    assert_eq!(state.spot_price(), 1_000);
+    state.tokens_sold = 5_000_000;
+    assert_eq!(state.spot_price(), 1_050);
+    let pool = PoolState { sol_reserves: 30_000_000_000, token_reserves: 600_000_000_000, fee_bps: 30 };
+    assert_eq!(state.pool_price(&pool), 50_000);
+    state.slope_lamports = u64::MAX;
+    state.tokens_sold = u64::MAX;
+    assert_eq!(state.spot_price(), u64::MAX);
+}
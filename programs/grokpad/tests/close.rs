@@ -0,0 +1,109 @@
+//! `close_launch`: only a graduated, fully settled launch that left the
+//! in-program pool closes, burning stray tokens and returning its rent.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use common::*;
+#[cfg(feature = "event-cpi")]
+use grokpad::events::GrokEvent;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::GrokError;
+use solana_signer::Signer;
+
+/// Tokens an AMM migration left behind in `vault_ata`.
+const STRAY: u64 = 777;
+
+/// A launch with some trading done, rewritten to look the way
+/// `graduate_to_amm` leaves it: liquidity gone to an external pool, a few
+/// stray tokens and the unclaimed fees still in the vaults.
+async fn graduated_to_amm(harness: &mut Harness) -> Launch {
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    harness.buy(&launch, &trader, 1_000_000_000).await.unwrap();
+    let result = harness.send(&[harness.close_launch_ix(&launch)], &[]).await;
+    assert_eq!(error_code(result), Some(GrokError::NotYetGraduate.into()));
+
+    let state = harness.state(&launch).await;
+    let rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    let vault_lamports = rent + state.platform_fee_accrued + state.creator_fee_accrued;
+    harness.set_lamports(&launch.vault_sol_pda, vault_lamports).await;
+    harness.set_token_balance(&launch.vault_ata, STRAY).await;
+    harness
+        .set_state(&launch, |state| {
+            state.graduated = 1;
+            state.pool = Pubkey::new_unique();
+            state.reserves_lamports = 0;
+            state.supply_remaining = 0;
+            state.pool_allocation = 0;
+        })
+        .await;
+    launch
+}
+
+#[tokio::test]
+async fn unsettled_launches_stay_open() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = graduated_to_amm(&mut harness).await;
+    let close = harness.close_launch_ix(&launch);
+
+    let result = harness.send(std::slice::from_ref(&close), &[]).await;
+    assert_eq!(error_code(result), Some(GrokError::NotSettled.into()));
+    harness.claim_fees(&launch).await.unwrap();
+
+    let unsettled: [fn(&mut grokpad::LaunchState); 3] = [
+        |state| state.reserves_lamports = 1,
+        |state| state.supply_remaining = 1,
+        |state| state.pool_allocation = 1,
+    ];
+    for unsettle in unsettled {
+        let settled = harness.state(&launch).await;
+        harness.set_state(&launch, unsettle).await;
+        let result = harness.send(std::slice::from_ref(&close), &[]).await;
+        assert_eq!(error_code(result), Some(GrokError::NotSettled.into()));
+        harness.set_state(&launch, |state| *state = settled).await;
+    }
+
+    // A launch trading in the in-program pool keeps its vaults
+    harness.set_state(&launch, |state| state.pool = launch.pool_pda).await;
+    let result = harness.send(&[close], &[]).await;
+    assert_eq!(error_code(result), Some(GrokError::NotSettled.into()));
+    assert!(harness.account(&launch.state_pda).await.is_some());
+}
+
+#[tokio::test]
+async fn settled_launch_burns_strays_and_returns_rent() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = graduated_to_amm(&mut harness).await;
+    harness.claim_fees(&launch).await.unwrap();
+
+    let creator = harness.admin.pubkey();
+    let creator_before = harness.lamports(&creator).await;
+    let rent = harness.lamports(&launch.state_pda).await
+        + harness.lamports(&launch.vault_sol_pda).await
+        + harness.lamports(&launch.vault_ata).await;
+    let mint = harness.account(&launch.mint).await.unwrap();
+    let supply_before = spl_token::state::Mint::unpack(&mint.data).unwrap().supply;
+
+    let close = harness.close_launch_ix(&launch);
+    #[cfg(feature = "event-cpi")]
+    {
+        let events = harness.send_for_events(&[close], &[]).await.unwrap();
+        let Some(GrokEvent::CloseLaunchEvent(event)) = events.into_iter().next() else { panic!("no close event") };
+        assert_eq!(event.tokens_burned, STRAY);
+        assert_eq!(event.rent_reclaimed, rent);
+        assert_eq!(event.total_supply, TOTAL_SUPPLY);
+        assert_eq!(event.tokens_sold, 1_000_000_000);
+    }
+    #[cfg(not(feature = "event-cpi"))]
+    harness.send(&[close], &[]).await.unwrap();
+
+    assert_eq!(harness.lamports(&creator).await - creator_before, rent);
+    let mint = harness.account(&launch.mint).await.unwrap();
+    assert_eq!(spl_token::state::Mint::unpack(&mint.data).unwrap().supply, supply_before - STRAY);
+    for closed in [launch.state_pda, launch.vault_sol_pda, launch.vault_ata] {
+        assert!(harness.account(&closed).await.is_none());
+    }
+}
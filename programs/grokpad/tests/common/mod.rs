@@ -0,0 +1,672 @@
+//! Runs the program natively under `solana-program-test`, with the real SPL
+//! Token and Associated Token programs, so handler tests need no SBF build.
+#![allow(dead_code)]
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorDeserialize, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::instructions::AdminArgs;
+#[cfg(feature = "event-cpi")]
+use grokpad::events::{events_from_inner_instructions, GrokEvent};
+use grokpad::layout::decode_launch_state;
+use grokpad::state::GlobalConfig;
+use grokpad::{FeeKind, LaunchState, Pool, SolvencyReport};
+use solana_account::{Account, AccountSharedData};
+use solana_keypair::Keypair;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+pub const DECIMALS: u8 = 6;
+pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000;
+pub const BASE_PRICE: u64 = 1_000;
+pub const SLOPE: u64 = 10;
+pub const CURVE_FEE_BPS: u16 = 100;
+pub const CREATOR_FEE_BPS: u16 = 50;
+
+/// Accounts of a `grokpad::accounts` struct as metas, with the event CPI
+/// accounts filled in when the tests run with `event-cpi`.
+#[macro_export]
+macro_rules! accounts {
+    ($name:ident { $($field:ident: $value:expr),* $(,)? }) => {
+        anchor_lang::ToAccountMetas::to_account_metas(
+            &grokpad::accounts::$name {
+                $($field: $value,)*
+                #[cfg(feature = "event-cpi")]
+                event_authority: grokpad::events::event_authority(),
+                #[cfg(feature = "event-cpi")]
+                program: grokpad::ID,
+            },
+            None,
+        )
+    };
+}
+
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the account infos to outlive their data
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    grokpad::entry(program_id, accounts, data)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &grokpad::ID).0
+}
+
+/// The PDAs and vaults of one launch.
+#[derive(Clone, Copy, Debug)]
+pub struct Launch {
+    pub mint: Pubkey,
+    pub authority_pda: Pubkey,
+    pub state_pda: Pubkey,
+    pub vault_sol_pda: Pubkey,
+    pub vault_ata: Pubkey,
+    pub pool_pda: Pubkey,
+}
+
+impl Launch {
+    pub fn new(mint: Pubkey) -> Self {
+        let authority_pda = pda(&[b"launch", mint.as_ref()]);
+        Launch {
+            mint,
+            authority_pda,
+            state_pda: pda(&[b"launch_state", mint.as_ref()]),
+            vault_sol_pda: pda(&[b"vault_sol", mint.as_ref()]),
+            vault_ata: get_associated_token_address(&authority_pda, &mint),
+            pool_pda: pda(&[b"pool", mint.as_ref()]),
+        }
+    }
+}
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    /// Config admin, treasury and creator of every launch.
+    pub admin: Keypair,
+    pub global_config: Pubkey,
+}
+
+impl Harness {
+    /// Starts a cluster whose config graduates default launches at
+    /// `graduation_target_lamports`.
+    pub async fn start(graduation_target_lamports: u64) -> Self {
+        Self::start_with(graduation_target_lamports, |_| {}).await
+    }
+
+    /// Like `start`, with `setup` adding programs or accounts to the
+    /// cluster first.
+    pub async fn start_with(graduation_target_lamports: u64, setup: impl FnOnce(&mut ProgramTest)) -> Self {
+        let mut program = ProgramTest::new("grokpad", grokpad::ID, processor!(process_instruction));
+        program.prefer_bpf(false);
+        setup(&mut program);
+        let mut ctx = program.start_with_context().await;
+        let admin = Keypair::new();
+        // The program isn't upgradeable here, so the config is written directly
+        let (global_config, bump) = Pubkey::find_program_address(&[b"config"], &grokpad::ID);
+        let config = GlobalConfig {
+            admin: admin.pubkey(),
+            treasury: admin.pubkey(),
+            curve_fee_bps: CURVE_FEE_BPS,
+            amm_fee_bps: 30,
+            creator_kickback_bps: CREATOR_FEE_BPS,
+            max_creator_fee_bps: 100,
+            graduation_target_lamports,
+            paused: false,
+            exit_when_paused: true,
+            migration_program: Pubkey::default(),
+            bump,
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        ctx.set_account(&global_config, &program_account(data));
+        let mut harness = Harness { ctx, admin, global_config };
+        let admin = harness.admin.pubkey();
+        harness.airdrop(&admin, 1_000_000_000_000).await;
+        harness
+    }
+
+    /// A transaction paid for by the context payer, on a fresh blockhash.
+    async fn transaction(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Transaction {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let payer = &self.ctx.payer;
+        let mut all: Vec<&Keypair> = vec![payer];
+        all.extend(signers.iter().copied().filter(|s| s.pubkey() != payer.pubkey()));
+        Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all, blockhash)
+    }
+
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let tx = self.transaction(ixs, signers).await;
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    /// Sends `ixs` and returns the program events they emitted, read from
+    /// the inner instructions of a simulation of the same transaction.
+    /// Natively run programs don't log `Program data:` lines, so only the
+    /// self-CPIs of `event-cpi` builds are visible.
+    #[cfg(feature = "event-cpi")]
+    pub async fn send_for_events(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<GrokEvent>, BanksClientError> {
+        let tx = self.transaction(ixs, signers).await;
+        let simulation = self.ctx.banks_client.simulate_transaction(tx.clone()).await?;
+        if let Some(Err(err)) = simulation.result {
+            return Err(err.into());
+        }
+        let inner = simulation.simulation_details.unwrap().inner_instructions.unwrap_or_default();
+        let keys = &tx.message.account_keys;
+        let inner = inner
+            .iter()
+            .flatten()
+            .map(|ix| (&keys[ix.instruction.program_id_index as usize], &ix.instruction.data[..]));
+        let events = events_from_inner_instructions(inner).unwrap();
+        self.ctx.banks_client.process_transaction(tx).await?;
+        Ok(events)
+    }
+
+    /// Runs `verify_launch` in a simulation and decodes the report it
+    /// returns, passing the launch's pool when `with_pool` is set.
+    pub async fn verify_launch(&mut self, launch: &Launch, with_pool: bool) -> Result<SolvencyReport, BanksClientError> {
+        let ix = self.verify_launch_ix(launch, with_pool);
+        let tx = self.transaction(&[ix], &[]).await;
+        let simulation = self.ctx.banks_client.simulate_transaction(tx).await?;
+        if let Some(Err(err)) = simulation.result {
+            return Err(err.into());
+        }
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        assert_eq!(return_data.program_id, grokpad::ID);
+        // The runtime trims trailing zero bytes off return data
+        let mut data = return_data.data;
+        data.resize(32 + 4 * 8, 0);
+        Ok(SolvencyReport::try_from_slice(&data).unwrap())
+    }
+
+    pub fn verify_launch_ix(&self, launch: &Launch, with_pool: bool) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(VerifyLaunch {
+                mint: launch.mint,
+                state_pda: launch.state_pda,
+                authority_pda: launch.authority_pda,
+                vault_sol_pda: launch.vault_sol_pda,
+                vault_ata: launch.vault_ata,
+                pool_pda: with_pool.then_some(launch.pool_pda),
+            }),
+            data: grokpad::instruction::VerifyLaunch {}.data(),
+        }
+    }
+
+    pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let ix = anchor_lang::solana_program::system_instruction::transfer(&self.ctx.payer.pubkey(), to, lamports);
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    pub async fn trader(&mut self) -> Keypair {
+        let trader = Keypair::new();
+        self.airdrop(&trader.pubkey(), 100_000_000_000_000).await;
+        trader
+    }
+
+    pub async fn account(&mut self, key: &Pubkey) -> Option<Account> {
+        self.ctx.banks_client.get_account(*key).await.unwrap()
+    }
+
+    pub async fn lamports(&mut self, key: &Pubkey) -> u64 {
+        self.account(key).await.map_or(0, |a| a.lamports)
+    }
+
+    pub async fn token_balance(&mut self, key: &Pubkey) -> u64 {
+        match self.account(key).await {
+            Some(account) => spl_token::state::Account::unpack(&account.data).unwrap().amount,
+            None => 0,
+        }
+    }
+
+    pub async fn state(&mut self, launch: &Launch) -> LaunchState {
+        decode_launch_state(&self.account(&launch.state_pda).await.unwrap().data).unwrap()
+    }
+
+    /// Rewrites a launch's state in place, for states no instruction
+    /// sequence here can reach.
+    pub async fn set_state(&mut self, launch: &Launch, update: impl FnOnce(&mut LaunchState)) {
+        let mut account = self.account(&launch.state_pda).await.unwrap();
+        update(bytemuck::from_bytes_mut(&mut account.data[8..]));
+        self.ctx.set_account(&launch.state_pda, &AccountSharedData::from(account));
+    }
+
+    /// Sets the balance of a token account.
+    pub async fn set_token_balance(&mut self, key: &Pubkey, amount: u64) {
+        let mut account = self.account(key).await.unwrap();
+        let mut holding = spl_token::state::Account::unpack(&account.data).unwrap();
+        holding.amount = amount;
+        holding.pack_into_slice(&mut account.data);
+        self.ctx.set_account(key, &AccountSharedData::from(account));
+    }
+
+    /// Sets the lamports of an account, keeping its owner and data.
+    pub async fn set_lamports(&mut self, key: &Pubkey, lamports: u64) {
+        let mut account = self.account(key).await.unwrap();
+        account.lamports = lamports;
+        self.ctx.set_account(key, &AccountSharedData::from(account));
+    }
+
+    /// Creates a fixed-supply launch through `initialize_launch_with_mint`.
+    pub async fn launch_with_mint(
+        &mut self,
+        graduation_criteria: Option<Vec<GraduationCriterion>>,
+    ) -> Result<Launch, BanksClientError> {
+        let mint = Keypair::new();
+        let launch = Launch::new(mint.pubkey());
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(InitializeLaunchWithMint {
+                payer: self.admin.pubkey(),
+                creator: self.admin.pubkey(),
+                global_config: self.global_config,
+                mint: launch.mint,
+                authority_pda: launch.authority_pda,
+                state_pda: launch.state_pda,
+                vault_sol_pda: launch.vault_sol_pda,
+                vault_ata: launch.vault_ata,
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            }),
+            data: grokpad::instruction::InitializeLaunchWithMint {
+                decimals: DECIMALS,
+                total_supply: TOTAL_SUPPLY,
+                base_price_lamports: BASE_PRICE,
+                slope_lamports: SLOPE,
+                creator_fee_bps: None,
+                graduation_criteria,
+                cap_graduating_buy: false,
+            }
+            .data(),
+        };
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin, &mint]).await?;
+        Ok(launch)
+    }
+
+    /// Writes a mint with no authorities and `amount` tokens held by the
+    /// admin, for launches created through `initialize_launch`.
+    pub async fn external_mint(&mut self, amount: u64) -> (Pubkey, Pubkey) {
+        let mint = Pubkey::new_unique();
+        let state = spl_token::state::Mint { supply: amount, decimals: DECIMALS, is_initialized: true, ..Default::default() };
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        state.pack_into_slice(&mut data);
+        self.set_token_account(&mint, data).await;
+        let admin_ata = get_associated_token_address(&self.admin.pubkey(), &mint);
+        let holding = spl_token::state::Account {
+            mint,
+            owner: self.admin.pubkey(),
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        holding.pack_into_slice(&mut data);
+        self.set_token_account(&admin_ata, data).await;
+        (mint, admin_ata)
+    }
+
+    async fn set_token_account(&mut self, key: &Pubkey, data: Vec<u8>) {
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let account = Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.ctx.set_account(key, &AccountSharedData::from(account));
+    }
+
+    /// Buy accounts with `trader` buying for itself.
+    pub fn buy_accounts(&self, launch: &Launch, trader: &Pubkey) -> Vec<anchor_lang::prelude::AccountMeta> {
+        self.buy_accounts_for(launch, trader, trader)
+    }
+
+    /// Buy accounts with `buyer` paying for tokens sent to `recipient`.
+    pub fn buy_accounts_for(
+        &self,
+        launch: &Launch,
+        buyer: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Vec<anchor_lang::prelude::AccountMeta> {
+        accounts!(Buy {
+            buyer: *buyer,
+            mint: launch.mint,
+            global_config: self.global_config,
+            state_pda: launch.state_pda,
+            authority_pda: launch.authority_pda,
+            vault_sol_pda: launch.vault_sol_pda,
+            vault_ata: launch.vault_ata,
+            recipient: *recipient,
+            recipient_ata: get_associated_token_address(recipient, &launch.mint),
+            system_program: anchor_lang::system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        })
+    }
+
+    pub async fn buy(&mut self, launch: &Launch, trader: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.buy_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::Buy { amount, max_cost_lamports: u64::MAX }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    pub async fn buy_partial(
+        &mut self,
+        launch: &Launch,
+        trader: &Keypair,
+        amount: u64,
+        max_cost_lamports: u64,
+        min_tokens_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.buy_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::BuyPartial { amount, max_cost_lamports, min_tokens_out }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    pub async fn buy_exact_in(
+        &mut self,
+        launch: &Launch,
+        trader: &Keypair,
+        lamports_in: u64,
+        min_tokens_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.buy_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::BuyExactIn { lamports_in, min_tokens_out }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    pub fn sell_accounts(&self, launch: &Launch, trader: &Pubkey) -> Vec<anchor_lang::prelude::AccountMeta> {
+        accounts!(Sell {
+            seller: *trader,
+            mint: launch.mint,
+            global_config: self.global_config,
+            state_pda: launch.state_pda,
+            authority_pda: launch.authority_pda,
+            vault_sol_pda: launch.vault_sol_pda,
+            vault_ata: launch.vault_ata,
+            seller_ata: get_associated_token_address(trader, &launch.mint),
+            system_program: anchor_lang::system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        })
+    }
+
+    pub async fn sell(&mut self, launch: &Launch, trader: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.sell_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::Sell { amount, min_payout_lamports: 0 }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    pub async fn sell_exact_out(
+        &mut self,
+        launch: &Launch,
+        trader: &Keypair,
+        lamports_out: u64,
+        max_tokens_in: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.sell_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::SellExactOut { lamports_out, max_tokens_in }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    pub async fn graduate(&mut self, launch: &Launch) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(Graduate {
+                caller: self.ctx.payer.pubkey(),
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+                pool_pda: launch.pool_pda,
+                system_program: anchor_lang::system_program::ID,
+            }),
+            data: grokpad::instruction::Graduate {}.data(),
+        };
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn pool(&mut self, launch: &Launch) -> Pool {
+        Pool::try_deserialize(&mut &self.account(&launch.pool_pda).await.unwrap().data[..]).unwrap()
+    }
+
+    fn swap_accounts(&self, launch: &Launch, trader: &Pubkey) -> Vec<anchor_lang::prelude::AccountMeta> {
+        accounts!(Swap {
+            trader: *trader,
+            mint: launch.mint,
+            global_config: self.global_config,
+            state_pda: launch.state_pda,
+            pool_pda: launch.pool_pda,
+            authority_pda: launch.authority_pda,
+            vault_sol_pda: launch.vault_sol_pda,
+            vault_ata: launch.vault_ata,
+            trader_ata: get_associated_token_address(trader, &launch.mint),
+            system_program: anchor_lang::system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        })
+    }
+
+    pub async fn swap_sol_for_tokens(
+        &mut self,
+        launch: &Launch,
+        trader: &Keypair,
+        lamports_in: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.swap_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::SwapSolForTokens { lamports_in, min_tokens_out: 1 }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    pub async fn swap_tokens_for_sol(
+        &mut self,
+        launch: &Launch,
+        trader: &Keypair,
+        tokens_in: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: self.swap_accounts(launch, &trader.pubkey()),
+            data: grokpad::instruction::SwapTokensForSol { tokens_in, min_lamports_out: 1 }.data(),
+        };
+        self.send(&[ix], &[trader]).await
+    }
+
+    /// `update_config` signed by the admin.
+    pub fn update_config_ix(&self, args: AdminArgs) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(Admin { global_config: self.global_config, admin: self.admin.pubkey() }),
+            data: grokpad::instruction::UpdateConfig { args }.data(),
+        }
+    }
+
+    pub async fn update_config(&mut self, args: AdminArgs) -> Result<(), BanksClientError> {
+        let ix = self.update_config_ix(args);
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin]).await
+    }
+
+    /// `set_launch_pause` signed by the admin.
+    pub fn set_launch_pause_ix(&self, launch: &Launch, paused: bool, exit_when_paused: bool) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(SetLaunchPause {
+                global_config: self.global_config,
+                admin: self.admin.pubkey(),
+                mint: launch.mint,
+                state_pda: launch.state_pda,
+            }),
+            data: grokpad::instruction::SetLaunchPause { paused, exit_when_paused }.data(),
+        }
+    }
+
+    pub async fn set_launch_pause(
+        &mut self,
+        launch: &Launch,
+        paused: bool,
+        exit_when_paused: bool,
+    ) -> Result<(), BanksClientError> {
+        let ix = self.set_launch_pause_ix(launch, paused, exit_when_paused);
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin]).await
+    }
+
+    pub fn close_launch_ix(&self, launch: &Launch) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(CloseLaunch {
+                caller: self.ctx.payer.pubkey(),
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+                authority_pda: launch.authority_pda,
+                vault_sol_pda: launch.vault_sol_pda,
+                vault_ata: launch.vault_ata,
+                creator: self.admin.pubkey(),
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }),
+            data: grokpad::instruction::CloseLaunch {}.data(),
+        }
+    }
+
+    /// `propose_creator`, to be signed by `creator`.
+    pub fn propose_creator_ix(&self, launch: &Launch, creator: &Pubkey, new_creator: Pubkey) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(ProposeCreator {
+                creator: *creator,
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+            }),
+            data: grokpad::instruction::ProposeCreator { new_creator }.data(),
+        }
+    }
+
+    /// `accept_creator`, to be signed by `new_creator`.
+    pub fn accept_creator_ix(&self, launch: &Launch, new_creator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(AcceptCreator {
+                new_creator: *new_creator,
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+            }),
+            data: grokpad::instruction::AcceptCreator {}.data(),
+        }
+    }
+
+    /// `set_fee_delegate`, to be signed by `recipient`.
+    pub fn set_fee_delegate_ix(&self, launch: &Launch, recipient: &Pubkey, kind: FeeKind, delegate: Pubkey) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(SetFeeDelegate {
+                recipient: *recipient,
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+            }),
+            data: grokpad::instruction::SetFeeDelegate { kind, delegate }.data(),
+        }
+    }
+
+    /// `migrate_launch`, paid for by the context payer.
+    pub fn migrate_launch_ix(&self, launch: &Launch) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(MigrateLaunch {
+                payer: self.ctx.payer.pubkey(),
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+                system_program: anchor_lang::system_program::ID,
+            }),
+            data: grokpad::instruction::MigrateLaunch {}.data(),
+        }
+    }
+
+    /// Claims `amount` of `kind` fees (all of them with `None`) as the admin,
+    /// who is every launch's fee recipient and creator.
+    pub fn claim_fees_ix(&self, launch: &Launch, kind: FeeKind, amount: Option<u64>) -> Instruction {
+        Instruction {
+            program_id: grokpad::ID,
+            accounts: accounts!(ClaimFees {
+                claimer: self.admin.pubkey(),
+                mint: launch.mint,
+                global_config: self.global_config,
+                state_pda: launch.state_pda,
+                vault_sol_pda: launch.vault_sol_pda,
+                destination: self.admin.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+            }),
+            data: match kind {
+                FeeKind::Platform => grokpad::instruction::ClaimPlatformFees { amount }.data(),
+                FeeKind::Creator => grokpad::instruction::ClaimCreatorFees { amount }.data(),
+            },
+        }
+    }
+
+    /// Claims all of both fee accruals.
+    pub async fn claim_fees(&mut self, launch: &Launch) -> Result<(), BanksClientError> {
+        let ixs = [self.claim_fees_ix(launch, FeeKind::Platform, None), self.claim_fees_ix(launch, FeeKind::Creator, None)];
+        let admin = self.admin.insecure_clone();
+        self.send(&ixs, &[&admin]).await
+    }
+}
+
+pub fn program_account(data: Vec<u8>) -> AccountSharedData {
+    AccountSharedData::from(Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: grokpad::ID,
+        executable: false,
+        rent_epoch: 0,
+    })
+}
+
+/// The program error code a failed transaction ended with.
+pub fn error_code(result: Result<(), BanksClientError>) -> Option<u32> {
+    use anchor_lang::solana_program::instruction::InstructionError;
+    use solana_transaction_error::TransactionError;
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code))))
+        | Err(BanksClientError::SimulationError {
+            err: TransactionError::InstructionError(_, InstructionError::Custom(code)),
+            ..
+        }) => Some(code),
+        _ => None,
+    }
+}
@@ -0,0 +1,244 @@
+//! Compute units per curve trade: the zero-copy `LaunchState` against the
+//! last revision that stored it with Borsh.
+//!
+//! Builds this tree and `BASELINE_REV` (checked out as a git worktree under
+//! `target/compute-units`) with `cargo build-sbf`, runs one buy and one sell
+//! on each and asserts the zero-copy build takes fewer units for both. It
+//! needs the Solana SBF toolchain, so it is ignored by default:
+//!
+//! ```text
+//! cargo test -p grokpad --test compute_units -- --ignored --nocapture
+//! ```
+//!
+//! Both programs are default builds; the baseline predates `event-cpi`, so
+//! the test doesn't run with that feature.
+#![cfg(not(feature = "event-cpi"))]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::bpf_loader;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::state::GlobalConfig;
+use solana_account::Account;
+use solana_keypair::Keypair;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+/// The last revision with a Borsh `LaunchState`.
+const BASELINE_REV: &str = "acb108dc8a945b21668353228a74809b76f4116c";
+const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+const TRADE: u64 = 1_000_000_000;
+/// Far beyond the trades measured, but within what selling out reaches.
+const GRADUATION_RESERVES: u64 = 1_000_000_000_000_000;
+
+#[derive(Debug, Clone, Copy)]
+struct TradeCosts {
+    buy: u64,
+    sell: u64,
+}
+
+/// Where this test keeps its builds and the baseline checkout.
+fn work_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/compute-units")
+}
+
+/// Builds the program at `manifest` into `out` and returns the `.so`.
+fn build_sbf(manifest: &Path, out: &Path) -> PathBuf {
+    let status = Command::new("cargo")
+        .args(["build-sbf", "--manifest-path"])
+        .arg(manifest)
+        .arg("--sbf-out-dir")
+        .arg(out)
+        .status()
+        .expect("cargo build-sbf isn't installed");
+    assert!(status.success(), "cargo build-sbf failed for {}", manifest.display());
+    out.join("grokpad.so")
+}
+
+/// A worktree of `BASELINE_REV`, checked out on the first run.
+fn baseline_checkout() -> PathBuf {
+    let checkout = work_dir().join("baseline");
+    if !checkout.exists() {
+        let git = |args: &[&str]| {
+            let status = Command::new("git").current_dir(env!("CARGO_MANIFEST_DIR")).args(args).status().unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+        // A checkout removed with `target/` leaves its worktree registered
+        git(&["worktree", "prune"]);
+        git(&["worktree", "add", "--detach", checkout.to_str().unwrap(), BASELINE_REV]);
+    }
+    checkout
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &grokpad::ID).0
+}
+
+/// An account holding `lamports` for `owner`.
+fn account(lamports: u64, data: Vec<u8>, owner: Pubkey, executable: bool) -> Account {
+    Account { lamports, data, owner, executable, rent_epoch: 0 }
+}
+
+async fn send(ctx: &mut ProgramTestContext, ix: Instruction, signers: &[&Keypair]) -> u64 {
+    let blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&signers[0].pubkey()), signers, blockhash);
+    let result = ctx.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+    let meta = result.metadata.unwrap();
+    result.result.unwrap_or_else(|e| panic!("{e}: {:?}", meta.log_messages));
+    meta.compute_units_consumed
+}
+
+/// Sets up a launch on `program` and returns the units one buy and one
+/// sell of `TRADE` base units take. `fee_accounts` passes the platform fee
+/// recipient and creator to the trades after the trader's token account,
+/// as the baseline's `Buy` and `Sell` still take them.
+async fn measure(program: &Path, fee_accounts: bool) -> TradeCosts {
+    let rent = Rent::default();
+    let mut program_test = ProgramTest::default();
+    let elf = std::fs::read(program).unwrap();
+    program_test.add_account(grokpad::ID, account(rent.minimum_balance(elf.len()), elf, bpf_loader::ID, true));
+    let admin = Keypair::new();
+    let trader = Keypair::new();
+    let mint = Keypair::new();
+    for holder in [&admin, &trader] {
+        program_test.add_account(holder.pubkey(), account(100_000_000_000, Vec::new(), anchor_lang::system_program::ID, false));
+    }
+
+    // The program isn't upgradeable here, so the config is written directly
+    let (global_config, bump) = Pubkey::find_program_address(&[b"config"], &grokpad::ID);
+    let config = GlobalConfig {
+        admin: admin.pubkey(),
+        treasury: admin.pubkey(),
+        curve_fee_bps: 100,
+        amm_fee_bps: 30,
+        creator_kickback_bps: 50,
+        max_creator_fee_bps: 100,
+        graduation_target_lamports: u64::MAX,
+        paused: false,
+        exit_when_paused: true,
+        migration_program: Pubkey::default(),
+        bump,
+    };
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+    program_test.add_account(global_config, account(rent.minimum_balance(data.len()), data, grokpad::ID, false));
+    let mut ctx = program_test.start_with_context().await;
+
+    let mint_key = mint.pubkey();
+    let authority_pda = pda(&[b"launch", mint_key.as_ref()]);
+    let state_pda = pda(&[b"launch_state", mint_key.as_ref()]);
+    let vault_sol_pda = pda(&[b"vault_sol", mint_key.as_ref()]);
+    let vault_ata = get_associated_token_address(&authority_pda, &mint_key);
+    let trader_ata = get_associated_token_address(&trader.pubkey(), &mint_key);
+
+    let init = Instruction {
+        program_id: grokpad::ID,
+        accounts: grokpad::accounts::InitializeLaunchWithMint {
+            payer: admin.pubkey(),
+            creator: admin.pubkey(),
+            global_config,
+            mint: mint_key,
+            authority_pda,
+            state_pda,
+            vault_sol_pda,
+            vault_ata,
+            system_program: anchor_lang::system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: grokpad::instruction::InitializeLaunchWithMint {
+            decimals: 6,
+            total_supply: TOTAL_SUPPLY,
+            base_price_lamports: 1_000,
+            slope_lamports: 10,
+            creator_fee_bps: None,
+            graduation_criteria: Some(vec![GraduationCriterion::Reserves { lamports: GRADUATION_RESERVES }]),
+            cap_graduating_buy: false,
+        }
+        .data(),
+    };
+    send(&mut ctx, init, &[&admin, &mint]).await;
+
+    let buy = Instruction {
+        program_id: grokpad::ID,
+        accounts: grokpad::accounts::Buy {
+            buyer: trader.pubkey(),
+            mint: mint_key,
+            global_config,
+            state_pda,
+            authority_pda,
+            vault_sol_pda,
+            vault_ata,
+            recipient: trader.pubkey(),
+            recipient_ata: trader_ata,
+            system_program: anchor_lang::system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: grokpad::instruction::Buy { amount: TRADE, max_cost_lamports: u64::MAX }.data(),
+    };
+    let buy = with_fee_accounts(buy, trader_ata, &admin.pubkey(), fee_accounts);
+    // The first buy also creates the trader's token account
+    send(&mut ctx, buy.clone(), &[&trader]).await;
+    let buy = send(&mut ctx, buy, &[&trader]).await;
+
+    let sell = Instruction {
+        program_id: grokpad::ID,
+        accounts: grokpad::accounts::Sell {
+            seller: trader.pubkey(),
+            mint: mint_key,
+            global_config,
+            state_pda,
+            authority_pda,
+            vault_sol_pda,
+            vault_ata,
+            seller_ata: trader_ata,
+            system_program: anchor_lang::system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: grokpad::instruction::Sell { amount: TRADE, min_payout_lamports: 0 }.data(),
+    };
+    let sell = with_fee_accounts(sell, trader_ata, &admin.pubkey(), fee_accounts);
+    let sell = send(&mut ctx, sell, &[&trader]).await;
+    TradeCosts { buy, sell }
+}
+
+/// `trade` with `recipient` as both fee accounts after `trader_ata`, when
+/// `fee_accounts` is set.
+fn with_fee_accounts(mut trade: Instruction, trader_ata: Pubkey, recipient: &Pubkey, fee_accounts: bool) -> Instruction {
+    if fee_accounts {
+        let at = trade.accounts.iter().position(|meta| meta.pubkey == trader_ata).unwrap() + 1;
+        trade.accounts.splice(at..at, vec![AccountMeta::new_readonly(*recipient, false); 2]);
+    }
+    trade
+}
+
+#[tokio::test]
+#[ignore = "needs the SBF toolchain"]
+async fn zero_copy_trades_cost_less_than_borsh() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let current = build_sbf(&manifest, &work_dir().join("current"));
+    let baseline_manifest = baseline_checkout().join("programs/grokpad/Cargo.toml");
+    let baseline = build_sbf(&baseline_manifest, &work_dir().join("baseline-deploy"));
+
+    let current = measure(&current, false).await;
+    let baseline = measure(&baseline, true).await;
+    println!("zero-copy LaunchState: buy {} CU, sell {} CU", current.buy, current.sell);
+    println!("Borsh LaunchState:     buy {} CU, sell {} CU", baseline.buy, baseline.sell);
+    assert!(current.buy < baseline.buy, "zero-copy buy isn't cheaper: {current:?} vs {baseline:?}");
+    assert!(current.sell < baseline.sell, "zero-copy sell isn't cheaper: {current:?} vs {baseline:?}");
+}
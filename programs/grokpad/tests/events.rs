@@ -0,0 +1,113 @@
+//! Events decode from the self-CPI inner instructions `event-cpi` builds
+//! emit, and from `Program data:` logs, with nothing else mistaken for one.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{Event, InstructionData};
+use grokpad::events::{decode_cpi_event, decode_event, event_authority, events_from_inner_instructions, GrokEvent};
+use grokpad::graduation::GraduationCriterion;
+use grokpad::{BuyEvent, GraduateEvent, PauseEvent};
+
+fn buy_event() -> BuyEvent {
+    BuyEvent {
+        mint: Pubkey::new_from_array([1; 32]),
+        buyer: Pubkey::new_from_array([2; 32]),
+        recipient: Pubkey::new_from_array([3; 32]),
+        requested_amount: 5_000,
+        amount: 4_000,
+        cost_lamports: 1_234_567,
+        platform_fee: 12_345,
+        creator_fee: 6_172,
+        reserves_after: 9_000_000,
+        tokens_sold_after: 40_000,
+        graduated: true,
+        decimals: 6,
+        price_before: 1_300,
+        price_after: 1_400,
+        trade_seq: 17,
+        slot: 250_000_000,
+        unix_timestamp: 1_760_000_000,
+    }
+}
+
+fn graduate_event() -> GraduateEvent {
+    GraduateEvent {
+        mint: Pubkey::new_from_array([1; 32]),
+        criterion: GraduationCriterion::PercentSold { bps: 8_000 },
+        reserves_lamports: 9_000_000,
+        tokens_sold: 40_000,
+    }
+}
+
+/// Instruction data of the self-CPI that `emit_cpi!` makes for `event`.
+fn cpi_data(event: &impl Event) -> Vec<u8> {
+    let mut data = EVENT_IX_TAG_LE.to_vec();
+    data.extend(event.data());
+    data
+}
+
+#[test]
+fn cpi_event_round_trips() {
+    let decoded = decode_cpi_event(&grokpad::ID, &cpi_data(&buy_event())).unwrap();
+    let Some(GrokEvent::BuyEvent(event)) = decoded else { panic!("not a buy event") };
+    let expected = buy_event();
+    assert_eq!(event.mint, expected.mint);
+    assert_eq!(event.buyer, expected.buyer);
+    assert_eq!(event.recipient, expected.recipient);
+    assert_eq!(event.amount, expected.amount);
+    assert_eq!(event.cost_lamports, expected.cost_lamports);
+    assert_eq!(event.platform_fee, expected.platform_fee);
+    assert_eq!(event.creator_fee, expected.creator_fee);
+    assert_eq!(event.tokens_sold_after, expected.tokens_sold_after);
+    assert!(event.graduated);
+    assert_eq!((event.decimals, event.price_before, event.price_after), (6, 1_300, 1_400));
+    assert_eq!((event.trade_seq, event.slot, event.unix_timestamp), (17, 250_000_000, 1_760_000_000));
+}
+
+#[test]
+fn log_event_decodes_without_the_tag() {
+    let pause = PauseEvent { mint: None, paused: true, exit_when_paused: false };
+    let Ok(GrokEvent::PauseEvent(event)) = decode_event(&pause.data()) else { panic!("not a pause event") };
+    assert_eq!(event.mint, None);
+    assert!(event.paused && !event.exit_when_paused);
+}
+
+#[test]
+fn other_instructions_are_not_events() {
+    let other_program = Pubkey::new_from_array([9; 32]);
+    assert!(decode_cpi_event(&other_program, &cpi_data(&buy_event())).unwrap().is_none());
+    let trade = grokpad::instruction::Buy { amount: 1, max_cost_lamports: 2 }.data();
+    assert!(decode_cpi_event(&grokpad::ID, &trade).unwrap().is_none());
+    assert!(decode_cpi_event(&grokpad::ID, &[]).unwrap().is_none());
+}
+
+#[test]
+fn malformed_events_are_errors() {
+    let data = cpi_data(&buy_event());
+    assert!(decode_cpi_event(&grokpad::ID, &data[..data.len() - 1]).is_err());
+    let mut unknown = EVENT_IX_TAG_LE.to_vec();
+    unknown.extend([0xff; 16]);
+    assert!(decode_cpi_event(&grokpad::ID, &unknown).is_err());
+}
+
+#[test]
+fn inner_instructions_yield_events_in_order() {
+    let token_program = anchor_spl::token::ID;
+    let transfer = vec![3, 0, 0, 0, 0, 0, 0, 0, 0];
+    let graduate = cpi_data(&graduate_event());
+    let buy = cpi_data(&buy_event());
+    let inner: Vec<(&Pubkey, &[u8])> = vec![
+        (&token_program, &transfer),
+        (&grokpad::ID, &graduate),
+        (&token_program, &transfer),
+        (&grokpad::ID, &buy),
+    ];
+    let events = events_from_inner_instructions(inner).unwrap();
+    assert_eq!(events.iter().map(GrokEvent::name).collect::<Vec<_>>(), ["GraduateEvent", "BuyEvent"]);
+}
+
+#[test]
+fn event_authority_is_the_anchor_pda() {
+    let (expected, _) = Pubkey::find_program_address(&[b"__event_authority"], &grokpad::ID);
+    assert_eq!(event_authority(), expected);
+}
@@ -0,0 +1,45 @@
+use grokpad::graduation::{validate_criteria, GraduationCriterion, MAX_GRADUATION_CRITERIA};
+use grokpad_math::CurveState;
+
+fn curve(tokens_sold: u64, reserves_lamports: u64) -> CurveState {
+    CurveState { base_price_lamports: 1_000, slope_lamports: 100, tokens_sold, reserves_lamports, ..CurveState::default() }
+}
+
+#[test]
+fn each_criterion_trips_at_its_threshold() {
+    let reserves = GraduationCriterion::Reserves { lamports: 500 };
+    assert!(!reserves.is_met(&curve(0, 499), 1_000, 0));
+    assert!(reserves.is_met(&curve(0, 500), 1_000, 0));
+
+    let sold_out = GraduationCriterion::PercentSold { bps: 8_000 };
+    assert!(!sold_out.is_met(&curve(799, 0), 1_000, 0));
+    assert!(sold_out.is_met(&curve(800, 0), 1_000, 0));
+    // An unfunded launch has sold 80% of nothing
+    assert!(!sold_out.is_met(&curve(0, 0), 0, 0));
+
+    // Spot price is 1_000 + 100 * 10 = 2_000 per token, on 1_000 tokens
+    let market_cap = GraduationCriterion::MarketCap { lamports: 2_000_000 };
+    assert!(!market_cap.is_met(&curve(9, 0), 1_000, 0));
+    assert!(market_cap.is_met(&curve(10, 0), 1_000, 0));
+
+    let deadline = GraduationCriterion::Deadline { unix_timestamp: 100 };
+    assert!(!deadline.is_met(&curve(0, 0), 1_000, 99));
+    assert!(deadline.is_met(&curve(0, 0), 1_000, 100));
+}
+
+#[test]
+fn criteria_lists_are_validated() {
+    let now = 1_000;
+    assert!(validate_criteria(&[], now).is_err());
+    assert!(validate_criteria(&[GraduationCriterion::Reserves { lamports: 1 }; MAX_GRADUATION_CRITERIA + 1], now).is_err());
+    assert!(validate_criteria(&[GraduationCriterion::Reserves { lamports: 0 }], now).is_err());
+    // The pool allocation is never sold, so the curve sells out at 80%
+    assert!(validate_criteria(&[GraduationCriterion::PercentSold { bps: 8_001 }], now).is_err());
+    assert!(validate_criteria(&[GraduationCriterion::PercentSold { bps: 10_000 }], now).is_err());
+    assert!(validate_criteria(&[GraduationCriterion::Deadline { unix_timestamp: now }], now).is_err());
+    assert!(validate_criteria(
+        &[GraduationCriterion::PercentSold { bps: 8_000 }, GraduationCriterion::MarketCap { lamports: 1 }],
+        now
+    )
+    .is_ok());
+}
@@ -0,0 +1,100 @@
+//! Launch setup through both paths: a fixed-supply mint created by the
+//! program, and an existing mint funded with `fund_curve`.
+
+mod common;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use common::*;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::GrokError;
+use solana_signer::Signer;
+
+/// Reserves the curve holds once it sells out, before fees: the curve sells
+/// 80% of `TOTAL_SUPPLY`, 800_000 whole tokens.
+const SOLD_OUT_RESERVES: u64 = 800_000_000 + 3_200_000_000_000;
+
+async fn initialize_and_fund(
+    harness: &mut Harness,
+    graduation_criteria: Vec<GraduationCriterion>,
+) -> Result<Launch, solana_program_test::BanksClientError> {
+    let (mint, admin_ata) = harness.external_mint(TOTAL_SUPPLY).await;
+    let launch = Launch::new(mint);
+    let admin = harness.admin.insecure_clone();
+    let init = Instruction {
+        program_id: grokpad::ID,
+        accounts: accounts!(InitializeLaunch {
+            payer: admin.pubkey(),
+            creator: admin.pubkey(),
+            global_config: harness.global_config,
+            mint: launch.mint,
+            authority_pda: launch.authority_pda,
+            state_pda: launch.state_pda,
+            vault_sol_pda: launch.vault_sol_pda,
+            vault_ata: launch.vault_ata,
+            system_program: anchor_lang::system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        }),
+        data: grokpad::instruction::InitializeLaunch {
+            base_price_lamports: BASE_PRICE,
+            slope_lamports: SLOPE,
+            creator_fee_bps: None,
+            graduation_criteria: Some(graduation_criteria),
+            cap_graduating_buy: false,
+        }
+        .data(),
+    };
+    harness.send(&[init], &[&admin]).await?;
+    let fund = Instruction {
+        program_id: grokpad::ID,
+        accounts: accounts!(FundCurve {
+            creator: admin.pubkey(),
+            mint: launch.mint,
+            global_config: harness.global_config,
+            state_pda: launch.state_pda,
+            authority_pda: launch.authority_pda,
+            creator_ata: admin_ata,
+            vault_ata: launch.vault_ata,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        }),
+        data: grokpad::instruction::FundCurve { amount: TOTAL_SUPPLY }.data(),
+    };
+    harness.send(&[fund], &[&admin]).await?;
+    Ok(launch)
+}
+
+#[tokio::test]
+async fn both_paths_reject_criteria_selling_out_cannot_meet() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let unreachable = vec![GraduationCriterion::Reserves { lamports: SOLD_OUT_RESERVES * 2 }];
+
+    let result = harness.launch_with_mint(Some(unreachable.clone())).await;
+    assert_eq!(error_code(result.map(|_| ())), Some(GrokError::InvalidParam.into()));
+
+    let result = initialize_and_fund(&mut harness, unreachable).await;
+    assert_eq!(error_code(result.map(|_| ())), Some(GrokError::InvalidParam.into()));
+
+    // The default config target is out of reach too
+    let result = harness.launch_with_mint(None).await;
+    assert_eq!(error_code(result.map(|_| ())), Some(GrokError::InvalidParam.into()));
+}
+
+#[tokio::test]
+async fn funded_launch_opens_with_the_vault_balance() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = initialize_and_fund(&mut harness, vec![GraduationCriterion::Reserves { lamports: 1_000_000_000_000 }])
+        .await
+        .unwrap();
+    let state = harness.state(&launch).await;
+    assert_eq!(state.total_supply, TOTAL_SUPPLY);
+    assert_eq!(state.pool_allocation, TOTAL_SUPPLY / 5);
+    assert_eq!(state.supply_remaining, TOTAL_SUPPLY - state.pool_allocation);
+    assert_eq!(harness.token_balance(&launch.vault_ata).await, TOTAL_SUPPLY);
+    let admin_ata = get_associated_token_address(&harness.admin.pubkey(), &launch.mint);
+    assert_eq!(harness.token_balance(&admin_ata).await, 0);
+}
@@ -0,0 +1,128 @@
+//! Launches stored in older `LaunchState` layouts still decode and upgrade.
+//! The fixtures are live-sized accounts written by those layouts.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use common::{error_code, program_account, Harness, Launch};
+use grokpad::graduation::{GraduationCriterion, StoredCriterion};
+use grokpad::layout::{
+    decode_launch_state, layout_version, LaunchStateV0, LaunchStateV1, LAUNCH_STATE_RESERVED, LAUNCH_STATE_VERSION,
+};
+use grokpad::{GrokError, LaunchState};
+
+const V0_FIXTURE: &[u8] = include_bytes!("fixtures/launch_state_v0.bin");
+const V1_FIXTURE: &[u8] = include_bytes!("fixtures/launch_state_v1.bin");
+
+fn account_data(state: &LaunchState) -> Vec<u8> {
+    let mut data = LaunchState::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(state));
+    data
+}
+
+#[test]
+fn layouts_are_told_apart_by_size() {
+    assert_eq!(V0_FIXTURE.len(), LaunchStateV0::LEN);
+    assert_eq!(V1_FIXTURE.len(), LaunchStateV1::LEN);
+    assert_eq!(layout_version(LaunchStateV0::LEN), Some(0));
+    assert_eq!(layout_version(LaunchStateV1::LEN), Some(1));
+    assert_eq!(layout_version(LaunchState::LEN), Some(LAUNCH_STATE_VERSION));
+    // Eight-byte aligned with no implicit padding, as `Pod` requires
+    assert_eq!(core::mem::size_of::<LaunchState>() % 8, 0);
+    assert_eq!(core::mem::size_of::<StoredCriterion>(), 16);
+}
+
+#[test]
+fn v0_fixture_decodes_and_upgrades() {
+    assert_eq!(LaunchStateV0::LEN, 174);
+    let state = decode_launch_state(V0_FIXTURE).unwrap();
+    assert_eq!(state.version, LAUNCH_STATE_VERSION);
+    assert_eq!(state.mint, Pubkey::new_from_array([1; 32]));
+    assert_eq!(state.platform_fee_recipient, Pubkey::new_from_array([2; 32]));
+    assert_eq!(state.creator, Pubkey::new_from_array([3; 32]));
+    assert_eq!((state.decimals, state.base_price_lamports, state.slope_lamports), (6, 1_000, 10));
+    assert_eq!((state.fee_bps, state.creator_fee_bps), (100, 50));
+    // The single reserves target becomes the only criterion
+    assert_eq!(state.criteria().collect::<Vec<_>>(), vec![GraduationCriterion::Reserves { lamports: 85_000_000_000 }]);
+    // Total supply is what the curve sold plus what it held, all of it
+    // still for sale: the upgrade holds nothing back for the pool
+    assert_eq!(state.total_supply, 1_000_000_000_000_000);
+    assert_eq!((state.supply_remaining, state.tokens_sold), (400_000_000_000_000, 600_000_000_000_000));
+    assert_eq!(state.pool_allocation, 0);
+    assert_eq!(
+        (state.reserves_lamports, state.platform_fee_accrued, state.creator_fee_accrued),
+        (42_000_000_000, 420_000_000, 210_000_000)
+    );
+    assert_eq!((state.cap_graduating_buy, state.exit_when_paused, state.paused, state.graduated), (0, 1, 0, 0));
+    assert_eq!(state.pool, Pubkey::default());
+    assert_eq!(state.creator_fee_delegate, Pubkey::default());
+    assert_eq!((state.volume_lamports, state.platform_fees_claimed, state.creator_fees_claimed), (0, 0, 0));
+    assert_eq!(state.reserved, [0; LAUNCH_STATE_RESERVED]);
+}
+
+#[test]
+fn v1_fixture_decodes_and_upgrades() {
+    let state = decode_launch_state(V1_FIXTURE).unwrap();
+    assert_eq!(state.version, LAUNCH_STATE_VERSION);
+    assert!(state.is_graduated());
+    assert_eq!(state.pool, Pubkey::new_from_array([5; 32]));
+    assert_eq!(
+        state.criteria().collect::<Vec<_>>(),
+        vec![
+            GraduationCriterion::Reserves { lamports: 85_000_000_000 },
+            GraduationCriterion::Deadline { unix_timestamp: 1_800_000_000 },
+            GraduationCriterion::MarketCap { lamports: 7_000_000_000_000 },
+            GraduationCriterion::PercentSold { bps: 8_000 },
+        ]
+    );
+    assert_eq!(state.reserves_lamports, 42_000_000_000);
+}
+
+#[test]
+fn current_layout_round_trips() {
+    let state = decode_launch_state(V1_FIXTURE).unwrap();
+    let data = account_data(&state);
+    assert_eq!(data.len(), LaunchState::LEN);
+    let decoded = decode_launch_state(&data).unwrap();
+    assert_eq!(account_data(&decoded), data);
+    assert_eq!(decoded.criteria().count(), 4);
+}
+
+#[test]
+fn new_fields_fit_in_the_old_reserved_bytes() {
+    // Version 2 accounts keep their size, and the new fields sit where they
+    // hold zeroed reserved bytes
+    assert_eq!(LaunchState::LEN, 464);
+    let version = core::mem::offset_of!(LaunchState, version);
+    assert_eq!(core::mem::offset_of!(LaunchState, trade_seq), version + 6);
+    assert_eq!(core::mem::offset_of!(LaunchState, pool_allocation), version + 14);
+    assert_eq!(core::mem::offset_of!(LaunchState, reserved), version + 22);
+    assert_eq!(decode_launch_state(V1_FIXTURE).unwrap().trade_seq, 0);
+}
+
+#[test]
+fn rejects_unknown_sizes_and_accounts() {
+    let mut data = V0_FIXTURE.to_vec();
+    data.push(0);
+    assert!(decode_launch_state(&data).is_err());
+    let mut data = V1_FIXTURE.to_vec();
+    data[0] ^= 1;
+    assert!(decode_launch_state(&data).is_err());
+    assert!(decode_launch_state(&[]).is_err());
+}
+
+#[tokio::test]
+async fn migrate_launch_rewrites_a_v0_account() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let (mint, _) = harness.external_mint(1).await;
+    let launch = Launch::new(mint);
+    harness.ctx.set_account(&launch.state_pda, &program_account(V0_FIXTURE.to_vec()));
+    let migrate = harness.migrate_launch_ix(&launch);
+    harness.send(std::slice::from_ref(&migrate), &[]).await.unwrap();
+    let account = harness.account(&launch.state_pda).await.unwrap();
+    assert_eq!(account.data, account_data(&decode_launch_state(V0_FIXTURE).unwrap()));
+
+    let result = harness.send(&[migrate], &[]).await;
+    assert_eq!(error_code(result), Some(GrokError::AlreadyMigrated.into()));
+}
@@ -0,0 +1,220 @@
+#[cfg(feature = "mock-amm")]
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+#[cfg(feature = "mock-amm")]
+use anchor_lang::Discriminator;
+use grokpad::migration::adapter_for;
+#[cfg(feature = "mock-amm")]
+use grokpad::migration::MigrationKeys;
+
+#[cfg(feature = "mock-amm")]
+fn keys() -> MigrationKeys {
+    MigrationKeys {
+        payer: Pubkey::new_unique(),
+        sol_source: Pubkey::new_unique(),
+        token_authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        token_source: Pubkey::new_unique(),
+        pool: Pubkey::new_unique(),
+        pool_token_vault: Pubkey::new_unique(),
+        lp_mint: Pubkey::new_unique(),
+        lp_destination: Pubkey::new_unique(),
+    }
+}
+
+#[cfg(feature = "mock-amm")]
+#[test]
+fn mock_amm_adapter_builds_create_pool() {
+    let adapter = adapter_for(&mock_amm::ID).expect("mock AMM adapter");
+    assert_eq!(adapter.program_id(), mock_amm::ID);
+    let keys = keys();
+    let ix = adapter.create_pool_ix(&keys, 5_000, 7_000);
+    assert_eq!(ix.program_id, mock_amm::ID);
+
+    let (discriminator, args) = ix.data.split_at(8);
+    assert_eq!(discriminator, mock_amm::instruction::CreatePool::DISCRIMINATOR);
+    assert_eq!(args, [5_000u64.to_le_bytes(), 7_000u64.to_le_bytes()].concat());
+
+    // Only the program's two PDAs and the payer sign
+    let signers: Vec<Pubkey> = ix.accounts.iter().filter(|m| m.is_signer).map(|m| m.pubkey).collect();
+    assert_eq!(signers, vec![keys.payer, keys.sol_source, keys.token_authority]);
+    let meta = |key: Pubkey| ix.accounts.iter().find(|m| m.pubkey == key).expect("account passed");
+    for writable in [keys.sol_source, keys.token_source, keys.pool, keys.pool_token_vault, keys.lp_mint, keys.lp_destination] {
+        assert!(meta(writable).is_writable);
+    }
+    assert!(!meta(keys.mint).is_writable);
+}
+
+#[test]
+fn unknown_amm_has_no_adapter() {
+    assert!(adapter_for(&Pubkey::new_unique()).is_none());
+    assert!(adapter_for(&grokpad::ID).is_none());
+}
+
+#[cfg(not(feature = "mock-amm"))]
+#[test]
+fn mock_amm_needs_its_feature() {
+    assert!(adapter_for(&mock_amm::ID).is_none());
+}
+
+/// `graduate_to_amm` run against the workspace's mock AMM, which only has
+/// an adapter in `mock-amm` builds:
+///
+/// ```text
+/// cargo test -p grokpad --features mock-amm --test migration
+/// ```
+#[cfg(feature = "mock-amm")]
+mod graduate_to_amm {
+    use anchor_lang::prelude::{AccountInfo, Pubkey};
+    use anchor_lang::solana_program::entrypoint::ProgramResult;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_lang::{AccountDeserialize, InstructionData};
+    use anchor_spl::associated_token::get_associated_token_address;
+    use anchor_spl::token::spl_token;
+    use super::common::*;
+    use grokpad::graduation::GraduationCriterion;
+    use grokpad::instructions::AdminArgs;
+    use grokpad::migration::LpHandling;
+    use grokpad::GrokError;
+    use solana_program_test::{processor, BanksClientError};
+    use solana_signer::Signer;
+
+    fn mock_amm_process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+        mock_amm::entry(program_id, accounts, data)
+    }
+
+    /// The mock AMM, but depositing one token less than it is asked to.
+    fn skimming_amm_process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        let mut data = data.to_vec();
+        let token_amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        data[16..24].copy_from_slice(&(token_amount - 1).to_le_bytes());
+        mock_amm_process(program_id, accounts, &data)
+    }
+
+    /// The mock AMM's accounts for `launch`.
+    struct AmmKeys {
+        pool: Pubkey,
+        pool_token_vault: Pubkey,
+        lp_mint: Pubkey,
+        lp_destination: Pubkey,
+    }
+
+    impl AmmKeys {
+        fn new(launch: &Launch) -> Self {
+            let pool = Pubkey::find_program_address(&[mock_amm::POOL_SEED, launch.mint.as_ref()], &mock_amm::ID).0;
+            let lp_mint = Pubkey::find_program_address(&[mock_amm::LP_MINT_SEED, pool.as_ref()], &mock_amm::ID).0;
+            AmmKeys {
+                pool,
+                pool_token_vault: get_associated_token_address(&pool, &launch.mint),
+                lp_mint,
+                lp_destination: get_associated_token_address(&launch.authority_pda, &lp_mint),
+            }
+        }
+    }
+
+    /// A cluster whose config migrates to the mock AMM, skimming its
+    /// deposit when `skim` is set, and a launch that has sold out its curve.
+    async fn sold_out(skim: bool) -> (Harness, Launch) {
+        let mut harness = Harness::start_with(u64::MAX, |program| {
+            if skim {
+                program.add_program("mock_amm", mock_amm::ID, processor!(skimming_amm_process));
+            } else {
+                program.add_program("mock_amm", mock_amm::ID, processor!(mock_amm_process));
+            }
+        })
+        .await;
+        let migrate_to_mock = AdminArgs { migration_program: Some(mock_amm::ID), ..AdminArgs::default() };
+        harness.update_config(migrate_to_mock).await.unwrap();
+        let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+        let trader = harness.trader().await;
+        let supply = harness.state(&launch).await.supply_remaining;
+        harness.buy(&launch, &trader, supply).await.unwrap();
+        (harness, launch)
+    }
+
+    async fn graduate_to_amm(
+        harness: &mut Harness,
+        launch: &Launch,
+        lp_handling: LpHandling,
+    ) -> Result<(), BanksClientError> {
+        let amm = AmmKeys::new(launch);
+        let ix = Instruction {
+            program_id: grokpad::ID,
+            accounts: crate::accounts!(GraduateToAmm {
+                caller: harness.ctx.payer.pubkey(),
+                mint: launch.mint,
+                global_config: harness.global_config,
+                state_pda: launch.state_pda,
+                authority_pda: launch.authority_pda,
+                vault_sol_pda: launch.vault_sol_pda,
+                vault_ata: launch.vault_ata,
+                amm_program: mock_amm::ID,
+                amm_pool: amm.pool,
+                amm_pool_token_vault: amm.pool_token_vault,
+                lp_mint: amm.lp_mint,
+                lp_destination: amm.lp_destination,
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            }),
+            data: grokpad::instruction::GraduateToAmm { lp_handling }.data(),
+        };
+        harness.send(&[ix], &[]).await
+    }
+
+    /// Migrates a sold-out launch and checks where its liquidity went;
+    /// returns the LP tokens left in `lp_destination` and the LP supply.
+    async fn migrates(lp_handling: LpHandling) -> (u64, u64) {
+        let (mut harness, launch) = sold_out(false).await;
+        let before = harness.state(&launch).await;
+        let tokens = before.vault_tokens().unwrap();
+        let vault_before = harness.lamports(&launch.vault_sol_pda).await;
+        graduate_to_amm(&mut harness, &launch, lp_handling).await.unwrap();
+
+        let amm = AmmKeys::new(&launch);
+        let state = harness.state(&launch).await;
+        assert_eq!(state.pool, amm.pool);
+        assert_eq!((state.reserves_lamports, state.supply_remaining, state.pool_allocation), (0, 0, 0));
+        // Only the reserves and the unsold tokens move; fees and rent stay
+        assert_eq!(vault_before - harness.lamports(&launch.vault_sol_pda).await, before.reserves_lamports);
+        assert_eq!(harness.token_balance(&launch.vault_ata).await, 0);
+        assert_eq!(harness.token_balance(&amm.pool_token_vault).await, tokens);
+        let pool = harness.account(&amm.pool).await.unwrap();
+        let recorded = mock_amm::AmmPool::try_deserialize(&mut &pool.data[..]).unwrap();
+        assert_eq!((recorded.sol_reserves, recorded.token_reserves), (before.reserves_lamports, tokens));
+
+        // A launch migrates once
+        let result = graduate_to_amm(&mut harness, &launch, lp_handling).await;
+        assert_eq!(error_code(result), Some(GrokError::PoolEmpty.into()));
+
+        let lp_mint = harness.account(&amm.lp_mint).await.unwrap();
+        let lp_supply = spl_token::state::Mint::unpack(&lp_mint.data).unwrap().supply;
+        (harness.token_balance(&amm.lp_destination).await, lp_supply)
+    }
+
+    #[tokio::test]
+    async fn locked_lp_stays_with_the_launch() {
+        let (held, supply) = migrates(LpHandling::Lock).await;
+        assert!(held > 0);
+        assert_eq!(held, supply);
+    }
+
+    #[tokio::test]
+    async fn burned_lp_is_gone() {
+        assert_eq!(migrates(LpHandling::Burn).await, (0, 0));
+    }
+
+    #[tokio::test]
+    async fn amm_taking_the_wrong_amount_is_rejected() {
+        let (mut harness, launch) = sold_out(true).await;
+        let result = graduate_to_amm(&mut harness, &launch, LpHandling::Lock).await;
+        assert_eq!(error_code(result), Some(GrokError::MigrationMismatch.into()));
+        let state = harness.state(&launch).await;
+        assert_eq!(state.pool, Pubkey::default());
+        assert!(harness.account(&AmmKeys::new(&launch).pool).await.is_none());
+    }
+}
@@ -0,0 +1,176 @@
+//! Pauses through the program: the platform-wide switch in `update_config`
+//! and the per-launch one in `set_launch_pause` stop buys, swaps into the
+//! pool and role changes, and leave the exits open only while
+//! `exit_when_paused` allows it.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::instructions::AdminArgs;
+use grokpad::{FeeKind, GrokError};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const V0_FIXTURE: &[u8] = include_bytes!("fixtures/launch_state_v0.bin");
+
+/// A launch on the curve and a trader holding some of it.
+async fn trading(harness: &mut Harness) -> (Launch, Keypair) {
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    harness.buy(&launch, &trader, 10_000_000_000).await.unwrap();
+    (launch, trader)
+}
+
+/// A launch trading in its in-program pool and a trader holding some of it.
+async fn graduated(harness: &mut Harness) -> (Launch, Keypair) {
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    let supply = harness.state(&launch).await.supply_remaining;
+    harness.buy(&launch, &trader, supply).await.unwrap();
+    harness.graduate(&launch).await.unwrap();
+    (launch, trader)
+}
+
+fn paused(result: Result<(), solana_program_test::BanksClientError>) -> bool {
+    error_code(result) == Some(GrokError::Paused.into())
+}
+
+/// Whether each of the curve's exits is open to `trader`. Successful sells
+/// leave the trader holding less, so the amounts stay small.
+async fn curve_exits(harness: &mut Harness, launch: &Launch, trader: &Keypair) -> [bool; 2] {
+    [
+        !paused(harness.sell(launch, trader, 1_000_000).await),
+        !paused(harness.sell_exact_out(launch, trader, 1_000, u64::MAX).await),
+    ]
+}
+
+fn pause(paused: bool, exit_when_paused: bool) -> AdminArgs {
+    AdminArgs { paused: Some(paused), exit_when_paused: Some(exit_when_paused), ..AdminArgs::default() }
+}
+
+#[tokio::test]
+async fn global_pause_stops_buys_and_leaves_the_exit_to_the_config() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let (launch, trader) = trading(&mut harness).await;
+
+    harness.update_config(pause(true, true)).await.unwrap();
+    assert!(paused(harness.buy(&launch, &trader, 1_000_000).await));
+    assert!(paused(harness.buy_exact_in(&launch, &trader, 1_000_000, 0).await));
+    assert!(paused(harness.buy_partial(&launch, &trader, 1_000_000, u64::MAX, 0).await));
+    assert_eq!(curve_exits(&mut harness, &launch, &trader).await, [true, true]);
+
+    harness.update_config(pause(true, false)).await.unwrap();
+    assert_eq!(curve_exits(&mut harness, &launch, &trader).await, [false, false]);
+
+    harness.update_config(pause(false, false)).await.unwrap();
+    harness.buy(&launch, &trader, 1_000_000).await.unwrap();
+    assert_eq!(curve_exits(&mut harness, &launch, &trader).await, [true, true]);
+}
+
+#[tokio::test]
+async fn launch_pause_stops_only_that_launch() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let (launch, trader) = trading(&mut harness).await;
+    let (other, other_trader) = trading(&mut harness).await;
+
+    harness.set_launch_pause(&launch, true, true).await.unwrap();
+    assert!(paused(harness.buy(&launch, &trader, 1_000_000).await));
+    assert!(paused(harness.buy_exact_in(&launch, &trader, 1_000_000, 0).await));
+    assert_eq!(curve_exits(&mut harness, &launch, &trader).await, [true, true]);
+    harness.buy(&other, &other_trader, 1_000_000).await.unwrap();
+
+    harness.set_launch_pause(&launch, true, false).await.unwrap();
+    assert_eq!(curve_exits(&mut harness, &launch, &trader).await, [false, false]);
+    assert_eq!(curve_exits(&mut harness, &other, &other_trader).await, [true, true]);
+
+    // A global pause with the exit open doesn't reopen a launch that closed it
+    harness.set_launch_pause(&launch, false, false).await.unwrap();
+    harness.update_config(pause(true, true)).await.unwrap();
+    harness.set_launch_pause(&launch, true, false).await.unwrap();
+    assert_eq!(curve_exits(&mut harness, &launch, &trader).await, [false, false]);
+
+    harness.update_config(pause(false, true)).await.unwrap();
+    harness.set_launch_pause(&launch, false, false).await.unwrap();
+    harness.buy(&launch, &trader, 1_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn pool_swaps_follow_the_same_pauses() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let (launch, trader) = graduated(&mut harness).await;
+
+    harness.update_config(pause(true, true)).await.unwrap();
+    assert!(paused(harness.swap_sol_for_tokens(&launch, &trader, 1_000_000).await));
+    harness.swap_tokens_for_sol(&launch, &trader, 1_000_000).await.unwrap();
+    harness.update_config(pause(true, false)).await.unwrap();
+    assert!(paused(harness.swap_tokens_for_sol(&launch, &trader, 1_000_000).await));
+
+    harness.update_config(pause(false, false)).await.unwrap();
+    harness.set_launch_pause(&launch, true, true).await.unwrap();
+    assert!(paused(harness.swap_sol_for_tokens(&launch, &trader, 1_000_000).await));
+    harness.swap_tokens_for_sol(&launch, &trader, 1_000_000).await.unwrap();
+    harness.set_launch_pause(&launch, true, false).await.unwrap();
+    assert!(paused(harness.swap_tokens_for_sol(&launch, &trader, 1_000_000).await));
+
+    harness.set_launch_pause(&launch, false, false).await.unwrap();
+    harness.swap_sol_for_tokens(&launch, &trader, 1_000_000).await.unwrap();
+    harness.swap_tokens_for_sol(&launch, &trader, 1_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn role_changes_and_migration_wait_out_a_pause() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let admin = harness.admin.insecure_clone();
+    let successor = harness.trader().await;
+    let (mint, _) = harness.external_mint(1).await;
+    let stale = Launch::new(mint);
+    harness.ctx.set_account(&stale.state_pda, &program_account(V0_FIXTURE.to_vec()));
+
+    let propose = harness.propose_creator_ix(&launch, &admin.pubkey(), successor.pubkey());
+    let accept = harness.accept_creator_ix(&launch, &successor.pubkey());
+    let delegate = harness.set_fee_delegate_ix(&launch, &admin.pubkey(), FeeKind::Platform, Pubkey::new_unique());
+    let migrate = harness.migrate_launch_ix(&stale);
+
+    harness.update_config(pause(true, true)).await.unwrap();
+    assert!(paused(harness.send(std::slice::from_ref(&propose), &[&admin]).await));
+    assert!(paused(harness.send(std::slice::from_ref(&delegate), &[&admin]).await));
+    assert!(paused(harness.send(std::slice::from_ref(&migrate), &[]).await));
+    harness.update_config(pause(false, true)).await.unwrap();
+    harness.send(&[propose], &[&admin]).await.unwrap();
+
+    harness.set_launch_pause(&launch, true, true).await.unwrap();
+    assert!(paused(harness.send(std::slice::from_ref(&accept), &[&successor]).await));
+    assert!(paused(harness.send(std::slice::from_ref(&delegate), &[&admin]).await));
+    harness.set_launch_pause(&launch, false, true).await.unwrap();
+
+    harness.send(&[delegate], &[&admin]).await.unwrap();
+    harness.send(&[accept], &[&successor]).await.unwrap();
+    harness.send(&[migrate], &[]).await.unwrap();
+    assert_eq!(harness.state(&launch).await.creator, successor.pubkey());
+}
+
+#[cfg(feature = "event-cpi")]
+#[tokio::test]
+async fn pause_switches_emit_events() {
+    use grokpad::events::GrokEvent;
+
+    let mut harness = Harness::start(u64::MAX).await;
+    let (launch, _) = trading(&mut harness).await;
+    let admin = harness.admin.insecure_clone();
+
+    let ix = harness.update_config_ix(pause(true, false));
+    let events = harness.send_for_events(std::slice::from_ref(&ix), &[&admin]).await.unwrap();
+    let [GrokEvent::PauseEvent(event)] = &events[..] else { panic!("expected one pause event") };
+    assert_eq!((event.mint, event.paused, event.exit_when_paused), (None, true, false));
+    // Setting the switch to what it already is changes nothing
+    let events = harness.send_for_events(&[ix], &[&admin]).await.unwrap();
+    assert!(events.is_empty());
+
+    let ix = harness.set_launch_pause_ix(&launch, true, true);
+    let events = harness.send_for_events(&[ix], &[&admin]).await.unwrap();
+    let [GrokEvent::PauseEvent(event)] = &events[..] else { panic!("expected one pause event") };
+    assert_eq!((event.mint, event.paused, event.exit_when_paused), (Some(launch.mint), true, true));
+}
@@ -0,0 +1,234 @@
+//! Fee and creator roles through the program: the two-step creator
+//! handover, fee delegates, and moving launches onto a new treasury with
+//! `rotate_platform_recipient`.
+
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use common::*;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::instructions::AdminArgs;
+use grokpad::{FeeKind, GrokError};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const V0_FIXTURE: &[u8] = include_bytes!("fixtures/launch_state_v0.bin");
+const V1_FIXTURE: &[u8] = include_bytes!("fixtures/launch_state_v1.bin");
+
+fn invalid_owner(result: Result<(), solana_program_test::BanksClientError>) -> bool {
+    error_code(result) == Some(GrokError::InvalidOwner.into())
+}
+
+/// A launch with fees of both kinds accrued.
+async fn traded(harness: &mut Harness) -> Launch {
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    harness.buy(&launch, &trader, 10_000_000_000).await.unwrap();
+    launch
+}
+
+/// `claim_*_fees` of all of `kind`, signed by `claimer` and paid to it.
+fn claim_ix(harness: &Harness, launch: &Launch, claimer: &Pubkey, kind: FeeKind) -> Instruction {
+    let mut ix = harness.claim_fees_ix(launch, kind, None);
+    ix.accounts = accounts!(ClaimFees {
+        claimer: *claimer,
+        mint: launch.mint,
+        global_config: harness.global_config,
+        state_pda: launch.state_pda,
+        vault_sol_pda: launch.vault_sol_pda,
+        destination: *claimer,
+        system_program: anchor_lang::system_program::ID,
+    });
+    ix
+}
+
+/// `rotate_platform_recipient` over `states`, signed by `admin`.
+fn rotate_ix(harness: &Harness, admin: &Pubkey, states: &[Pubkey]) -> Instruction {
+    let mut accounts = accounts!(RotatePlatformRecipient { global_config: harness.global_config, admin: *admin });
+    accounts.extend(states.iter().map(|state| AccountMeta::new(*state, false)));
+    Instruction { program_id: grokpad::ID, accounts, data: grokpad::instruction::RotatePlatformRecipient {}.data() }
+}
+
+#[tokio::test]
+async fn creator_hands_over_in_two_steps() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let admin = harness.admin.insecure_clone();
+    let first = harness.trader().await;
+    let second = harness.trader().await;
+
+    // Only the creator proposes, and nobody accepts before a proposal
+    let propose = harness.propose_creator_ix(&launch, &first.pubkey(), first.pubkey());
+    assert!(invalid_owner(harness.send(&[propose], &[&first]).await));
+    let accept = harness.accept_creator_ix(&launch, &first.pubkey());
+    assert!(invalid_owner(harness.send(&[accept], &[&first]).await));
+
+    // Proposing again replaces the offer
+    let propose = harness.propose_creator_ix(&launch, &admin.pubkey(), first.pubkey());
+    harness.send(&[propose], &[&admin]).await.unwrap();
+    let propose = harness.propose_creator_ix(&launch, &admin.pubkey(), second.pubkey());
+    harness.send(&[propose], &[&admin]).await.unwrap();
+    assert_eq!(harness.state(&launch).await.pending_creator, second.pubkey());
+    let accept = harness.accept_creator_ix(&launch, &first.pubkey());
+    assert!(invalid_owner(harness.send(&[accept], &[&first]).await));
+
+    let delegate = harness.set_fee_delegate_ix(&launch, &admin.pubkey(), FeeKind::Creator, first.pubkey());
+    harness.send(&[delegate], &[&admin]).await.unwrap();
+    let accrued = harness.state(&launch).await.creator_fee_accrued;
+    let accept = harness.accept_creator_ix(&launch, &second.pubkey());
+    harness.send(&[accept], &[&second]).await.unwrap();
+    let state = harness.state(&launch).await;
+    assert_eq!(state.creator, second.pubkey());
+    assert_eq!((state.pending_creator, state.creator_fee_delegate), (Pubkey::default(), Pubkey::default()));
+
+    // Unclaimed creator fees moved with the role; the old creator and its
+    // delegate lost them
+    for old in [&admin, &first] {
+        let claim = claim_ix(&harness, &launch, &old.pubkey(), FeeKind::Creator);
+        assert!(invalid_owner(harness.send(&[claim], &[old]).await));
+    }
+    let before = harness.lamports(&second.pubkey()).await;
+    let claim = claim_ix(&harness, &launch, &second.pubkey(), FeeKind::Creator);
+    harness.send(&[claim], &[&second]).await.unwrap();
+    assert_eq!(harness.lamports(&second.pubkey()).await - before, accrued);
+}
+
+#[tokio::test]
+async fn proposal_can_be_withdrawn() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let admin = harness.admin.insecure_clone();
+    let successor = harness.trader().await;
+
+    let propose = harness.propose_creator_ix(&launch, &admin.pubkey(), successor.pubkey());
+    harness.send(&[propose], &[&admin]).await.unwrap();
+    let withdraw = harness.propose_creator_ix(&launch, &admin.pubkey(), Pubkey::default());
+    harness.send(&[withdraw], &[&admin]).await.unwrap();
+    let accept = harness.accept_creator_ix(&launch, &successor.pubkey());
+    assert!(invalid_owner(harness.send(&[accept], &[&successor]).await));
+    assert_eq!(harness.state(&launch).await.creator, admin.pubkey());
+}
+
+#[tokio::test]
+async fn fee_delegates_claim_alongside_their_recipient() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let admin = harness.admin.insecure_clone();
+    let delegate = harness.trader().await;
+
+    // Only the recipient names a delegate
+    let set = harness.set_fee_delegate_ix(&launch, &delegate.pubkey(), FeeKind::Platform, delegate.pubkey());
+    assert!(invalid_owner(harness.send(&[set], &[&delegate]).await));
+
+    let set = harness.set_fee_delegate_ix(&launch, &admin.pubkey(), FeeKind::Platform, delegate.pubkey());
+    harness.send(&[set], &[&admin]).await.unwrap();
+    assert_eq!(harness.state(&launch).await.platform_fee_delegate, delegate.pubkey());
+    // A platform delegate can't claim creator fees
+    let claim = claim_ix(&harness, &launch, &delegate.pubkey(), FeeKind::Creator);
+    assert!(invalid_owner(harness.send(&[claim], &[&delegate]).await));
+    let accrued = harness.state(&launch).await.platform_fee_accrued;
+    let before = harness.lamports(&delegate.pubkey()).await;
+    let claim = claim_ix(&harness, &launch, &delegate.pubkey(), FeeKind::Platform);
+    harness.send(&[claim], &[&delegate]).await.unwrap();
+    assert_eq!(harness.lamports(&delegate.pubkey()).await - before, accrued);
+
+    let remove = harness.set_fee_delegate_ix(&launch, &admin.pubkey(), FeeKind::Platform, Pubkey::default());
+    harness.send(&[remove], &[&admin]).await.unwrap();
+    let trader = harness.trader().await;
+    harness.buy(&launch, &trader, 10_000_000_000).await.unwrap();
+    let claim = claim_ix(&harness, &launch, &delegate.pubkey(), FeeKind::Platform);
+    assert!(invalid_owner(harness.send(&[claim], &[&delegate]).await));
+}
+
+#[tokio::test]
+async fn rotation_moves_launches_onto_the_treasury() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let other = traded(&mut harness).await;
+    let admin = harness.admin.insecure_clone();
+    let delegate = Pubkey::new_unique();
+    let set = harness.set_fee_delegate_ix(&launch, &admin.pubkey(), FeeKind::Platform, delegate);
+    harness.send(&[set], &[&admin]).await.unwrap();
+
+    let treasury = Keypair::new();
+    harness.update_config(AdminArgs { treasury: Some(treasury.pubkey()), ..AdminArgs::default() }).await.unwrap();
+    let stranger = harness.trader().await;
+    let rotate = rotate_ix(&harness, &stranger.pubkey(), &[launch.state_pda]);
+    assert!(invalid_owner(harness.send(&[rotate], &[&stranger]).await));
+    // The states have to be passed writable
+    let mut rotate = rotate_ix(&harness, &admin.pubkey(), &[launch.state_pda]);
+    rotate.accounts.last_mut().unwrap().is_writable = false;
+    let result = harness.send(&[rotate], &[&admin]).await;
+    assert_eq!(error_code(result), Some(GrokError::InvalidParam.into()));
+
+    let rotate = rotate_ix(&harness, &admin.pubkey(), &[launch.state_pda, other.state_pda]);
+    harness.send(&[rotate], &[&admin]).await.unwrap();
+    for rotated in [&launch, &other] {
+        let state = harness.state(rotated).await;
+        assert_eq!((state.platform_fee_recipient, state.platform_fee_delegate), (treasury.pubkey(), Pubkey::default()));
+    }
+
+    // The accrued platform fees now belong to the treasury
+    let claim = claim_ix(&harness, &launch, &admin.pubkey(), FeeKind::Platform);
+    assert!(invalid_owner(harness.send(&[claim], &[&admin]).await));
+    harness.airdrop(&treasury.pubkey(), 1_000_000_000).await;
+    let claim = claim_ix(&harness, &launch, &treasury.pubkey(), FeeKind::Platform);
+    harness.send(&[claim], &[&treasury]).await.unwrap();
+}
+
+#[tokio::test]
+async fn rotation_rejects_unmigrated_layouts() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let admin = harness.admin.insecure_clone();
+    for fixture in [V0_FIXTURE, V1_FIXTURE] {
+        let (mint, _) = harness.external_mint(1).await;
+        let stale = Launch::new(mint);
+        harness.ctx.set_account(&stale.state_pda, &program_account(fixture.to_vec()));
+        let rotate = rotate_ix(&harness, &admin.pubkey(), &[stale.state_pda]);
+        let result = harness.send(std::slice::from_ref(&rotate), &[&admin]).await;
+        assert_eq!(error_code(result), Some(GrokError::NotMigrated.into()));
+
+        // Once migrated it rotates like any launch
+        harness.send(&[harness.migrate_launch_ix(&stale)], &[]).await.unwrap();
+        harness.send(&[rotate], &[&admin]).await.unwrap();
+        assert_eq!(harness.state(&stale).await.platform_fee_recipient, admin.pubkey());
+    }
+}
+
+#[cfg(feature = "event-cpi")]
+#[tokio::test]
+async fn role_changes_emit_events() {
+    use grokpad::events::GrokEvent;
+
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let admin = harness.admin.insecure_clone();
+    let successor = harness.trader().await;
+    let delegate = Pubkey::new_unique();
+
+    let propose = harness.propose_creator_ix(&launch, &admin.pubkey(), successor.pubkey());
+    let events = harness.send_for_events(&[propose], &[&admin]).await.unwrap();
+    let [GrokEvent::CreatorProposedEvent(event)] = &events[..] else { panic!("expected one proposal event") };
+    assert_eq!((event.mint, event.creator, event.pending_creator), (launch.mint, admin.pubkey(), successor.pubkey()));
+
+    let accept = harness.accept_creator_ix(&launch, &successor.pubkey());
+    let events = harness.send_for_events(&[accept], &[&successor]).await.unwrap();
+    let [GrokEvent::CreatorTransferEvent(event)] = &events[..] else { panic!("expected one transfer event") };
+    assert_eq!((event.mint, event.previous, event.creator), (launch.mint, admin.pubkey(), successor.pubkey()));
+
+    let set = harness.set_fee_delegate_ix(&launch, &successor.pubkey(), FeeKind::Creator, delegate);
+    let events = harness.send_for_events(&[set], &[&successor]).await.unwrap();
+    let [GrokEvent::FeeDelegateEvent(event)] = &events[..] else { panic!("expected one delegate event") };
+    assert_eq!((event.mint, event.kind, event.delegate), (launch.mint, FeeKind::Creator, delegate));
+
+    let treasury = Pubkey::new_unique();
+    harness.update_config(AdminArgs { treasury: Some(treasury), ..AdminArgs::default() }).await.unwrap();
+    let rotate = rotate_ix(&harness, &admin.pubkey(), &[launch.state_pda]);
+    let events = harness.send_for_events(std::slice::from_ref(&rotate), &[&admin]).await.unwrap();
+    let [GrokEvent::PlatformRecipientEvent(event)] = &events[..] else { panic!("expected one rotation event") };
+    assert_eq!((event.mint, event.previous, event.recipient), (launch.mint, admin.pubkey(), treasury));
+    // Launches already on the treasury are skipped quietly
+    assert!(harness.send_for_events(&[rotate], &[&admin]).await.unwrap().is_empty());
+}
@@ -0,0 +1,207 @@
+//! Curve trades through the program: buys into new and existing token
+//! accounts, exact-SOL-in buys and exact-SOL-out sells, partial fills at
+//! the end of the supply, and a sold-out launch graduating into its pool.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::InstructionData;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use common::*;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::{GrokError, POOL_ALLOCATION_BPS};
+use solana_signer::Signer;
+
+/// Tokens left on the curve when the partial fills start.
+const LEFT: u64 = 1_000;
+
+/// A launch that only graduates by selling out, with all but `LEFT` of the
+/// curve's supply bought.
+async fn nearly_sold_out(harness: &mut Harness) -> Launch {
+    // The pool allocation is never sold, so past 80% is out of reach
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_001 }])).await;
+    assert_eq!(error_code(launch.map(|_| ())), Some(GrokError::InvalidParam.into()));
+
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let whale = harness.trader().await;
+    let supply = harness.state(&launch).await.supply_remaining;
+    harness.buy(&launch, &whale, supply - LEFT).await.unwrap();
+    assert!(!harness.state(&launch).await.is_graduated());
+    launch
+}
+
+#[tokio::test]
+async fn buy_partial_fills_the_rest_of_the_supply() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = nearly_sold_out(&mut harness).await;
+    let trader = harness.trader().await;
+    let trader_ata = get_associated_token_address(&trader.pubkey(), &launch.mint);
+    let before = harness.state(&launch).await;
+    let quote = grokpad_math::quote_buy(&before.curve(), LEFT).unwrap();
+
+    // Slippage is checked against the filled amount, not the requested one
+    let result = harness.buy_partial(&launch, &trader, 5 * LEFT, u64::MAX, LEFT + 1).await;
+    assert_eq!(error_code(result), Some(GrokError::SlippageExceeded.into()));
+    let result = harness.buy_partial(&launch, &trader, 5 * LEFT, quote.cost - 1, LEFT).await;
+    assert_eq!(error_code(result), Some(GrokError::SlippageExceeded.into()));
+
+    let vault_before = harness.lamports(&launch.vault_sol_pda).await;
+    let trader_before = harness.lamports(&trader.pubkey()).await;
+    harness.buy_partial(&launch, &trader, 5 * LEFT, quote.cost, LEFT).await.unwrap();
+    assert_eq!(harness.token_balance(&trader_ata).await, LEFT);
+    assert_eq!(harness.lamports(&launch.vault_sol_pda).await - vault_before, quote.cost);
+    let ata_rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(spl_token::state::Account::LEN);
+    assert_eq!(trader_before - harness.lamports(&trader.pubkey()).await, quote.cost + ata_rent);
+
+    let state = harness.state(&launch).await;
+    assert_eq!(state.supply_remaining, 0);
+    assert_eq!(state.tokens_sold, before.tokens_sold + LEFT);
+    assert_eq!(state.reserves_lamports, quote.after.reserves_lamports);
+    assert!(state.is_graduated());
+
+    let result = harness.buy_partial(&launch, &trader, LEFT, u64::MAX, 0).await;
+    assert_eq!(error_code(result), Some(GrokError::LaunchGraduated.into()));
+}
+
+#[tokio::test]
+async fn sold_out_launch_graduates_into_its_pool() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = nearly_sold_out(&mut harness).await;
+    let trader = harness.trader().await;
+    harness.buy_partial(&launch, &trader, LEFT, u64::MAX, LEFT).await.unwrap();
+    let sold_out = harness.state(&launch).await;
+    let allocation = TOTAL_SUPPLY * POOL_ALLOCATION_BPS as u64 / 10_000;
+    assert_eq!(sold_out.pool_allocation, allocation);
+    assert_eq!(harness.token_balance(&launch.vault_ata).await, allocation);
+
+    harness.graduate(&launch).await.unwrap();
+    let pool = harness.pool(&launch).await;
+    assert_eq!((pool.sol_reserves, pool.token_reserves), (sold_out.reserves_lamports, allocation));
+    let state = harness.state(&launch).await;
+    assert_eq!((state.reserves_lamports, state.supply_remaining, state.pool_allocation), (0, 0, 0));
+    assert_eq!(state.pool, launch.pool_pda);
+
+    // Trading carries on in the pool
+    let swapper = harness.trader().await;
+    harness.swap_sol_for_tokens(&launch, &swapper, 1_000_000_000).await.unwrap();
+    let bought = harness.token_balance(&get_associated_token_address(&swapper.pubkey(), &launch.mint)).await;
+    assert!(bought > 0);
+    assert_eq!(harness.pool(&launch).await.token_reserves, allocation - bought);
+    assert_eq!(harness.token_balance(&launch.vault_ata).await, allocation - bought);
+}
+
+#[tokio::test]
+async fn buy_partial_needs_a_nonzero_amount() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    let result = harness.buy_partial(&launch, &trader, 0, u64::MAX, 0).await;
+    assert_eq!(error_code(result), Some(GrokError::ZeroAmount.into()));
+}
+
+#[tokio::test]
+async fn second_buy_tops_up_the_existing_token_account() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    let trader_ata = get_associated_token_address(&trader.pubkey(), &launch.mint);
+    harness.buy(&launch, &trader, 1_000_000).await.unwrap();
+    let ata_rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(spl_token::state::Account::LEN);
+    assert_eq!(harness.lamports(&trader_ata).await, ata_rent);
+
+    let quote = grokpad_math::quote_buy(&harness.state(&launch).await.curve(), 2_000_000).unwrap();
+    let trader_before = harness.lamports(&trader.pubkey()).await;
+    harness.buy(&launch, &trader, 2_000_000).await.unwrap();
+    // The token account already exists, so only the trade is paid for
+    assert_eq!(trader_before - harness.lamports(&trader.pubkey()).await, quote.cost);
+    assert_eq!(harness.token_balance(&trader_ata).await, 3_000_000);
+    assert_eq!(harness.lamports(&trader_ata).await, ata_rent);
+}
+
+#[tokio::test]
+async fn buy_sends_tokens_to_another_recipient() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let buyer = harness.trader().await;
+    let recipient = Pubkey::new_unique();
+    let quote = grokpad_math::quote_buy(&harness.state(&launch).await.curve(), 1_000_000).unwrap();
+    let ix = Instruction {
+        program_id: grokpad::ID,
+        accounts: harness.buy_accounts_for(&launch, &buyer.pubkey(), &recipient),
+        data: grokpad::instruction::Buy { amount: 1_000_000, max_cost_lamports: quote.cost }.data(),
+    };
+    let buyer_before = harness.lamports(&buyer.pubkey()).await;
+    harness.send(&[ix], &[&buyer]).await.unwrap();
+
+    // The buyer pays for the trade and the recipient's new token account
+    let ata_rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(spl_token::state::Account::LEN);
+    assert_eq!(buyer_before - harness.lamports(&buyer.pubkey()).await, quote.cost + ata_rent);
+    assert_eq!(harness.token_balance(&get_associated_token_address(&recipient, &launch.mint)).await, 1_000_000);
+    assert!(harness.account(&get_associated_token_address(&buyer.pubkey(), &launch.mint)).await.is_none());
+    assert_eq!(harness.lamports(&recipient).await, 0);
+}
+
+#[tokio::test]
+async fn buy_exact_in_takes_only_the_filled_cost() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = nearly_sold_out(&mut harness).await;
+    let trader = harness.trader().await;
+    let trader_ata = get_associated_token_address(&trader.pubkey(), &launch.mint);
+    // Far more than the rest of the supply costs
+    let lamports_in = 1_000_000_000;
+    let quote = grokpad_math::quote_buy_exact_in(&harness.state(&launch).await.curve(), lamports_in).unwrap();
+    assert_eq!(quote.amount, LEFT);
+    assert!(quote.cost < lamports_in);
+
+    let result = harness.buy_exact_in(&launch, &trader, lamports_in, LEFT + 1).await;
+    assert_eq!(error_code(result), Some(GrokError::SlippageExceeded.into()));
+
+    let vault_before = harness.lamports(&launch.vault_sol_pda).await;
+    let trader_before = harness.lamports(&trader.pubkey()).await;
+    harness.buy_exact_in(&launch, &trader, lamports_in, LEFT).await.unwrap();
+    assert_eq!(harness.token_balance(&trader_ata).await, LEFT);
+    assert_eq!(harness.lamports(&launch.vault_sol_pda).await - vault_before, quote.cost);
+    // The rest of `lamports_in` never leaves the buyer, who pays only the
+    // filled cost and the new token account
+    let ata_rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(spl_token::state::Account::LEN);
+    assert_eq!(trader_before - harness.lamports(&trader.pubkey()).await, quote.cost + ata_rent);
+    assert_eq!(harness.state(&launch).await.reserves_lamports, quote.after.reserves_lamports);
+}
+
+#[tokio::test]
+async fn sell_exact_out_pays_at_least_the_requested_lamports() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    let trader_ata = get_associated_token_address(&trader.pubkey(), &launch.mint);
+    harness.buy(&launch, &trader, 100_000_000_000).await.unwrap();
+    let held = harness.token_balance(&trader_ata).await;
+    let lamports_out = 1_000_000_000;
+    let quote = grokpad_math::quote_sell_exact_out(&harness.state(&launch).await.curve(), lamports_out).unwrap();
+
+    let result = harness.sell_exact_out(&launch, &trader, lamports_out, quote.amount - 1).await;
+    assert_eq!(error_code(result), Some(GrokError::SlippageExceeded.into()));
+
+    let vault_before = harness.lamports(&launch.vault_sol_pda).await;
+    let trader_before = harness.lamports(&trader.pubkey()).await;
+    harness.sell_exact_out(&launch, &trader, lamports_out, quote.amount).await.unwrap();
+    assert!(quote.refund_net >= lamports_out);
+    assert_eq!(harness.lamports(&trader.pubkey()).await - trader_before, quote.refund_net);
+    assert_eq!(vault_before - harness.lamports(&launch.vault_sol_pda).await, quote.refund_net);
+    assert_eq!(harness.token_balance(&trader_ata).await, held - quote.amount);
+    assert_eq!(harness.state(&launch).await.reserves_lamports, quote.after.reserves_lamports);
+}
+
+#[tokio::test]
+async fn sell_exact_out_cannot_take_more_than_the_reserves() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    harness.buy(&launch, &trader, 100_000_000_000).await.unwrap();
+    let reserves = harness.state(&launch).await.reserves_lamports;
+    let result = harness.sell_exact_out(&launch, &trader, reserves + 1, u64::MAX).await;
+    assert_eq!(error_code(result), Some(GrokError::InsufficientReserves.into()));
+}
@@ -0,0 +1,114 @@
+//! `verify_launch` through the program: the `SolvencyReport` it returns for
+//! balanced, short and over-funded vaults, the event a mismatch emits in
+//! `event-cpi` builds, and launches whose liquidity sits in the pool.
+
+mod common;
+
+use common::*;
+use grokpad::graduation::GraduationCriterion;
+use grokpad::{GrokError, SolvencyReport};
+
+/// A launch on the curve with a trade and its fees in the vaults.
+async fn traded(harness: &mut Harness) -> Launch {
+    let launch = harness.launch_with_mint(Some(vec![GraduationCriterion::PercentSold { bps: 8_000 }])).await.unwrap();
+    let trader = harness.trader().await;
+    harness.buy(&launch, &trader, 10_000_000_000).await.unwrap();
+    launch
+}
+
+/// The report a launch's state says `verify_launch` should return when
+/// the vaults hold exactly what they owe.
+async fn balanced(harness: &mut Harness, launch: &Launch, pool: (u64, u64)) -> SolvencyReport {
+    let state = harness.state(launch).await;
+    let rent = harness.ctx.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    let expected_lamports = state.reserves_lamports + state.platform_fee_accrued + state.creator_fee_accrued + pool.0 + rent;
+    let expected_tokens = state.vault_tokens().unwrap() + pool.1;
+    SolvencyReport {
+        mint: launch.mint,
+        expected_lamports,
+        actual_lamports: expected_lamports,
+        expected_tokens,
+        actual_tokens: expected_tokens,
+    }
+}
+
+#[tokio::test]
+async fn balanced_launch_reports_solvent() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let report = harness.verify_launch(&launch, false).await.unwrap();
+    assert_eq!(report, balanced(&mut harness, &launch, (0, 0)).await);
+    assert!(report.is_solvent());
+}
+
+#[tokio::test]
+async fn lamport_mismatches_are_reported() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let expected = balanced(&mut harness, &launch, (0, 0)).await;
+
+    harness.set_lamports(&launch.vault_sol_pda, expected.expected_lamports - 1).await;
+    let report = harness.verify_launch(&launch, false).await.unwrap();
+    assert_eq!(report, SolvencyReport { actual_lamports: expected.expected_lamports - 1, ..expected });
+    assert!(!report.is_solvent());
+
+    // A donation is a mismatch too, but not a shortfall
+    harness.set_lamports(&launch.vault_sol_pda, expected.expected_lamports + 1_000).await;
+    let report = harness.verify_launch(&launch, false).await.unwrap();
+    assert_eq!(report, SolvencyReport { actual_lamports: expected.expected_lamports + 1_000, ..expected });
+    assert!(report.is_solvent());
+}
+
+#[tokio::test]
+async fn token_mismatches_are_reported() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let expected = balanced(&mut harness, &launch, (0, 0)).await;
+
+    harness.set_token_balance(&launch.vault_ata, expected.expected_tokens - 1).await;
+    let report = harness.verify_launch(&launch, false).await.unwrap();
+    assert_eq!(report, SolvencyReport { actual_tokens: expected.expected_tokens - 1, ..expected });
+    assert!(!report.is_solvent());
+}
+
+#[tokio::test]
+async fn graduated_launch_counts_its_pool() {
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let trader = harness.trader().await;
+    let supply = harness.state(&launch).await.supply_remaining;
+    harness.buy(&launch, &trader, supply).await.unwrap();
+    harness.graduate(&launch).await.unwrap();
+    harness.swap_sol_for_tokens(&launch, &trader, 1_000_000_000).await.unwrap();
+
+    // Leaving the pool out would read its liquidity as surplus
+    let result = harness.verify_launch(&launch, false).await.map(|_| ());
+    assert_eq!(error_code(result), Some(GrokError::MissingPool.into()));
+
+    let pool = harness.pool(&launch).await;
+    let report = harness.verify_launch(&launch, true).await.unwrap();
+    assert_eq!(report, balanced(&mut harness, &launch, (pool.sol_reserves, pool.token_reserves)).await);
+    assert!(pool.sol_reserves > 0 && pool.token_reserves > 0);
+}
+
+#[cfg(feature = "event-cpi")]
+#[tokio::test]
+async fn mismatches_emit_an_event() {
+    use grokpad::events::GrokEvent;
+
+    let mut harness = Harness::start(u64::MAX).await;
+    let launch = traded(&mut harness).await;
+    let expected = balanced(&mut harness, &launch, (0, 0)).await;
+
+    let ix = harness.verify_launch_ix(&launch, false);
+    assert!(harness.send_for_events(std::slice::from_ref(&ix), &[]).await.unwrap().is_empty());
+
+    harness.set_lamports(&launch.vault_sol_pda, expected.expected_lamports - 1).await;
+    harness.set_token_balance(&launch.vault_ata, expected.expected_tokens + 1).await;
+    let events = harness.send_for_events(&[ix], &[]).await.unwrap();
+    let [GrokEvent::SolvencyMismatchEvent(event)] = &events[..] else { panic!("expected one mismatch event") };
+    assert_eq!(event.mint, launch.mint);
+    assert_eq!((event.expected_lamports, event.actual_lamports), (expected.expected_lamports, expected.expected_lamports - 1));
+    assert_eq!((event.expected_tokens, event.actual_tokens), (expected.expected_tokens, expected.expected_tokens + 1));
+    assert!(!event.solvent);
+}
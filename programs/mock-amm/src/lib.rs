@@ -0,0 +1,137 @@
+//! Stand-in for an external AMM. `create_pool` takes SOL and tokens from two
+//! signing sources, parks them under a pool PDA and mints LP tokens 1:1 with
+//! the deposited lamports. There is no swapping; it only exists so grokpad's
+//! migration path can run end to end on a local validator.
+
+// `#[program]` generates Anchor's IDL instructions at the crate root, out of
+// reach of any narrower attribute, and they call the deprecated
+// `AccountInfo::realloc`; the crate's own code uses no deprecated API.
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+declare_id!("CdF9nzaFUboYDYqyG8CYBzcmrCtYQuzbqhoec3jtFE7H");
+
+pub const POOL_SEED: &[u8] = b"pool";
+pub const LP_MINT_SEED: &[u8] = b"lp_mint";
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn create_pool(ctx: Context<CreatePool>, sol_amount: u64, token_amount: u64) -> Result<()> {
+        require!(sol_amount > 0 && token_amount > 0, MockAmmError::EmptyDeposit);
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.sol_source.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            sol_amount,
+        )?;
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_source.to_account_info(),
+                    to: ctx.accounts.pool_token_vault.to_account_info(),
+                    authority: ctx.accounts.token_authority.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+        let mint_key = ctx.accounts.mint.key();
+        let pool_seeds: &[&[u8]] = &[POOL_SEED, mint_key.as_ref(), &[ctx.bumps.pool]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_destination.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            sol_amount,
+        )?;
+        let pool = &mut ctx.accounts.pool;
+        pool.mint = mint_key;
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.sol_reserves = sol_amount;
+        pool.token_reserves = token_amount;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct AmmPool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub bump: u8,
+}
+impl AmmPool {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub sol_source: Signer<'info>,
+    pub token_authority: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut, token::mint = mint, token::authority = token_authority)]
+    pub token_source: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = AmmPool::LEN,
+        seeds = [POOL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [LP_MINT_SEED, pool.key().as_ref()],
+        bump,
+        mint::decimals = 9,
+        mint::authority = pool,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    /// CHECK: owner of the LP tokens
+    pub lp_owner: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_owner
+    )]
+    pub lp_destination: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Both sides of the deposit must be non-zero")] EmptyDeposit,
+}
@@ -2,6 +2,7 @@ const anchor = require('@coral-xyz/anchor');
 const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
 const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } = require('@solana/spl-token');
 const { PROGRAM_ID } = require('./config');
+const { quoteBuy, curveState } = require('./grokpad-math');
 
 // Get the mint address from command line
 const mintAddress = process.argv[2];
@@ -10,9 +11,9 @@ if (!mintAddress) {
   process.exit(1);
 }
 
-// Amount to buy (in token units)
-const amountToBuy = new anchor.BN(5_000_000); // 5 tokens (assuming 6 decimals)
-const maxCostLamports = new anchor.BN(10_000_000_000); // 10 SOL (max willing to pay)
+// Whole tokens to buy, and the slippage allowed over the quoted cost
+const TOKENS_TO_BUY = 5n;
+const SLIPPAGE_BPS = 100n;
 
 // Set up the provider
 const provider = anchor.AnchorProvider.env();
@@ -50,14 +51,25 @@ async function main() {
       owner: authorityPda
     });
     
+    const [globalConfig] = PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+    
     // Get buyer ATA
     const buyerAta = await anchor.utils.token.associatedAddress({
       mint: mint,
       owner: provider.wallet.publicKey
     });
     
+    // Quote with the program's math; the cost includes both fees
+    const curve = curveState(await program.account.launchState.fetch(statePda));
+    const amount = TOKENS_TO_BUY * 10n ** BigInt(curve.decimals);
+    const quote = quoteBuy(curve, amount);
+    const maxCost = quote.cost + quote.cost * SLIPPAGE_BPS / 10_000n;
+    const amountToBuy = new anchor.BN(amount.toString());
+    const maxCostLamports = new anchor.BN(maxCost.toString());
+    
     console.log("Buying tokens...");
     console.log("Amount:", amountToBuy.toString());
+    console.log("Quoted cost:", quote.cost.toString(), "lamports, of which fees:", (quote.platformFee + quote.creatorFee).toString());
     console.log("Max cost:", maxCostLamports.toString(), "lamports");
     
     const tx = await program.methods
@@ -68,13 +80,13 @@ async function main() {
       .accounts({
         buyer: provider.wallet.publicKey,
         mint: mint,
+        globalConfig: globalConfig,
         statePda: statePda,
         authorityPda: authorityPda,
         vaultSolPda: vaultSolPda,
         vaultAta: vaultAta,
-        buyerAta: buyerAta,
-        platformFeeRecipient: provider.wallet.publicKey,
-        creator: provider.wallet.publicKey,
+        recipient: provider.wallet.publicKey,
+        recipientAta: buyerAta,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
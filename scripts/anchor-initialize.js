@@ -1,6 +1,6 @@
 const anchor = require('@coral-xyz/anchor');
 const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
-const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } = require('@solana/spl-token');
+const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } = require('@solana/spl-token');
 const { PROGRAM_ID } = require('./config');
 
 // Get the mint address from command line
@@ -43,6 +43,8 @@ async function main() {
     );
     console.log('Vault SOL PDA:', vaultSolPda.toString());
     
+    const [globalConfig] = PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+    
     // Get vault ATA
     const vaultAta = await anchor.utils.token.associatedAddress({
       mint: mint,
@@ -50,10 +52,10 @@ async function main() {
     });
     console.log('Vault ATA:', vaultAta.toString());
     
-    // Launch parameters
-    const basePriceLamports = new anchor.BN(1_000_000); // 0.001 SOL base price
-    const slopeLamports = new anchor.BN(100_000); // 0.0001 SOL per token price increase
-    const feeBps = 300; // 3.00% platform fee
+    // Launch parameters. The platform fee and its recipient come from the
+    // global config
+    const basePriceLamports = new anchor.BN(1_000_000); // 0.001 SOL per whole token
+    const slopeLamports = new anchor.BN(100_000); // +0.0001 SOL per whole token sold
     const creatorFeeBps = 100; // 1.00% creator fee
     const graduationTargetLamports = new anchor.BN(2 * 1_000_000_000); // 2 SOL graduation target
     
@@ -62,14 +64,14 @@ async function main() {
       .initializeLaunch(
         basePriceLamports,
         slopeLamports,
-        feeBps,
         creatorFeeBps,
-        graduationTargetLamports
+        [{ reserves: { lamports: graduationTargetLamports } }],
+        false
       )
       .accounts({
         payer: provider.wallet.publicKey,
         creator: provider.wallet.publicKey,
-        platformFeeRecipient: provider.wallet.publicKey,
+        globalConfig: globalConfig,
         mint: mint,
         authorityPda: authorityPda,
         statePda: statePda,
@@ -84,6 +86,28 @@ async function main() {
     
     console.log("Launch initialized! Transaction signature:", tx);
     
+    // Open the curve with the wallet's whole balance of the mint
+    const creatorAta = await anchor.utils.token.associatedAddress({
+      mint: mint,
+      owner: provider.wallet.publicKey
+    });
+    const balance = (await getAccount(provider.connection, creatorAta)).amount;
+    const fundTx = await program.methods
+      .fundCurve(new anchor.BN(balance.toString()))
+      .accounts({
+        creator: provider.wallet.publicKey,
+        mint: mint,
+        globalConfig: globalConfig,
+        statePda: statePda,
+        authorityPda: authorityPda,
+        creatorAta: creatorAta,
+        vaultAta: vaultAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID
+      })
+      .rpc();
+    console.log("Curve funded with", balance.toString(), "base units! Transaction signature:", fundTx);
+    
     // Get the state account
     const state = await program.account.launchState.fetch(statePda);
     console.log("Launch state:", {
@@ -91,6 +115,7 @@ async function main() {
       slopeLamports: state.slopeLamports.toString(),
       feeBps: state.feeBps,
       creatorFeeBps: state.creatorFeeBps,
+      supplyRemaining: state.supplyRemaining.toString(),
       tokensSold: state.tokensSold.toString(),
       reservesLamports: state.reservesLamports.toString()
     });
@@ -2,6 +2,10 @@ const anchor = require('@coral-xyz/anchor');
 const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
 const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } = require('@solana/spl-token');
 const { PROGRAM_ID } = require('./config');
+const { quoteSell, curveState } = require('./grokpad-math');
+
+// Slippage allowed under the quoted payout
+const SLIPPAGE_BPS = 100n;
 
 // Get the mint address from command line
 const mintAddress = process.argv[2];
@@ -40,6 +44,8 @@ async function main() {
       programId
     );
     
+    const [globalConfig] = PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+    
     // Get vault ATA
     const vaultAta = await anchor.utils.token.associatedAddress({
       mint: mint,
@@ -58,16 +64,22 @@ async function main() {
     console.log("Current token balance:", tokenAccount.amount.toString());
     
     // Calculate amount to sell (half of current balance)
-    const sellAmount = new anchor.BN(Math.floor(Number(tokenAccount.amount) / 2));
-    const minPayoutLamports = new anchor.BN(100_000); // 0.0001 SOL minimum payout
-    
-    if (sellAmount.lte(new anchor.BN(0))) {
+    const amount = tokenAccount.amount / 2n;
+    if (amount === 0n) {
       console.log("No tokens to sell");
       return;
     }
     
+    // Quote with the program's math; fees come out of the refund
+    const curve = curveState(await program.account.launchState.fetch(statePda));
+    const quote = quoteSell(curve, amount);
+    const minPayout = quote.refundNet - quote.refundNet * SLIPPAGE_BPS / 10_000n;
+    const sellAmount = new anchor.BN(amount.toString());
+    const minPayoutLamports = new anchor.BN(minPayout.toString());
+    
     console.log("Selling tokens...");
     console.log("Amount to sell:", sellAmount.toString());
+    console.log("Quoted payout:", quote.refundNet.toString(), "lamports, after fees:", (quote.platformFee + quote.creatorFee).toString());
     console.log("Min payout:", minPayoutLamports.toString(), "lamports");
     
     const tx = await program.methods
@@ -78,13 +90,12 @@ async function main() {
       .accounts({
         seller: provider.wallet.publicKey,
         mint: mint,
+        globalConfig: globalConfig,
         statePda: statePda,
         authorityPda: authorityPda,
         vaultSolPda: vaultSolPda,
         vaultAta: vaultAta,
         sellerAta: sellerAta,
-        platformFeeRecipient: provider.wallet.publicKey,
-        creator: provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
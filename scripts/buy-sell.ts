@@ -7,10 +7,12 @@ import {
 import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
   createMint,
   mintTo,
 } from "@solana/spl-token";
+const { quoteBuy, quoteSell, curveState } = require("./grokpad-math");
 
 const ONE = 1_000_000_000n;
 const INITIAL_SUPPLY_TOKENS = 1_000n;
@@ -55,59 +57,82 @@ async function main() {
   console.log("statePda:", statePda.toBase58());
   console.log("vaultSolPda:", vaultSolPda.toBase58());
 
-  // Vault ATA
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  const [globalConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    programId
+  );
+
+  // Vault ATA, created by initialize_launch
+  const vaultAta = getAssociatedTokenAddressSync(mint, authorityPda, true);
+  console.log("vaultAta:", vaultAta.toBase58());
+
+  // Mint initial supply to the creator
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     (wallet as any).payer,
     mint,
-    authorityPda,
-    true
+    wallet.publicKey
   );
-  console.log("vaultAta:", vaultAta.address.toBase58());
-
-  // Mint initial supply
   const initialSupplyAmount = INITIAL_SUPPLY_TOKENS * ONE;
   await mintTo(
     connection,
     (wallet as any).payer,
     mint,
-    vaultAta.address,
+    creatorAta.address,
     (wallet as any).payer,
     initialSupplyAmount
   );
   console.log("Minted initial supply:", INITIAL_SUPPLY_TOKENS.toString());
 
-  // Initialize launch
-  const feeBps = 300;
+  // Initialize launch. The platform fee and its recipient come from the
+  // global config
   const creatorFeeBps = 100;
-  const basePrice = 1_000_000; // 0.001 SOL
-  const slope = 100_000;       // +0.0001 SOL per unit
+  const basePrice = 1_000_000; // 0.001 SOL per whole token
+  const slope = 100_000;       // +0.0001 SOL per whole token sold
   const graduationTarget = 2 * LAMPORTS_PER_SOL;
 
   const initSig = await program.methods
     .initializeLaunch(
-      feeBps,
-      creatorFeeBps,
       new anchor.BN(basePrice),
       new anchor.BN(slope),
-      new anchor.BN(graduationTarget)
+      creatorFeeBps,
+      [{ reserves: { lamports: new anchor.BN(graduationTarget) } }],
+      false
     )
     .accounts({
       payer: wallet.publicKey,
       creator: wallet.publicKey,
-      platformFeeRecipient: wallet.publicKey,
+      globalConfig,
       mint,
       authorityPda,
       statePda,
       vaultSolPda,
-      vaultAta: vaultAta.address,
+      vaultAta,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
     })
     .rpc();
   console.log("initialize_launch tx:", initSig);
 
+  // Move the supply into the vault to open the curve
+  const fundSig = await program.methods
+    .fundCurve(new anchor.BN(initialSupplyAmount.toString()))
+    .accounts({
+      creator: wallet.publicKey,
+      mint,
+      globalConfig,
+      statePda,
+      authorityPda,
+      creatorAta: creatorAta.address,
+      vaultAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+  console.log("fund_curve tx:", fundSig);
+
   // User ATA
   const userAta = await getOrCreateAssociatedTokenAccount(
     connection,
@@ -120,65 +145,76 @@ async function main() {
   const lamportsOf = (pk: PublicKey) => connection.getBalance(pk);
   const tokenBal = (pk: PublicKey) => connection.getTokenAccountBalance(pk);
 
-  const beforeVaultToken = await tokenBal(vaultAta.address);
+  const beforeVaultToken = await tokenBal(vaultAta);
   const beforeUserToken = await tokenBal(userAta.address);
   const beforeVaultSol = await lamportsOf(vaultSolPda);
   console.log("Before buy -> vaultToken:", beforeVaultToken.value.uiAmountString, "userToken:", beforeUserToken.value.uiAmountString, "vaultSOL:", beforeVaultSol);
 
   // BUY
   const buyAmountRaw = BUY_AMOUNT_TOKENS * ONE;
+  const buyQuote = quoteBuy(
+    curveState(await (program.account as any).launchState.fetch(statePda)),
+    buyAmountRaw
+  );
+  const maxCostLamports = buyQuote.cost + buyQuote.cost / 100n; // 1% slippage
   const buySig = await program.methods
-    .buy(new anchor.BN(buyAmountRaw))
+    .buy(new anchor.BN(buyAmountRaw.toString()), new anchor.BN(maxCostLamports.toString()))
     .accounts({
       buyer: wallet.publicKey,
       mint,
+      globalConfig,
       statePda,
-      vaultSolPda,
       authorityPda,
-      vaultAta: vaultAta.address,
-      buyerAta: userAta.address,
-      platformFeeRecipient: wallet.publicKey,
-      creator: wallet.publicKey,
+      vaultSolPda,
+      vaultAta,
+      recipient: wallet.publicKey,
+      recipientAta: userAta.address,
+      systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
     })
     .rpc();
   console.log("Buy tx:", buySig);
 
-  const midVaultToken = await tokenBal(vaultAta.address);
+  const midVaultToken = await tokenBal(vaultAta);
   const midUserToken = await tokenBal(userAta.address);
   const midVaultSol = await lamportsOf(vaultSolPda);
   console.log("After buy  -> vaultToken:", midVaultToken.value.uiAmountString, "userToken:", midUserToken.value.uiAmountString, "vaultSOL:", midVaultSol);
 
-  // SELL (no SOL refund yet on-chain)
+  // SELL
   const sellAmountRaw = SELL_AMOUNT_TOKENS * ONE;
+  const sellQuote = quoteSell(
+    curveState(await (program.account as any).launchState.fetch(statePda)),
+    sellAmountRaw
+  );
+  const minPayoutLamports = sellQuote.refundNet - sellQuote.refundNet / 100n; // 1% slippage
   const sellSig = await program.methods
-    .sell(new anchor.BN(sellAmountRaw))
+    .sell(new anchor.BN(sellAmountRaw.toString()), new anchor.BN(minPayoutLamports.toString()))
     .accounts({
       seller: wallet.publicKey,
       mint,
+      globalConfig,
       statePda,
-      vaultSolPda,
       authorityPda,
-      vaultAta: vaultAta.address,
+      vaultSolPda,
+      vaultAta,
       sellerAta: userAta.address,
-      platformFeeRecipient: wallet.publicKey,
-      creator: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
     })
     .rpc();
   console.log("Sell tx:", sellSig);
 
-  const afterVaultToken = await tokenBal(vaultAta.address);
+  const afterVaultToken = await tokenBal(vaultAta);
   const afterUserToken = await tokenBal(userAta.address);
   const afterVaultSol = await lamportsOf(vaultSolPda);
   console.log("After sell -> vaultToken:", afterVaultToken.value.uiAmountString, "userToken:", afterUserToken.value.uiAmountString, "vaultSOL:", afterVaultSol);
 
   // State
-  const state = await (program.account as any).launch.fetch(statePda);
+  const state = await (program.account as any).launchState.fetch(statePda);
   console.log("State tokens_sold:", state.tokensSold.toString(), "reserves_lamports:", state.reservesLamports.toString(), "platform_fee_accrued:", state.platformFeeAccrued.toString(), "creator_fee_accrued:", state.creatorFeeAccrued.toString());
 }
 
@@ -36,6 +36,7 @@ Object.defineProperty(exports, "__esModule", { value: true });
 const anchor = __importStar(require("@coral-xyz/anchor"));
 const web3_js_1 = require("@solana/web3.js");
 const spl_token_1 = require("@solana/spl-token");
+const grokpad_math_1 = require("./grokpad-math");
 const ONE = 1000000000n;
 const INITIAL_SUPPLY_TOKENS = 1000n;
 const BUY_AMOUNT_TOKENS = 5n;
@@ -57,59 +58,78 @@ async function main() {
     console.log("authorityPda:", authorityPda.toBase58());
     console.log("statePda:", statePda.toBase58());
     console.log("vaultSolPda:", vaultSolPda.toBase58());
-    // Vault ATA
-    const vaultAta = await (0, spl_token_1.getOrCreateAssociatedTokenAccount)(connection, wallet.payer, mint, authorityPda, true);
-    console.log("vaultAta:", vaultAta.address.toBase58());
-    // Mint initial supply
+    const [globalConfig] = web3_js_1.PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+    const vaultAta = (0, spl_token_1.getAssociatedTokenAddressSync)(mint, authorityPda, true);
+    console.log("vaultAta:", vaultAta.toBase58());
+    // Mint initial supply to the creator; fund_curve moves it into the vault
+    const creatorAta = await (0, spl_token_1.getOrCreateAssociatedTokenAccount)(connection, wallet.payer, mint, wallet.publicKey);
     const initialSupplyAmount = INITIAL_SUPPLY_TOKENS * ONE;
-    await (0, spl_token_1.mintTo)(connection, wallet.payer, mint, vaultAta.address, wallet.payer, initialSupplyAmount);
+    await (0, spl_token_1.mintTo)(connection, wallet.payer, mint, creatorAta.address, wallet.payer, initialSupplyAmount);
     console.log("Minted initial supply:", INITIAL_SUPPLY_TOKENS.toString());
-    // Initialize launch
-    const feeBps = 300;
+    // Initialize launch. The platform fee and its recipient come from the config
     const creatorFeeBps = 100;
-    const basePrice = 1000000; // 0.001 SOL
-    const slope = 100000; // +0.0001 SOL per unit
+    const basePrice = 1000000; // 0.001 SOL per whole token
+    const slope = 100000; // +0.0001 SOL per whole token sold
     const graduationTarget = 2 * web3_js_1.LAMPORTS_PER_SOL;
     const initSig = await program.methods
-        .initializeLaunch(new anchor.BN(basePrice), new anchor.BN(slope), feeBps, creatorFeeBps, new anchor.BN(graduationTarget))
+        .initializeLaunch(new anchor.BN(basePrice), new anchor.BN(slope), creatorFeeBps, [{ reserves: { lamports: new anchor.BN(graduationTarget) } }], false)
         .accounts({
         payer: wallet.publicKey,
         creator: wallet.publicKey,
-        platformFeeRecipient: wallet.publicKey,
+        globalConfig,
         mint,
         authorityPda,
         statePda,
         vaultSolPda,
-        vaultAta: vaultAta.address,
+        vaultAta,
         systemProgram: web3_js_1.SystemProgram.programId,
         tokenProgram: spl_token_1.TOKEN_PROGRAM_ID,
         associatedTokenProgram: spl_token_1.ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
     })
         .rpc();
     console.log("initialize_launch tx:", initSig);
-    // User ATA
-    const userAta = await (0, spl_token_1.getOrCreateAssociatedTokenAccount)(connection, wallet.payer, mint, wallet.publicKey);
-    console.log("userAta:", userAta.address.toBase58());
+    const fundSig = await program.methods
+        .fundCurve(new anchor.BN(initialSupplyAmount.toString()))
+        .accounts({
+        creator: wallet.publicKey,
+        mint,
+        globalConfig,
+        statePda,
+        authorityPda,
+        creatorAta: creatorAta.address,
+        vaultAta,
+        tokenProgram: spl_token_1.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: spl_token_1.ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+        .rpc();
+    console.log("fund_curve tx:", fundSig);
+    // The buyer is the creator here, so the tokens land back in its account
+    const userAta = creatorAta;
     const lamportsOf = (pk) => connection.getBalance(pk);
     const tokenBal = (pk) => connection.getTokenAccountBalance(pk);
-    const beforeVaultToken = await tokenBal(vaultAta.address);
+    const beforeVaultToken = await tokenBal(vaultAta);
     const beforeUserToken = await tokenBal(userAta.address);
     const beforeVaultSol = await lamportsOf(vaultSolPda);
     console.log("Before buy -> vaultToken:", beforeVaultToken.value.uiAmountString, "userToken:", beforeUserToken.value.uiAmountString, "vaultSOL:", beforeVaultSol);
     // BUY
     const buyAmountRaw = BUY_AMOUNT_TOKENS * ONE;
-    // Set max cost as 10 SOL to ensure it goes through (can be calculated more precisely)
-    const maxCostLamports = 10 * web3_js_1.LAMPORTS_PER_SOL;
+    // Quote with the program's math; the cost includes both fees
+    const quote = (0, grokpad_math_1.quoteBuy)((0, grokpad_math_1.curveState)(await program.account.launchState.fetch(statePda)), buyAmountRaw);
+    console.log("Quoted cost:", quote.cost.toString(), "platform fee:", quote.platformFee.toString(), "creator fee:", quote.creatorFee.toString());
+    const maxCostLamports = quote.cost + quote.cost / 100n; // 1% slippage
     const buySig = await program.methods
-        .buy(new anchor.BN(buyAmountRaw), new anchor.BN(maxCostLamports))
+        .buy(new anchor.BN(buyAmountRaw.toString()), new anchor.BN(maxCostLamports.toString()))
         .accounts({
         buyer: wallet.publicKey,
         mint,
+        globalConfig,
         statePda,
-        vaultSolPda,
         authorityPda,
-        vaultAta: vaultAta.address,
-        buyerAta: userAta.address,
+        vaultSolPda,
+        vaultAta,
+        recipient: wallet.publicKey,
+        recipientAta: userAta.address,
         tokenProgram: spl_token_1.TOKEN_PROGRAM_ID,
         associatedTokenProgram: spl_token_1.ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: web3_js_1.SystemProgram.programId,
@@ -117,7 +137,7 @@ async function main() {
     })
         .rpc();
     console.log("Buy tx:", buySig);
-    const afterVaultToken = await tokenBal(vaultAta.address);
+    const afterVaultToken = await tokenBal(vaultAta);
     const afterUserToken = await tokenBal(userAta.address);
     const afterVaultSol = await lamportsOf(vaultSolPda);
     console.log("After buy  -> vaultToken:", afterVaultToken.value.uiAmountString, "userToken:", afterUserToken.value.uiAmountString, "vaultSOL:", afterVaultSol);
@@ -7,10 +7,12 @@ import {
 import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
   createMint,
   mintTo,
 } from "@solana/spl-token";
+const { quoteBuy, curveState } = require("./grokpad-math");
 
 const ONE = 1_000_000_000n;
 const INITIAL_SUPPLY_TOKENS = 1_000n;
@@ -54,102 +56,119 @@ async function main() {
   console.log("statePda:", statePda.toBase58());
   console.log("vaultSolPda:", vaultSolPda.toBase58());
 
-  // Vault ATA
-  const vaultAta = await getOrCreateAssociatedTokenAccount(
+  const [globalConfig] = PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+  const vaultAta = getAssociatedTokenAddressSync(mint, authorityPda, true);
+  console.log("vaultAta:", vaultAta.toBase58());
+
+  // Mint initial supply to the creator; fund_curve moves it into the vault
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
     connection,
     (wallet as any).payer,
     mint,
-    authorityPda,
-    true
+    wallet.publicKey
   );
-  console.log("vaultAta:", vaultAta.address.toBase58());
-
-  // Mint initial supply
   const initialSupplyAmount = INITIAL_SUPPLY_TOKENS * ONE;
   await mintTo(
     connection,
     (wallet as any).payer,
     mint,
-    vaultAta.address,
+    creatorAta.address,
     (wallet as any).payer,
     initialSupplyAmount
   );
   console.log("Minted initial supply:", INITIAL_SUPPLY_TOKENS.toString());
 
-  // Initialize launch
-  const feeBps = 300;
+  // Initialize launch. The platform fee and its recipient come from the config
   const creatorFeeBps = 100;
-  const basePrice = 1_000_000; // 0.001 SOL
-  const slope = 100_000;       // +0.0001 SOL per unit
+  const basePrice = 1_000_000; // 0.001 SOL per whole token
+  const slope = 100_000;       // +0.0001 SOL per whole token sold
   const graduationTarget = 2 * LAMPORTS_PER_SOL;
 
   const initSig = await program.methods
     .initializeLaunch(
-      feeBps,
-      creatorFeeBps,
       new anchor.BN(basePrice),
       new anchor.BN(slope),
-      new anchor.BN(graduationTarget)
+      creatorFeeBps,
+      [{ reserves: { lamports: new anchor.BN(graduationTarget) } }],
+      false
     )
     .accounts({
       payer: wallet.publicKey,
       creator: wallet.publicKey,
-      platformFeeRecipient: wallet.publicKey,
+      globalConfig,
       mint,
       authorityPda,
       statePda,
       vaultSolPda,
-      vaultAta: vaultAta.address,
+      vaultAta,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
     })
     .rpc();
   console.log("initialize_launch tx:", initSig);
 
-  // User ATA
-  const userAta = await getOrCreateAssociatedTokenAccount(
-    connection,
-    (wallet as any).payer,
-    mint,
-    wallet.publicKey
-  );
-  console.log("userAta:", userAta.address.toBase58());
+  const fundSig = await program.methods
+    .fundCurve(new anchor.BN(initialSupplyAmount.toString()))
+    .accounts({
+      creator: wallet.publicKey,
+      mint,
+      globalConfig,
+      statePda,
+      authorityPda,
+      creatorAta: creatorAta.address,
+      vaultAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+  console.log("fund_curve tx:", fundSig);
+
+  // The buyer is the creator here, so the tokens land back in its account
+  const userAta = creatorAta;
 
   const lamportsOf = (pk: PublicKey) => connection.getBalance(pk);
   const tokenBal = (pk: PublicKey) => connection.getTokenAccountBalance(pk);
 
-  const beforeVaultToken = await tokenBal(vaultAta.address);
+  const beforeVaultToken = await tokenBal(vaultAta);
   const beforeUserToken = await tokenBal(userAta.address);
   const beforeVaultSol = await lamportsOf(vaultSolPda);
   console.log("Before buy -> vaultToken:", beforeVaultToken.value.uiAmountString, "userToken:", beforeUserToken.value.uiAmountString, "vaultSOL:", beforeVaultSol);
 
   // BUY
   const buyAmountRaw = BUY_AMOUNT_TOKENS * ONE;
+  // Quote with the program's math; the cost includes both fees
+  const quote = quoteBuy(curveState(await (program.account as any).launchState.fetch(statePda)), buyAmountRaw);
+  console.log("Quoted cost:", quote.cost.toString(), "platform fee:", quote.platformFee.toString(), "creator fee:", quote.creatorFee.toString());
+  const maxCostLamports = quote.cost + quote.cost / 100n; // 1% slippage
   const buySig = await program.methods
-    .buy(new anchor.BN(buyAmountRaw))
+    .buy(new anchor.BN(buyAmountRaw.toString()), new anchor.BN(maxCostLamports.toString()))
     .accounts({
       buyer: wallet.publicKey,
       mint,
+      globalConfig,
       statePda,
-      vaultSolPda,
       authorityPda,
-      vaultAta: vaultAta.address,
-      buyerAta: userAta.address,
+      vaultSolPda,
+      vaultAta,
+      recipient: wallet.publicKey,
+      recipientAta: userAta.address,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
     })
     .rpc();
   console.log("Buy tx:", buySig);
 
-  const afterVaultToken = await tokenBal(vaultAta.address);
+  const afterVaultToken = await tokenBal(vaultAta);
   const afterUserToken = await tokenBal(userAta.address);
   const afterVaultSol = await lamportsOf(vaultSolPda);
   console.log("After buy  -> vaultToken:", afterVaultToken.value.uiAmountString, "userToken:", afterUserToken.value.uiAmountString, "vaultSOL:", afterVaultSol);
 
   // State
-  const state = await (program.account as any).launch.fetch(statePda);
+  const state = await (program.account as any).launchState.fetch(statePda);
   console.log("State tokens_sold:", state.tokensSold.toString(), "reserves_lamports:", state.reservesLamports.toString(), "platform_fee_accrued:", state.platformFeeAccrued.toString(), "creator_fee_accrued:", state.creatorFeeAccrued.toString());
 }
 
@@ -0,0 +1,77 @@
+"use strict";
+// Checks scripts/grokpad-math.js against the quote vectors the grokpad-math
+// crate verifies (crates/grokpad-math/tests/quote_vectors.json). Exits
+// non-zero on the first vector the port quotes differently.
+//
+//   node scripts/check-quote-parity.js
+const fs = require("fs");
+const path = require("path");
+const math = require("./grokpad-math");
+
+const VECTORS = path.join(__dirname, "../crates/grokpad-math/tests/quote_vectors.json");
+
+const QUOTES = {
+    buy: math.quoteBuy,
+    sell: math.quoteSell,
+    buy_exact_in: math.quoteBuyExactIn,
+    sell_exact_out: math.quoteSellExactOut,
+};
+
+const camel = (key) => key.replace(/_([a-z])/g, (_, c) => c.toUpperCase());
+const snake = (key) => key.replace(/[A-Z]/g, (c) => `_${c.toLowerCase()}`);
+
+// A vector's state, as the port takes it: BigInt amounts, numeric bps
+function fromVector(state) {
+    const out = {};
+    for (const [key, value] of Object.entries(state)) {
+        out[camel(key)] = typeof value === "string" ? BigInt(value) : value;
+    }
+    return out;
+}
+
+// A quote from the port, in the vectors' shape
+function toVector(value) {
+    if (typeof value === "bigint") {
+        return value.toString();
+    }
+    if (typeof value === "object") {
+        return Object.fromEntries(Object.entries(value).map(([key, v]) => [snake(key), toVector(v)]));
+    }
+    return value;
+}
+
+function outcome(vector) {
+    try {
+        return { quote: toVector(QUOTES[vector.op](fromVector(vector.state), BigInt(vector.input))) };
+    } catch (e) {
+        if (!(e instanceof math.QuoteError)) {
+            throw e;
+        }
+        return { error: e.message };
+    }
+}
+
+// Key order differs between the two sides, so compare sorted
+function canonical(value) {
+    if (typeof value !== "object" || value === null) {
+        return JSON.stringify(value);
+    }
+    return `{${Object.keys(value).sort().map((key) => `${key}:${canonical(value[key])}`).join(",")}}`;
+}
+
+function main() {
+    const vectors = JSON.parse(fs.readFileSync(VECTORS, "utf8"));
+    vectors.forEach((vector, i) => {
+        const expected = vector.error === undefined ? { quote: vector.quote } : { error: vector.error };
+        const actual = outcome(vector);
+        if (canonical(actual) !== canonical(expected)) {
+            console.error(`vector ${i} (${vector.op} ${vector.input}) differs`);
+            console.error("  expected:", JSON.stringify(expected));
+            console.error("  actual:  ", JSON.stringify(actual));
+            process.exit(1);
+        }
+    });
+    console.log(`${vectors.length} quote vectors match`);
+}
+
+main();
@@ -45,22 +45,15 @@ async function main() {
       
       // Try to parse the state data based on the LaunchState struct
       try {
-        // Basic parsing assuming the LaunchState layout is:
-        // - mint: PublicKey (32 bytes)
-        // - decimals: u8 (1 byte)
-        // - basePriceLamports: u64 (8 bytes)
-        // - slopeLamports: u64 (8 bytes)
-        // - feeBps: u16 (2 bytes)
-        // - creatorFeeBps: u16 (2 bytes)
-        // - platformFeeRecipient: PublicKey (32 bytes)
-        // - creator: PublicKey (32 bytes)
-        // - graduationTargetLamports: u64 (8 bytes)
-        // - graduated: bool (1 byte)
-        // - supplyRemaining: u64 (8 bytes)
-        // - tokensSold: u64 (8 bytes)
-        // - reservesLamports: u64 (8 bytes)
-        // - platformFeeAccrued: u64 (8 bytes)
-        // - creatorFeeAccrued: u64 (8 bytes)
+        // Zero-copy LaunchState layout after the discriminator:
+        // - 7 PublicKeys (224 bytes): mint, platformFeeRecipient, creator,
+        //   pool, platformFeeDelegate, creatorFeeDelegate, pendingCreator
+        // - 11 u64s (88 bytes): basePriceLamports, slopeLamports,
+        //   totalSupply, supplyRemaining, tokensSold, reservesLamports,
+        //   platformFeeAccrued, creatorFeeAccrued, volumeLamports,
+        //   platformFeesClaimed, creatorFeesClaimed
+        // - 4 graduation criteria (64 bytes)
+        // - feeBps: u16, creatorFeeBps: u16, decimals: u8, ...
         
         // Skip 8 bytes of anchor's discriminator
         const data = stateInfo.data.slice(8);
@@ -69,8 +62,16 @@ async function main() {
         const mintKey = new PublicKey(data.slice(0, 32));
         console.log('Mint:', mintKey.toString());
         
+        const u64At = (offset) => data.readBigUInt64LE(offset).toString();
+        console.log('Base price:', u64At(224), 'lamports');
+        console.log('Slope:', u64At(232), 'lamports');
+        console.log('Supply remaining:', u64At(248));
+        console.log('Tokens sold:', u64At(256));
+        console.log('Reserves:', u64At(264), 'lamports');
+        console.log('Fee bps:', data.readUInt16LE(376), 'creator fee bps:', data.readUInt16LE(378));
+        
         // Extract decimals
-        const decimals = data[32];
+        const decimals = data[380];
         console.log('Decimals:', decimals);
         
         // Check if the state account is properly initialized
@@ -0,0 +1,207 @@
+"use strict";
+// Instruction builders for the grokpad program's launch and curve trading
+// instructions, for the scripts that don't go through the generated client.
+// Account lists follow the program's `Accounts` structs in order; data is the
+// Anchor discriminator followed by the Borsh-encoded arguments.
+Object.defineProperty(exports, "__esModule", { value: true });
+
+const crypto = require("crypto");
+const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, TransactionInstruction } = require("@solana/web3.js");
+const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } = require("@solana/spl-token");
+
+function discriminator(name) {
+    return crypto.createHash("sha256").update(`global:${name}`).digest().subarray(0, 8);
+}
+
+function u8(v) {
+    return Buffer.from([v]);
+}
+
+function u16(v) {
+    const b = Buffer.alloc(2);
+    b.writeUInt16LE(Number(v));
+    return b;
+}
+
+function u32(v) {
+    const b = Buffer.alloc(4);
+    b.writeUInt32LE(Number(v));
+    return b;
+}
+
+function u64(v) {
+    const b = Buffer.alloc(8);
+    b.writeBigUInt64LE(BigInt(v.toString()));
+    return b;
+}
+
+function i64(v) {
+    const b = Buffer.alloc(8);
+    b.writeBigInt64LE(BigInt(v.toString()));
+    return b;
+}
+
+function option(v, encode) {
+    return v === null || v === undefined ? u8(0) : Buffer.concat([u8(1), encode(v)]);
+}
+
+// `GraduationCriterion` variants, in the program's declaration order
+const GraduationCriterion = {
+    reserves: (lamports) => Buffer.concat([u8(0), u64(lamports)]),
+    percentSold: (bps) => Buffer.concat([u8(1), u16(bps)]),
+    marketCap: (lamports) => Buffer.concat([u8(2), u64(lamports)]),
+    deadline: (unixTimestamp) => Buffer.concat([u8(3), i64(unixTimestamp)]),
+};
+
+// Arguments `initialize_launch` takes after the accounts. `creatorFeeBps`
+// and `graduationCriteria` left null take the config's defaults; the
+// platform fee and its recipient always come from the config.
+function initializeLaunchData({ basePriceLamports, slopeLamports, creatorFeeBps = null, graduationCriteria = null, capGraduatingBuy = false }) {
+    return Buffer.concat([
+        discriminator("initialize_launch"),
+        u64(basePriceLamports),
+        u64(slopeLamports),
+        option(creatorFeeBps, u16),
+        option(graduationCriteria, (criteria) => Buffer.concat([u32(criteria.length), ...criteria])),
+        u8(capGraduatingBuy ? 1 : 0),
+    ]);
+}
+
+function fundCurveData(amount) {
+    return Buffer.concat([discriminator("fund_curve"), u64(amount)]);
+}
+
+function buyData(amount, maxCostLamports) {
+    return Buffer.concat([discriminator("buy"), u64(amount), u64(maxCostLamports)]);
+}
+
+function sellData(amount, minPayoutLamports) {
+    return Buffer.concat([discriminator("sell"), u64(amount), u64(minPayoutLamports)]);
+}
+
+function configPda(programId) {
+    return PublicKey.findProgramAddressSync([Buffer.from("config")], programId)[0];
+}
+
+// The accounts every launch derives from its mint
+function launchPdas(programId, mint) {
+    const pda = (seed) => PublicKey.findProgramAddressSync([Buffer.from(seed), mint.toBuffer()], programId)[0];
+    const authorityPda = pda("launch");
+    return {
+        authorityPda,
+        statePda: pda("launch_state"),
+        vaultSolPda: pda("vault_sol"),
+        vaultAta: getAssociatedTokenAddressSync(mint, authorityPda, true),
+    };
+}
+
+const meta = (pubkey, isSigner, isWritable) => ({ pubkey, isSigner, isWritable });
+
+// `initialize_launch` for an existing mint. The program creates the state,
+// both vaults and the vault token account; the curve opens once the
+// creator moves the supply in with `fund_curve`.
+function initializeLaunchIx(programId, { payer, creator, mint, ...args }) {
+    const { authorityPda, statePda, vaultSolPda, vaultAta } = launchPdas(programId, mint);
+    return new TransactionInstruction({
+        programId,
+        keys: [
+            meta(payer, true, true),
+            meta(creator, false, false),
+            meta(configPda(programId), false, false),
+            meta(mint, false, false),
+            meta(authorityPda, false, false),
+            meta(statePda, false, true),
+            meta(vaultSolPda, false, true),
+            meta(vaultAta, false, true),
+            meta(SystemProgram.programId, false, false),
+            meta(TOKEN_PROGRAM_ID, false, false),
+            meta(ASSOCIATED_TOKEN_PROGRAM_ID, false, false),
+            meta(SYSVAR_RENT_PUBKEY, false, false),
+        ],
+        data: initializeLaunchData(args),
+    });
+}
+
+// `fund_curve`: moves `amount` from the creator's token account into the
+// vault and opens the curve with it, less the pool allocation
+function fundCurveIx(programId, { creator, mint, amount }) {
+    const { authorityPda, statePda, vaultAta } = launchPdas(programId, mint);
+    return new TransactionInstruction({
+        programId,
+        keys: [
+            meta(creator, true, false),
+            meta(mint, false, false),
+            meta(configPda(programId), false, false),
+            meta(statePda, false, true),
+            meta(authorityPda, false, false),
+            meta(getAssociatedTokenAddressSync(mint, creator), false, true),
+            meta(vaultAta, false, true),
+            meta(TOKEN_PROGRAM_ID, false, false),
+            meta(ASSOCIATED_TOKEN_PROGRAM_ID, false, false),
+        ],
+        data: fundCurveData(amount),
+    });
+}
+
+// `buy` of exactly `amount` base units, paying at most `maxCostLamports`
+// including fees. The tokens go to `recipient`'s token account, created if
+// needed at the buyer's expense.
+function buyIx(programId, { buyer, mint, amount, maxCostLamports, recipient = buyer }) {
+    const { authorityPda, statePda, vaultSolPda, vaultAta } = launchPdas(programId, mint);
+    return new TransactionInstruction({
+        programId,
+        keys: [
+            meta(buyer, true, true),
+            meta(mint, false, false),
+            meta(configPda(programId), false, false),
+            meta(statePda, false, true),
+            meta(authorityPda, false, false),
+            meta(vaultSolPda, false, true),
+            meta(vaultAta, false, true),
+            meta(recipient, false, false),
+            meta(getAssociatedTokenAddressSync(mint, recipient, true), false, true),
+            meta(SystemProgram.programId, false, false),
+            meta(TOKEN_PROGRAM_ID, false, false),
+            meta(ASSOCIATED_TOKEN_PROGRAM_ID, false, false),
+            meta(SYSVAR_RENT_PUBKEY, false, false),
+        ],
+        data: buyData(amount, maxCostLamports),
+    });
+}
+
+// `sell` of exactly `amount` base units for at least `minPayoutLamports`
+// after fees
+function sellIx(programId, { seller, mint, amount, minPayoutLamports }) {
+    const { authorityPda, statePda, vaultSolPda, vaultAta } = launchPdas(programId, mint);
+    return new TransactionInstruction({
+        programId,
+        keys: [
+            meta(seller, true, true),
+            meta(mint, false, false),
+            meta(configPda(programId), false, false),
+            meta(statePda, false, true),
+            meta(authorityPda, false, false),
+            meta(vaultSolPda, false, true),
+            meta(vaultAta, false, true),
+            meta(getAssociatedTokenAddressSync(mint, seller), false, true),
+            meta(SystemProgram.programId, false, false),
+            meta(TOKEN_PROGRAM_ID, false, false),
+            meta(ASSOCIATED_TOKEN_PROGRAM_ID, false, false),
+            meta(SYSVAR_RENT_PUBKEY, false, false),
+        ],
+        data: sellData(amount, minPayoutLamports),
+    });
+}
+
+exports.discriminator = discriminator;
+exports.GraduationCriterion = GraduationCriterion;
+exports.initializeLaunchData = initializeLaunchData;
+exports.fundCurveData = fundCurveData;
+exports.buyData = buyData;
+exports.sellData = sellData;
+exports.configPda = configPda;
+exports.launchPdas = launchPdas;
+exports.initializeLaunchIx = initializeLaunchIx;
+exports.fundCurveIx = fundCurveIx;
+exports.buyIx = buyIx;
+exports.sellIx = sellIx;
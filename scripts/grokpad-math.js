@@ -0,0 +1,344 @@
+"use strict";
+// BigInt port of crates/grokpad-math: curve pricing, fee splitting and the
+// trade quotes the program settles with. Every function follows its Rust
+// namesake step for step, including where it rounds and where it reports an
+// overflow, so a quote taken from a launch's state here matches the on-chain
+// result exactly. scripts/check-quote-parity.js checks this against the
+// vectors the Rust crate verifies.
+//
+// Amounts are BigInt. Failures throw a QuoteError whose `kind` is the Rust
+// variant name; overflows use kind "Math" and carry the failing step in `op`.
+Object.defineProperty(exports, "__esModule", { value: true });
+
+const BPS_DENOMINATOR = 10000n;
+const U64_MAX = (1n << 64n) - 1n;
+const U256_MAX = (1n << 256n) - 1n;
+
+class QuoteError extends Error {
+    constructor(kind, op) {
+        super(op === undefined ? kind : `${kind}(${op})`);
+        this.name = "QuoteError";
+        this.kind = kind;
+        this.op = op;
+    }
+}
+
+function overflow(op) {
+    return new QuoteError("Math", op);
+}
+
+// Range checks standing in for the crate's u64 and U256 arithmetic
+function wide(v, op) {
+    if (v > U256_MAX) {
+        throw overflow(op);
+    }
+    return v;
+}
+
+function toU64(v, op) {
+    if (v > U64_MAX) {
+        throw overflow(op);
+    }
+    return v;
+}
+
+function add(a, b, op) {
+    return toU64(a + b, op);
+}
+
+function isqrt(n) {
+    if (n < 2n) {
+        return n;
+    }
+    let x = n;
+    let y = (x + 1n) >> 1n;
+    while (y < x) {
+        x = y;
+        y = (x + n / x) >> 1n;
+    }
+    return x;
+}
+
+function divCeil(a, b) {
+    return a % b === 0n ? a / b : a / b + 1n;
+}
+
+// `base_price*scale + slope*sold`: the marginal price at `sold`, times `scale`
+function scaledPrice(basePrice, slope, scale, sold) {
+    return wide(wide(basePrice * scale, "scaled price") + wide(slope * sold, "scaled price"), "scaled price");
+}
+
+// Lamports for moving `amount` base units along the curve from `soldBefore`:
+// (2*base_price*scale*amount + slope*amount*(2*sold_before + amount)) / (2*scale^2),
+// rounded up for buys and down for sells
+function curveCost(basePrice, slope, scale, soldBefore, amount, roundUp) {
+    const op = "curve cost";
+    const baseTerm = wide(wide(basePrice * scale, op) * wide(amount * 2n, op), op);
+    const span = wide(wide(soldBefore * 2n, op) + amount, op);
+    const slopeTerm = wide(wide(slope * amount, op) * span, op);
+    const numerator = wide(baseTerm + slopeTerm, op);
+    const denominator = wide(wide(scale * scale, op) * 2n, op);
+    if (denominator === 0n) {
+        throw overflow(op);
+    }
+    let cost = numerator / denominator;
+    if (roundUp && numerator % denominator !== 0n) {
+        cost = wide(cost + 1n, op);
+    }
+    return toU64(cost, op);
+}
+
+// Largest amount, at most `maxAmount`, whose rounded-up cost fits in `budget`
+function amountForCost(basePrice, slope, scale, soldBefore, budget, maxAmount) {
+    const op = "inverse buy";
+    const q = scaledPrice(basePrice, slope, scale, soldBefore);
+    const rhs = wide(wide(scale * scale, op) * wide(budget * 2n, op), op);
+    let estimate;
+    if (slope === 0n) {
+        const denominator = wide(q * 2n, op);
+        if (denominator === 0n) {
+            throw overflow(op);
+        }
+        estimate = rhs / denominator;
+    } else {
+        const disc = wide(wide(q * q, op) + wide(slope * rhs, op), op);
+        const root = isqrt(disc);
+        if (root < q) {
+            throw overflow(op);
+        }
+        estimate = (root - q) / slope;
+    }
+    let amount = estimate > maxAmount ? maxAmount : estimate;
+    const fits = (amount) => {
+        try {
+            return curveCost(basePrice, slope, scale, soldBefore, amount, true) <= budget;
+        } catch (e) {
+            return false;
+        }
+    };
+    while (amount > 0n && !fits(amount)) {
+        amount -= 1n;
+    }
+    while (amount < maxAmount && fits(amount + 1n)) {
+        amount += 1n;
+    }
+    return amount;
+}
+
+// Smallest amount whose rounded-down refund from `sold` is at least `target`,
+// or null if selling everything sold still refunds less
+function amountForRefund(basePrice, slope, scale, sold, target) {
+    const op = "inverse sell";
+    const refund = (amount) => curveCost(basePrice, slope, scale, sold - amount, amount, false);
+    if (refund(sold) < target) {
+        return null;
+    }
+    const q = scaledPrice(basePrice, slope, scale, sold);
+    const rhs = wide(wide(scale * scale, op) * wide(target * 2n, op), op);
+    let estimate;
+    if (slope === 0n) {
+        const denominator = wide(q * 2n, op);
+        if (denominator === 0n) {
+            throw overflow(op);
+        }
+        estimate = divCeil(rhs, denominator);
+    } else {
+        const square = wide(q * q, op);
+        const scaledRhs = wide(slope * rhs, op);
+        if (square < scaledRhs) {
+            throw overflow(op);
+        }
+        estimate = divCeil(q - isqrt(square - scaledRhs), slope);
+    }
+    let amount = estimate > sold ? sold : estimate;
+    while (amount < sold && refund(amount) < target) {
+        amount += 1n;
+    }
+    while (amount > 0n && refund(amount - 1n) >= target) {
+        amount -= 1n;
+    }
+    return amount;
+}
+
+function spotPrice(basePrice, slope, scale, sold) {
+    if (scale === 0n) {
+        throw overflow("spot price");
+    }
+    return toU64(scaledPrice(basePrice, slope, scale, sold) / scale, "spot price");
+}
+
+// Each fee rounds down; the crate computes them in u128 and truncates to u64
+function splitFees(gross, feeBps, creatorFeeBps) {
+    const bpsOf = (bps) => ((gross * BigInt(bps)) / BPS_DENOMINATOR) & U64_MAX;
+    const platformFee = bpsOf(feeBps);
+    const creatorFee = bpsOf(creatorFeeBps);
+    const net = gross - platformFee - creatorFee;
+    if (gross < platformFee || gross - platformFee < creatorFee) {
+        throw overflow("fee split");
+    }
+    return { platformFee, creatorFee, net };
+}
+
+// Largest base amount that, with its fees added on top, fits in `total`
+function baseForTotal(total, feeBps, creatorFeeBps) {
+    const feeTotalBps = BigInt(feeBps) + BigInt(creatorFeeBps);
+    const withFees = (base) => {
+        const fees = splitFees(base, feeBps, creatorFeeBps);
+        return base + fees.platformFee + fees.creatorFee;
+    };
+    let base = (total * BPS_DENOMINATOR) / (BPS_DENOMINATOR + feeTotalBps);
+    while (base < total && withFees(base + 1n) <= total) {
+        base += 1n;
+    }
+    return base;
+}
+
+// Smallest gross whose remainder after fees is at least `net`
+function grossForNet(net, feeBps, creatorFeeBps) {
+    const feeTotalBps = BigInt(feeBps) + BigInt(creatorFeeBps);
+    if (feeTotalBps >= BPS_DENOMINATOR) {
+        throw overflow("gross for net");
+    }
+    let gross = toU64(divCeil(net * BPS_DENOMINATOR, BPS_DENOMINATOR - feeTotalBps), "gross for net");
+    while (gross > 1n && splitFees(gross - 1n, feeBps, creatorFeeBps).net >= net) {
+        gross -= 1n;
+    }
+    return gross;
+}
+
+function scaleOf(state) {
+    return toU64(10n ** BigInt(state.decimals), "decimals scale");
+}
+
+// The fields of a fetched LaunchState that pricing reads, as BigInt
+function curveState(launchState) {
+    const big = (v) => BigInt(v.toString());
+    return {
+        basePriceLamports: big(launchState.basePriceLamports),
+        slopeLamports: big(launchState.slopeLamports),
+        decimals: Number(launchState.decimals),
+        feeBps: Number(launchState.feeBps),
+        creatorFeeBps: Number(launchState.creatorFeeBps),
+        supplyRemaining: big(launchState.supplyRemaining),
+        tokensSold: big(launchState.tokensSold),
+        reservesLamports: big(launchState.reservesLamports),
+        platformFeeAccrued: big(launchState.platformFeeAccrued),
+        creatorFeeAccrued: big(launchState.creatorFeeAccrued),
+    };
+}
+
+// Quotes buying exactly `amount` base units. `cost` is what the buyer pays:
+// the curve cost with both fees on top
+function quoteBuy(state, amount) {
+    if (amount === 0n) {
+        throw new QuoteError("ZeroAmount");
+    }
+    if (amount > state.supplyRemaining) {
+        throw new QuoteError("NotEnoughSupply");
+    }
+    const curve = curveCost(state.basePriceLamports, state.slopeLamports, scaleOf(state), state.tokensSold, amount, true);
+    const fees = splitFees(curve, state.feeBps, state.creatorFeeBps);
+    const cost = add(add(curve, fees.platformFee, "buy cost"), fees.creatorFee, "buy cost");
+    const after = {
+        ...state,
+        supplyRemaining: state.supplyRemaining - amount,
+        tokensSold: add(state.tokensSold, amount, "tokens sold"),
+        reservesLamports: add(state.reservesLamports, curve, "reserves"),
+        platformFeeAccrued: add(state.platformFeeAccrued, fees.platformFee, "platform fees"),
+        creatorFeeAccrued: add(state.creatorFeeAccrued, fees.creatorFee, "creator fees"),
+    };
+    return { amount, cost, platformFee: fees.platformFee, creatorFee: fees.creatorFee, after };
+}
+
+// Quotes the largest buy `lamportsIn` pays for, capped at the remaining supply
+function quoteBuyExactIn(state, lamportsIn) {
+    if (lamportsIn === 0n) {
+        throw new QuoteError("ZeroAmount");
+    }
+    const budget = baseForTotal(lamportsIn, state.feeBps, state.creatorFeeBps);
+    const amount = amountForCost(
+        state.basePriceLamports,
+        state.slopeLamports,
+        scaleOf(state),
+        state.tokensSold,
+        budget,
+        state.supplyRemaining,
+    );
+    if (amount === 0n) {
+        throw new QuoteError("ZeroOutput");
+    }
+    return quoteBuy(state, amount);
+}
+
+// Quotes selling exactly `amount` base units. The full curve value leaves
+// reserves and the fees come out of the seller's share
+function quoteSell(state, amount) {
+    if (amount === 0n) {
+        throw new QuoteError("ZeroAmount");
+    }
+    if (amount > state.tokensSold) {
+        throw new QuoteError("NotEnoughSold");
+    }
+    const refundGross = curveCost(
+        state.basePriceLamports,
+        state.slopeLamports,
+        scaleOf(state),
+        state.tokensSold - amount,
+        amount,
+        false,
+    );
+    const fees = splitFees(refundGross, state.feeBps, state.creatorFeeBps);
+    if (fees.net === 0n) {
+        throw new QuoteError("ZeroOutput");
+    }
+    if (refundGross > state.reservesLamports) {
+        throw new QuoteError("InsufficientReserves");
+    }
+    const after = {
+        ...state,
+        supplyRemaining: add(state.supplyRemaining, amount, "supply remaining"),
+        tokensSold: state.tokensSold - amount,
+        reservesLamports: state.reservesLamports - refundGross,
+        platformFeeAccrued: add(state.platformFeeAccrued, fees.platformFee, "platform fees"),
+        creatorFeeAccrued: add(state.creatorFeeAccrued, fees.creatorFee, "creator fees"),
+    };
+    return {
+        amount,
+        refundGross,
+        refundNet: fees.net,
+        platformFee: fees.platformFee,
+        creatorFee: fees.creatorFee,
+        after,
+    };
+}
+
+// Quotes the smallest sell whose net refund is at least `lamportsOut`
+function quoteSellExactOut(state, lamportsOut) {
+    if (lamportsOut === 0n) {
+        throw new QuoteError("ZeroAmount");
+    }
+    if (BigInt(state.feeBps) + BigInt(state.creatorFeeBps) >= BPS_DENOMINATOR) {
+        throw new QuoteError("FeeTooHigh");
+    }
+    const gross = grossForNet(lamportsOut, state.feeBps, state.creatorFeeBps);
+    const amount = amountForRefund(state.basePriceLamports, state.slopeLamports, scaleOf(state), state.tokensSold, gross);
+    if (amount === null) {
+        throw new QuoteError("InsufficientReserves");
+    }
+    return quoteSell(state, amount);
+}
+
+exports.BPS_DENOMINATOR = BPS_DENOMINATOR;
+exports.QuoteError = QuoteError;
+exports.curveCost = curveCost;
+exports.amountForCost = amountForCost;
+exports.amountForRefund = amountForRefund;
+exports.spotPrice = spotPrice;
+exports.splitFees = splitFees;
+exports.baseForTotal = baseForTotal;
+exports.grossForNet = grossForNet;
+exports.curveState = curveState;
+exports.quoteBuy = quoteBuy;
+exports.quoteBuyExactIn = quoteBuyExactIn;
+exports.quoteSell = quoteSell;
+exports.quoteSellExactOut = quoteSellExactOut;
@@ -38,6 +38,8 @@ const anchor = __importStar(require("@coral-xyz/anchor"));
 const grokpad_1 = require("../target/types/grokpad");
 const web3_js_1 = require("@solana/web3.js");
 const spl_token_1 = require("@solana/spl-token");
+const grokpad_ix_1 = require("./grokpad-ix");
+const grokpad_math_1 = require("./grokpad-math");
 // SDK Class for higher-level interaction with the Grokpad program
 class GrokpadSDK {
     constructor(client, connection, wallet) {
@@ -55,28 +57,48 @@ class GrokpadSDK {
         const client = new grokpad_1.GrokpadClient(provider, actualProgramId);
         return new GrokpadSDK(client, connection, wallet);
     }
-    // Creates a new token mint and initializes a bonding curve launch
-    async createNewLaunch(initialSupply, basePriceLamports, slopeLamports, feeBps, creatorFeeBps, graduationTargetLamports) {
+    // Creates a new token mint, mints the supply to the wallet and opens a
+    // bonding curve launch with it. The platform fee and its recipient come
+    // from the global config; a null creator fee or graduation target takes
+    // the config's default.
+    async createNewLaunch(initialSupply, basePriceLamports, slopeLamports, creatorFeeBps = null, graduationTargetLamports = null) {
         // Create the mint account
         const mint = await (0, spl_token_1.createMint)(this.connection, this.wallet.payer, this.wallet.publicKey, null, // Freeze authority
         9 // Decimals
         );
         console.log("Created mint:", mint.toBase58());
-        // Calculate PDAs for this launch
-        const [authorityPda] = web3_js_1.PublicKey.findProgramAddressSync([Buffer.from("launch"), mint.toBuffer()], this.client.program.programId);
-        // Get the vault token account
-        const vaultAta = await (0, spl_token_1.getOrCreateAssociatedTokenAccount)(this.connection, this.wallet.payer, mint, authorityPda, true);
-        // Mint initial supply to the vault
-        const mintAmount = initialSupply * (10 ** 9);
-        await (0, spl_token_1.mintTo)(this.connection, this.wallet.payer, mint, vaultAta.address, this.wallet.publicKey, mintAmount);
-        console.log(`Minted ${initialSupply} tokens to vault`);
-        // Initialize the launch
-        const txSignature = await this.client.initializeLaunch(mint, new anchor.BN(basePriceLamports), new anchor.BN(slopeLamports), feeBps, creatorFeeBps, new anchor.BN(graduationTargetLamports), this.wallet.publicKey, // creator
-        this.wallet.publicKey // platform fee recipient
-        );
+        // Mint the supply to the creator, who moves it into the vault with fund_curve
+        const creatorAta = await (0, spl_token_1.getOrCreateAssociatedTokenAccount)(this.connection, this.wallet.payer, mint, this.wallet.publicKey);
+        const mintAmount = BigInt(initialSupply) * 10n ** 9n;
+        await (0, spl_token_1.mintTo)(this.connection, this.wallet.payer, mint, creatorAta.address, this.wallet.publicKey, mintAmount);
+        console.log(`Minted ${initialSupply} tokens to the creator`);
+        // Initialize the launch and open the curve in one transaction
+        const programId = this.client.program.programId;
+        const graduationCriteria = graduationTargetLamports === null ? null : [grokpad_ix_1.GraduationCriterion.reserves(graduationTargetLamports)];
+        const tx = new web3_js_1.Transaction().add((0, grokpad_ix_1.initializeLaunchIx)(programId, {
+            payer: this.wallet.publicKey,
+            creator: this.wallet.publicKey,
+            mint,
+            basePriceLamports,
+            slopeLamports,
+            creatorFeeBps,
+            graduationCriteria,
+        }), (0, grokpad_ix_1.fundCurveIx)(programId, { creator: this.wallet.publicKey, mint, amount: mintAmount }));
+        const txSignature = await (0, web3_js_1.sendAndConfirmTransaction)(this.connection, tx, [this.wallet.payer]);
         console.log("Launch initialized:", txSignature);
         return { mint, txSignature };
     }
+    // Quotes buying `amount` base units at the launch's current state, with
+    // the math the program settles with: `cost` includes both fees
+    async quoteBuy(mint, amount) {
+        const state = await this.client.getLaunchState(mint);
+        return (0, grokpad_math_1.quoteBuy)((0, grokpad_math_1.curveState)(state), BigInt(amount));
+    }
+    // Quotes selling `amount` base units; `refundNet` is what the seller receives
+    async quoteSell(mint, amount) {
+        const state = await this.client.getLaunchState(mint);
+        return (0, grokpad_math_1.quoteSell)((0, grokpad_math_1.curveState)(state), BigInt(amount));
+    }
     // Helper method to get current token balance
     async getTokenBalance(tokenAccount) {
         const account = await (0, spl_token_1.getAccount)(this.connection, tokenAccount);
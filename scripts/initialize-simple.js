@@ -1,5 +1,5 @@
 const { Connection, PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction, sendAndConfirmTransaction } = require('@solana/web3.js');
-const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getOrCreateAssociatedTokenAccount, getAccount } = require('@solana/spl-token');
+const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync, getAccount } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
 const { PROGRAM_ID } = require('./config');
@@ -45,15 +45,9 @@ async function main() {
     );
     console.log('Vault SOL PDA:', vaultSolPda.toString());
     
-    // Get vault ATA
-    const vaultAta = await getOrCreateAssociatedTokenAccount(
-      connection,
-      wallet,
-      mint,
-      authorityPda,
-      true
-    );
-    console.log('Vault ATA:', vaultAta.address.toString());
+    // Get vault ATA; initialize_launch creates it
+    const vaultAta = getAssociatedTokenAddressSync(mint, authorityPda, true);
+    console.log('Vault ATA:', vaultAta.toString());
     
     // Check if state account exists
     const stateInfo = await connection.getAccountInfo(statePda);
@@ -70,355 +64,9 @@ async function main() {
     console.log('\nThen use the following Anchor CLI command to call the initialize_launch instruction:');
     console.log(`cd /Users/bash/Desktop/FUN.GROK && anchor run scripts/anchor-initialize.js -- ${MINT_ADDRESS}`);
     
-    // Create anchor-initialize.js script
-    const anchorInitPath = path.join(__dirname, 'anchor-initialize.js');
-    const anchorInitScript = `
-const anchor = require('@coral-xyz/anchor');
-const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
-const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } = require('@solana/spl-token');
+    // anchor-initialize.js, anchor-buy.js and anchor-sell.js are checked in
+    // next to this script and kept in step with the program's instructions.
 
-// Get the mint address from command line
-const mintAddress = process.argv[2];
-if (!mintAddress) {
-  console.error("Please provide a mint address as argument");
-  process.exit(1);
-}
-
-// Set up the provider
-const provider = anchor.AnchorProvider.env();
-anchor.setProvider(provider);
-
-// Load the IDL
-const idl = require('../target/idl/grokpad.json');
-const programId = PROGRAM_ID;
-const program = new anchor.Program(idl, programId, provider);
-
-async function main() {
-  try {
-    const mint = new PublicKey(mintAddress);
-    console.log("Using mint:", mint.toString());
-    
-    // Derive PDAs
-    const [authorityPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("launch"), mint.toBuffer()], 
-      programId
-    );
-    console.log('Authority PDA:', authorityPda.toString());
-    
-    const [statePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("launch_state"), mint.toBuffer()],
-      programId
-    );
-    console.log('State PDA:', statePda.toString());
-    
-    const [vaultSolPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault_sol"), mint.toBuffer()],
-      programId
-    );
-    console.log('Vault SOL PDA:', vaultSolPda.toString());
-    
-    // Get vault ATA
-    const vaultAta = await anchor.utils.token.associatedAddress({
-      mint: mint,
-      owner: authorityPda
-    });
-    console.log('Vault ATA:', vaultAta.toString());
-    
-    // Launch parameters
-    const basePriceLamports = new anchor.BN(1_000_000); // 0.001 SOL base price
-    const slopeLamports = new anchor.BN(100_000); // 0.0001 SOL per token price increase
-    const feeBps = 300; // 3.00% platform fee
-    const creatorFeeBps = 100; // 1.00% creator fee
-    const graduationTargetLamports = new anchor.BN(2 * 1_000_000_000); // 2 SOL graduation target
-    
-    console.log("Initializing launch...");
-    const tx = await program.methods
-      .initializeLaunch(
-        basePriceLamports,
-        slopeLamports,
-        feeBps,
-        creatorFeeBps,
-        graduationTargetLamports
-      )
-      .accounts({
-        payer: provider.wallet.publicKey,
-        creator: provider.wallet.publicKey,
-        platformFeeRecipient: provider.wallet.publicKey,
-        mint: mint,
-        authorityPda: authorityPda,
-        statePda: statePda,
-        vaultSolPda: vaultSolPda,
-        vaultAta: vaultAta,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        rent: SYSVAR_RENT_PUBKEY
-      })
-      .rpc();
-    
-    console.log("Launch initialized! Transaction signature:", tx);
-    
-    // Get the state account
-    const state = await program.account.launchState.fetch(statePda);
-    console.log("Launch state:", {
-      basePriceLamports: state.basePriceLamports.toString(),
-      slopeLamports: state.slopeLamports.toString(),
-      feeBps: state.feeBps,
-      creatorFeeBps: state.creatorFeeBps,
-      tokensSold: state.tokensSold.toString(),
-      reservesLamports: state.reservesLamports.toString()
-    });
-    
-  } catch (error) {
-    console.error("Error:", error);
-    if (error.logs) {
-      console.error("Program logs:", error.logs);
-    }
-  }
-}
-
-main();
-`;
-    
-    fs.writeFileSync(anchorInitPath, anchorInitScript);
-    console.log(`\nCreated ${anchorInitPath} script.`);
-    
-    // Create script for buying tokens
-    const anchorBuyPath = path.join(__dirname, 'anchor-buy.js');
-    const anchorBuyScript = `
-const anchor = require('@coral-xyz/anchor');
-const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
-const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } = require('@solana/spl-token');
-
-// Get the mint address from command line
-const mintAddress = process.argv[2];
-if (!mintAddress) {
-  console.error("Please provide a mint address as argument");
-  process.exit(1);
-}
-
-// Amount to buy (in token units)
-const amountToBuy = new anchor.BN(5_000_000); // 5 tokens (assuming 6 decimals)
-const maxCostLamports = new anchor.BN(10_000_000_000); // 10 SOL (max willing to pay)
-
-// Set up the provider
-const provider = anchor.AnchorProvider.env();
-anchor.setProvider(provider);
-
-// Load the IDL
-const idl = require('../target/idl/grokpad.json');
-const programId = PROGRAM_ID;
-const program = new anchor.Program(idl, programId, provider);
-
-async function main() {
-  try {
-    const mint = new PublicKey(mintAddress);
-    console.log("Using mint:", mint.toString());
-    
-    // Derive PDAs
-    const [authorityPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("launch"), mint.toBuffer()], 
-      programId
-    );
-    
-    const [statePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("launch_state"), mint.toBuffer()],
-      programId
-    );
-    
-    const [vaultSolPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault_sol"), mint.toBuffer()],
-      programId
-    );
-    
-    // Get vault ATA
-    const vaultAta = await anchor.utils.token.associatedAddress({
-      mint: mint,
-      owner: authorityPda
-    });
-    
-    // Get buyer ATA
-    const buyerAta = await anchor.utils.token.associatedAddress({
-      mint: mint,
-      owner: provider.wallet.publicKey
-    });
-    
-    console.log("Buying tokens...");
-    console.log("Amount:", amountToBuy.toString());
-    console.log("Max cost:", maxCostLamports.toString(), "lamports");
-    
-    const tx = await program.methods
-      .buy(
-        amountToBuy,
-        maxCostLamports
-      )
-      .accounts({
-        buyer: provider.wallet.publicKey,
-        mint: mint,
-        statePda: statePda,
-        authorityPda: authorityPda,
-        vaultSolPda: vaultSolPda,
-        vaultAta: vaultAta,
-        buyerAta: buyerAta,
-        platformFeeRecipient: provider.wallet.publicKey,
-        creator: provider.wallet.publicKey,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        rent: SYSVAR_RENT_PUBKEY
-      })
-      .rpc();
-    
-    console.log("Buy transaction successful! Signature:", tx);
-    
-    // Get the state account after purchase
-    const state = await program.account.launchState.fetch(statePda);
-    console.log("Updated launch state:", {
-      tokensSold: state.tokensSold.toString(),
-      reservesLamports: state.reservesLamports.toString(),
-      platformFeeAccrued: state.platformFeeAccrued.toString(),
-      creatorFeeAccrued: state.creatorFeeAccrued.toString()
-    });
-    
-  } catch (error) {
-    console.error("Error:", error);
-    if (error.logs) {
-      console.error("Program logs:", error.logs);
-    }
-  }
-}
-
-main();
-`;
-    
-    fs.writeFileSync(anchorBuyPath, anchorBuyScript);
-    console.log(`Created ${anchorBuyPath} script.`);
-    
-    // Create sell script
-    const anchorSellPath = path.join(__dirname, 'anchor-sell.js');
-    const anchorSellScript = `
-const anchor = require('@coral-xyz/anchor');
-const { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
-const { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } = require('@solana/spl-token');
-
-// Get the mint address from command line
-const mintAddress = process.argv[2];
-if (!mintAddress) {
-  console.error("Please provide a mint address as argument");
-  process.exit(1);
-}
-
-// Set up the provider
-const provider = anchor.AnchorProvider.env();
-anchor.setProvider(provider);
-
-// Load the IDL
-const idl = require('../target/idl/grokpad.json');
-const programId = PROGRAM_ID;
-const program = new anchor.Program(idl, programId, provider);
-
-async function main() {
-  try {
-    const mint = new PublicKey(mintAddress);
-    console.log("Using mint:", mint.toString());
-    
-    // Derive PDAs
-    const [authorityPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("launch"), mint.toBuffer()], 
-      programId
-    );
-    
-    const [statePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("launch_state"), mint.toBuffer()],
-      programId
-    );
-    
-    const [vaultSolPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault_sol"), mint.toBuffer()],
-      programId
-    );
-    
-    // Get vault ATA
-    const vaultAta = await anchor.utils.token.associatedAddress({
-      mint: mint,
-      owner: authorityPda
-    });
-    
-    // Get seller ATA
-    const sellerAta = await anchor.utils.token.associatedAddress({
-      mint: mint,
-      owner: provider.wallet.publicKey
-    });
-    
-    // Get current token balance
-    const connection = provider.connection;
-    const tokenAccount = await getAccount(connection, sellerAta);
-    console.log("Current token balance:", tokenAccount.amount.toString());
-    
-    // Calculate amount to sell (half of current balance)
-    const sellAmount = new anchor.BN(Math.floor(Number(tokenAccount.amount) / 2));
-    const minPayoutLamports = new anchor.BN(100_000); // 0.0001 SOL minimum payout
-    
-    if (sellAmount.lte(new anchor.BN(0))) {
-      console.log("No tokens to sell");
-      return;
-    }
-    
-    console.log("Selling tokens...");
-    console.log("Amount to sell:", sellAmount.toString());
-    console.log("Min payout:", minPayoutLamports.toString(), "lamports");
-    
-    const tx = await program.methods
-      .sell(
-        sellAmount,
-        minPayoutLamports
-      )
-      .accounts({
-        seller: provider.wallet.publicKey,
-        mint: mint,
-        statePda: statePda,
-        authorityPda: authorityPda,
-        vaultSolPda: vaultSolPda,
-        vaultAta: vaultAta,
-        sellerAta: sellerAta,
-        platformFeeRecipient: provider.wallet.publicKey,
-        creator: provider.wallet.publicKey,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        rent: SYSVAR_RENT_PUBKEY
-      })
-      .rpc();
-    
-    console.log("Sell transaction successful! Signature:", tx);
-    
-    // Get the state account after sale
-    const state = await program.account.launchState.fetch(statePda);
-    console.log("Updated launch state:", {
-      tokensSold: state.tokensSold.toString(),
-      reservesLamports: state.reservesLamports.toString(),
-      platformFeeAccrued: state.platformFeeAccrued.toString(),
-      creatorFeeAccrued: state.creatorFeeAccrued.toString()
-    });
-    
-    // Check new token balance
-    const newTokenAccount = await getAccount(connection, sellerAta);
-    console.log("New token balance:", newTokenAccount.amount.toString());
-    
-  } catch (error) {
-    console.error("Error:", error);
-    if (error.logs) {
-      console.error("Program logs:", error.logs);
-    }
-  }
-}
-
-main();
-`;
-    
-    fs.writeFileSync(anchorSellPath, anchorSellScript);
-    console.log(`Created ${anchorSellPath} script.`);
-    
     // Create withdraw fees script
     const anchorWithdrawPath = path.join(__dirname, 'anchor-withdraw.js');
     const anchorWithdrawScript = `
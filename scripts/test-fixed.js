@@ -3,30 +3,26 @@ const {
   PublicKey, 
   Keypair,
   SystemProgram, 
-  SYSVAR_RENT_PUBKEY,
   Transaction,
   sendAndConfirmTransaction
 } = require('@solana/web3.js');
 const { 
-  TOKEN_PROGRAM_ID, 
-  ASSOCIATED_TOKEN_PROGRAM_ID, 
   getOrCreateAssociatedTokenAccount, 
   getAccount,
-  createAssociatedTokenAccount,
+  createMint,
   mintTo
 } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
 const { PROGRAM_ID, CLUSTER_URL } = require('./config');
+const { GraduationCriterion, launchPdas, initializeLaunchIx, fundCurveIx, buyIx, sellIx } = require('./grokpad-ix');
 
 // Configure constants
 const USE_EXISTING_MINT = true;
 const EXISTING_MINT = "FF5Khx5KBpyZKurFM4zUe2L4C7FWzAAZ92sP7mEWqvCJ";
 
-// Instruction discriminators (these are from the Anchor IDL)
-const DISCRIMINATOR_INITIALIZE = Buffer.from([30, 120, 39, 212, 120, 168, 29, 81]); // anchor.sighash("global:initializeLaunch")
-const DISCRIMINATOR_BUY = Buffer.from([103, 17, 200, 25, 118, 95, 125, 61]); // anchor.sighash("global:buy")
-const DISCRIMINATOR_SELL = Buffer.from([168, 86, 144, 193, 84, 236, 124, 112]); // anchor.sighash("global:sell")
+// Instruction discriminators (these are from the Anchor IDL); launch and
+// trade instructions are built by grokpad-ix
 const DISCRIMINATOR_WITHDRAW_FEES = Buffer.from([249, 77, 180, 202, 241, 88, 221, 124]); // anchor.sighash("global:withdrawFees")
 const DISCRIMINATOR_GRADUATE = Buffer.from([175, 69, 176, 246, 204, 41, 172, 222]); // anchor.sighash("global:graduate")
 
@@ -84,39 +80,34 @@ async function main() {
     );
     console.log('Vault SOL PDA:', vaultSolPda.toString());
     
-    // Get vault ATA
-    const vaultAta = await getOrCreateAssociatedTokenAccount(
+    // Get vault ATA; initialize_launch creates it
+    const { vaultAta } = launchPdas(programId, mint);
+    console.log('Vault ATA:', vaultAta.toString());
+    
+    // Get buyer's ATA, which is also the creator's
+    const buyerAta = await getOrCreateAssociatedTokenAccount(
       connection,
       wallet,
       mint,
-      authorityPda,
-      true
+      wallet.publicKey
     );
-    console.log('Vault ATA:', vaultAta.address.toString());
+    console.log('Buyer ATA:', buyerAta.address.toString());
     
-    // If not using existing mint, mint initial supply
+    // If not using existing mint, mint initial supply to the creator
+    const initialSupply = 1000000000000; // 1000 tokens with 9 decimals
     if (!USE_EXISTING_MINT) {
       console.log('Minting initial supply...');
       await mintTo(
         connection,
         wallet,
         mint,
-        vaultAta.address,
+        buyerAta.address,
         wallet.publicKey,
-        1000000000000 // 1000 tokens with 9 decimals
+        initialSupply
       );
       console.log('Initial supply minted');
     }
     
-    // Get buyer's ATA
-    const buyerAta = await getOrCreateAssociatedTokenAccount(
-      connection,
-      wallet,
-      mint,
-      wallet.publicKey
-    );
-    console.log('Buyer ATA:', buyerAta.address.toString());
-    
     // Check if state account exists
     const stateInfo = await connection.getAccountInfo(statePda);
     
@@ -126,50 +117,32 @@ async function main() {
     } else {
       console.log('State account does not exist, initializing launch...');
       
-      // Initialize launch parameters
-      const basePriceLamports = 1_000_000; // 0.001 SOL
-      const slopeLamports = 100_000; // 0.0001 SOL per token
-      const feeBps = 300; // 3.00%
+      // Initialize launch parameters. The platform fee and its recipient
+      // come from the global config.
+      const basePriceLamports = 1_000_000; // 0.001 SOL per whole token
+      const slopeLamports = 100_000; // +0.0001 SOL per whole token sold
       const creatorFeeBps = 100; // 1.00%
       const graduationTargetLamports = 2 * 1_000_000_000; // 2 SOL
       
-      // Construct initialization data
-      const initData = Buffer.alloc(8 + 8 + 8 + 2 + 2 + 8);
-      
-      // Write discriminator
-      DISCRIMINATOR_INITIALIZE.copy(initData, 0);
-      
-      // Write params
-      let offset = 8;
-      initData.writeBigUInt64LE(BigInt(basePriceLamports), offset); offset += 8;
-      initData.writeBigUInt64LE(BigInt(slopeLamports), offset); offset += 8;
-      initData.writeUInt16LE(feeBps, offset); offset += 2;
-      initData.writeUInt16LE(creatorFeeBps, offset); offset += 2;
-      initData.writeBigUInt64LE(BigInt(graduationTargetLamports), offset);
-      
-      // Create instruction
-      const initIx = {
-        programId,
-        keys: [
-          { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // payer
-          { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-          { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-          { pubkey: mint, isSigner: false, isWritable: false }, // mint
-          { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-          { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-          { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-          { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-          { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-          { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-        ],
-        data: initData
-      };
+      // Create instructions: initialize, then move the supply into the vault
+      const initIx = initializeLaunchIx(programId, {
+        payer: wallet.publicKey,
+        creator: wallet.publicKey,
+        mint,
+        basePriceLamports,
+        slopeLamports,
+        creatorFeeBps,
+        graduationCriteria: [GraduationCriterion.reserves(graduationTargetLamports)],
+      });
+      const fundIx = fundCurveIx(programId, {
+        creator: wallet.publicKey,
+        mint,
+        amount: (await getAccount(connection, buyerAta.address)).amount,
+      });
       
       // Send transaction
       console.log('Sending initialization transaction...');
-      const initTx = new Transaction().add(initIx);
+      const initTx = new Transaction().add(initIx, fundIx);
       const initSig = await sendAndConfirmTransaction(connection, initTx, [wallet]);
       console.log('Initialization transaction sent! Signature:', initSig);
     }
@@ -181,42 +154,18 @@ async function main() {
     const buyAmount = 5_000_000_000; // 5 tokens with 9 decimals
     const maxCostLamports = 10_000_000_000; // 10 SOL max cost
     
-    // Construct buy data
-    const buyData = Buffer.alloc(8 + 8 + 8);
-    
-    // Write discriminator
-    DISCRIMINATOR_BUY.copy(buyData, 0);
-    
-    // Write params
-    let buyOffset = 8;
-    buyData.writeBigUInt64LE(BigInt(buyAmount), buyOffset); buyOffset += 8;
-    buyData.writeBigUInt64LE(BigInt(maxCostLamports), buyOffset);
-    
     // Create instruction
-    const buyIx = {
-      programId,
-      keys: [
-        { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // buyer
-        { pubkey: mint, isSigner: false, isWritable: false }, // mint
-        { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-        { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-        { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-        { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-        { pubkey: buyerAta.address, isSigner: false, isWritable: true }, // buyer_ata
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-        { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-      ],
-      data: buyData
-    };
+    const buyInstruction = buyIx(programId, {
+      buyer: wallet.publicKey,
+      mint,
+      amount: buyAmount,
+      maxCostLamports,
+    });
     
     // Send transaction
     console.log('Sending buy transaction...');
     try {
-      const buyTx = new Transaction().add(buyIx);
+      const buyTx = new Transaction().add(buyInstruction);
       const buySig = await sendAndConfirmTransaction(connection, buyTx, [wallet]);
       console.log('Buy transaction sent! Signature:', buySig);
       
@@ -245,40 +194,16 @@ async function main() {
         
         console.log('Selling', sellAmount.toString(), 'tokens...');
         
-        // Construct sell data
-        const sellData = Buffer.alloc(8 + 8 + 8);
-        
-        // Write discriminator
-        DISCRIMINATOR_SELL.copy(sellData, 0);
-        
-        // Write params
-        let sellOffset = 8;
-        sellData.writeBigUInt64LE(sellAmount, sellOffset); sellOffset += 8;
-        sellData.writeBigUInt64LE(BigInt(minPayoutLamports), sellOffset);
-        
         // Create instruction
-        const sellIx = {
-          programId,
-          keys: [
-            { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // seller
-            { pubkey: mint, isSigner: false, isWritable: false }, // mint
-            { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-            { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-            { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-            { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-            { pubkey: buyerAta.address, isSigner: false, isWritable: true }, // seller_ata
-            { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-            { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-            { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-            { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-          ],
-          data: sellData
-        };
+        const sellInstruction = sellIx(programId, {
+          seller: wallet.publicKey,
+          mint,
+          amount: sellAmount,
+          minPayoutLamports,
+        });
         
         // Send transaction
-        const sellTx = new Transaction().add(sellIx);
+        const sellTx = new Transaction().add(sellInstruction);
         const sellSig = await sendAndConfirmTransaction(connection, sellTx, [wallet]);
         console.log('Sell transaction sent! Signature:', sellSig);
         
@@ -339,37 +264,13 @@ async function main() {
       const largeBuyAmount = 1_000_000_000_000; // 1000 tokens
       const largeMaxCostLamports = 100_000_000_000; // 100 SOL max cost
       
-      // Construct buy data
-      const largeBuyData = Buffer.alloc(8 + 8 + 8);
-      
-      // Write discriminator
-      DISCRIMINATOR_BUY.copy(largeBuyData, 0);
-      
-      // Write params
-      let largeBuyOffset = 8;
-      largeBuyData.writeBigUInt64LE(BigInt(largeBuyAmount), largeBuyOffset); largeBuyOffset += 8;
-      largeBuyData.writeBigUInt64LE(BigInt(largeMaxCostLamports), largeBuyOffset);
-      
       // Create instruction
-      const largeBuyIx = {
-        programId,
-        keys: [
-          { pubkey: wallet.publicKey, isSigner: true, isWritable: true }, // buyer
-          { pubkey: mint, isSigner: false, isWritable: false }, // mint
-          { pubkey: statePda, isSigner: false, isWritable: true }, // state_pda
-          { pubkey: authorityPda, isSigner: false, isWritable: false }, // authority_pda
-          { pubkey: vaultSolPda, isSigner: false, isWritable: true }, // vault_sol_pda
-          { pubkey: vaultAta.address, isSigner: false, isWritable: true }, // vault_ata
-          { pubkey: buyerAta.address, isSigner: false, isWritable: true }, // buyer_ata
-          { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // platform_fee_recipient
-          { pubkey: wallet.publicKey, isSigner: false, isWritable: false }, // creator
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // token_program
-          { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // associated_token_program
-          { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false } // rent
-        ],
-        data: largeBuyData
-      };
+      const largeBuyIx = buyIx(programId, {
+        buyer: wallet.publicKey,
+        mint,
+        amount: largeBuyAmount,
+        maxCostLamports: largeMaxCostLamports,
+      });
       
       // Send transaction
       const largeBuyTx = new Transaction().add(largeBuyIx);
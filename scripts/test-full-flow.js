@@ -5,6 +5,7 @@ const anchor = require('@coral-xyz/anchor');
 const fs = require('fs');
 const path = require('path');
 const { PROGRAM_ID, CLUSTER_URL } = require('./config');
+const { quoteBuy, quoteSell, curveState } = require('./grokpad-math');
 
 // Load the IDL
 const idlPath = path.join(__dirname, '..', 'grokpad-idl.json');
@@ -53,7 +54,6 @@ async function main() {
     // Launch parameters
     const basePriceLamports = new BN(10_000_000); // 0.01 SOL base price
     const slopeLamports = new BN(100_000); // 0.0001 SOL per token price increase
-    const feeBps = 50; // 0.5% platform fee
     const creatorFeeBps = 250; // 2.5% creator fee
     const graduationTargetLamports = new BN(1_000_000_000); // 1 SOL graduation target
     
@@ -73,54 +73,55 @@ async function main() {
     const [statePda, stateBump] = await findPDA([STATE_SEED, mint.toBuffer()]);
     const [vaultSolPda, vaultSolBump] = await findPDA([VAULT_SOL_SEED, mint.toBuffer()]);
     
-    // Create vault ATA
+    const [globalConfig] = await findPDA([Buffer.from('config')]);
+    
+    // Vault ATA, created by initialize_launch
     const vaultAta = await getAssociatedTokenAddress(mint, authorityPda, true);
     console.log('Vault ATA:', vaultAta.toString());
     
-    // Create vault ATA
-    await createAssociatedTokenAccount(
+    // 2. Initialize launch
+    console.log('Initializing launch...');
+    
+    // Create separate platform fee recipient and creator wallets. Curve
+    // fees go to the config's treasury; the recipient is only used to
+    // withdraw below.
+    const platformFeeRecipient = Keypair.generate();
+    const creator = Keypair.generate();
+    
+    // Fund these wallets with some SOL
+    await connection.requestAirdrop(platformFeeRecipient.publicKey, 1_000_000_000);
+    await connection.requestAirdrop(creator.publicKey, 1_000_000_000);
+    
+    // Mint tokens to the creator, who funds the curve with them
+    const creatorAta = await createAssociatedTokenAccount(
       connection,
       payer,
       mint,
-      authorityPda,
-      true
+      creator.publicKey
     );
-    
-    // Mint tokens to vault
     await mintTo(
       connection,
       payer,
       mint,
-      vaultAta,
+      creatorAta,
       payer, // mint authority
-      initialSupply.toNumber()
+      BigInt(initialSupply.toString())
     );
-    console.log('Minted', initialSupply.toString(), 'tokens to vault');
-    
-    // 2. Initialize launch
-    console.log('Initializing launch...');
-    
-    // Create separate platform fee recipient and creator wallets
-    const platformFeeRecipient = Keypair.generate();
-    const creator = Keypair.generate();
-    
-    // Fund these wallets with some SOL
-    await connection.requestAirdrop(platformFeeRecipient.publicKey, 1_000_000_000);
-    await connection.requestAirdrop(creator.publicKey, 1_000_000_000);
+    console.log('Minted', initialSupply.toString(), 'tokens to creator');
     
     // Initialize the launch
     await program.methods
       .initializeLaunch(
         basePriceLamports,
         slopeLamports,
-        feeBps,
         creatorFeeBps,
-        graduationTargetLamports
+        [{ reserves: { lamports: graduationTargetLamports } }],
+        false
       )
       .accounts({
         payer: payer.publicKey,
         creator: creator.publicKey,
-        platformFeeRecipient: platformFeeRecipient.publicKey,
+        globalConfig: globalConfig,
         mint: mint,
         authorityPda: authorityPda,
         statePda: statePda,
@@ -135,6 +136,25 @@ async function main() {
     
     console.log('Launch initialized');
     
+    // Move the supply into the vault to open the curve
+    await program.methods
+      .fundCurve(initialSupply)
+      .accounts({
+        creator: creator.publicKey,
+        mint: mint,
+        globalConfig: globalConfig,
+        statePda: statePda,
+        authorityPda: authorityPda,
+        creatorAta: creatorAta,
+        vaultAta: vaultAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID
+      })
+      .signers([creator])
+      .rpc();
+    
+    console.log('Curve funded');
+    
     // Fetch the state
     const state = await program.account.launchState.fetch(statePda);
     console.log('Initial state:', {
@@ -142,7 +162,7 @@ async function main() {
       slopeLamports: state.slopeLamports.toString(),
       feeBps: state.feeBps,
       creatorFeeBps: state.creatorFeeBps,
-      graduationTargetLamports: state.graduationTargetLamports.toString(),
+      graduationCriteria: state.graduationCriteria.slice(0, state.graduationCriteriaLen),
       supplyRemaining: state.supplyRemaining.toString()
     });
     
@@ -168,7 +188,12 @@ async function main() {
     
     // Buy tokens
     const buyAmount = new BN(1_000_000).mul(new BN(10).pow(new BN(decimals))); // 1 million tokens
-    const maxCostLamports = new BN(100_000_000_000); // 100 SOL max cost (high slippage tolerance)
+    // Quote with the program's math, allowing 1% slippage on top
+    const maxCost = (amount) => program.account.launchState.fetch(statePda).then((launchState) => {
+      const { cost } = quoteBuy(curveState(launchState), BigInt(amount.toString()));
+      return new BN((cost + cost / 100n).toString());
+    });
+    const maxCostLamports = await maxCost(buyAmount);
     
     await program.methods
       .buy(
@@ -178,13 +203,13 @@ async function main() {
       .accounts({
         buyer: buyer.publicKey,
         mint: mint,
+        globalConfig: globalConfig,
         statePda: statePda,
         authorityPda: authorityPda,
         vaultSolPda: vaultSolPda,
         vaultAta: vaultAta,
-        buyerAta: buyerAta,
-        platformFeeRecipient: platformFeeRecipient.publicKey,
-        creator: creator.publicKey,
+        recipient: buyer.publicKey,
+        recipientAta: buyerAta,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -209,7 +234,8 @@ async function main() {
     
     // Sell half of the bought tokens
     const sellAmount = buyAmount.div(new BN(2));
-    const minPayoutLamports = new BN(1_000_000); // 0.001 SOL minimum payout
+    const { refundNet } = quoteSell(curveState(await program.account.launchState.fetch(statePda)), BigInt(sellAmount.toString()));
+    const minPayoutLamports = new BN((refundNet - refundNet / 100n).toString()); // 1% slippage
     
     await program.methods
       .sell(
@@ -219,13 +245,12 @@ async function main() {
       .accounts({
         seller: buyer.publicKey,
         mint: mint,
+        globalConfig: globalConfig,
         statePda: statePda,
         authorityPda: authorityPda,
         vaultSolPda: vaultSolPda,
         vaultAta: vaultAta,
         sellerAta: buyerAta,
-        platformFeeRecipient: platformFeeRecipient.publicKey,
-        creator: creator.publicKey,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -291,18 +316,18 @@ async function main() {
     await program.methods
       .buy(
         largeAmount,
-        maxCostLamports
+        await maxCost(largeAmount)
       )
       .accounts({
         buyer: buyer.publicKey,
         mint: mint,
+        globalConfig: globalConfig,
         statePda: statePda,
         authorityPda: authorityPda,
         vaultSolPda: vaultSolPda,
         vaultAta: vaultAta,
-        buyerAta: buyerAta,
-        platformFeeRecipient: platformFeeRecipient.publicKey,
-        creator: creator.publicKey,
+        recipient: buyer.publicKey,
+        recipientAta: buyerAta,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -322,7 +347,7 @@ async function main() {
     
     // 7. Call graduate explicitly
     if (!stateAfterLargeBuy.graduated && 
-        stateAfterLargeBuy.reservesLamports.gte(stateAfterLargeBuy.graduationTargetLamports)) {
+        stateAfterLargeBuy.reservesLamports.gte(graduationTargetLamports)) {
       console.log('Calling graduate explicitly...');
       
       await program.methods